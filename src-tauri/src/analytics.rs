@@ -0,0 +1,401 @@
+// Dönemlik performans analizi: puan serileri, hareketli ortalama, dağılımlar
+// ve gelişim / risk listeleri. Çıktılar grafiklere doğrudan verilebilecek
+// biçimde (etiketler + veri dizileri) döner.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::Datelike;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::parse_date;
+use crate::open_conn;
+use crate::query::{filter_clause, InternFilter};
+
+const DEFAULT_WINDOW: usize = 3;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct AnalyticsQuery {
+    pub filter: InternFilter,
+    // değerlendirme tarihi aralığı (YYYY-MM-DD)
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    Department,
+    School,
+    // değerlendirmenin yapıldığı ay (YYYY-MM)
+    Month,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Bucket {
+    Week,
+    #[default]
+    Month,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScorePoint {
+    pub id: i64,
+    pub date: String,
+    pub label: String,
+    pub score: i64,
+    pub moving_average: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Stats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p90: f64,
+    pub min: i64,
+    pub max: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DistributionGroup {
+    pub key: String,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChartDataset {
+    pub label: String,
+    pub data: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChartSeries {
+    pub labels: Vec<String>,
+    pub datasets: Vec<ChartDataset>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InternTrend {
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub department: String,
+    pub school: String,
+    pub evaluations: usize,
+    pub first_average: f64,
+    pub recent_average: f64,
+    // haftalık puan değişimi (en küçük kareler eğimi)
+    pub slope_per_week: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImprovementReport {
+    pub most_improved: Vec<InternTrend>,
+    pub at_risk: Vec<InternTrend>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ImprovementOptions {
+    pub min_evaluations: usize,
+    // ilk / son ortalama için kullanılan değerlendirme sayısı
+    pub window: usize,
+    pub limit: usize,
+    // son ortalaması bunun altında kalanlar risk listesine girer
+    pub at_risk_below: f64,
+}
+
+impl Default for ImprovementOptions {
+    fn default() -> Self {
+        ImprovementOptions { min_evaluations: 3, window: DEFAULT_WINDOW, limit: 10, at_risk_below: 60.0 }
+    }
+}
+
+struct EvalRow {
+    intern_id: i64,
+    intern_name: String,
+    department: String,
+    school: String,
+    date: String,
+    score: i64,
+}
+
+fn load_rows(conn: &Connection, q: &AnalyticsQuery) -> Result<Vec<EvalRow>, String> {
+    use rusqlite::types::Value;
+
+    let (where_sql, mut vals) = filter_clause(&q.filter);
+    let mut conds = vec![where_sql];
+    if let Some(from) = q.from.as_deref().filter(|s| !s.trim().is_empty()) {
+        vals.push(Value::Text(from.trim().to_string()));
+        conds.push(format!("date(e.created_at) >= ?{}", vals.len()));
+    }
+    if let Some(to) = q.to.as_deref().filter(|s| !s.trim().is_empty()) {
+        vals.push(Value::Text(to.trim().to_string()));
+        conds.push(format!("date(e.created_at) <= ?{}", vals.len()));
+    }
+
+    let sql = format!(
+        r#"
+        SELECT e.intern_id, i.first_name || ' ' || i.last_name, i.department, i.school,
+               date(e.created_at), e.score
+        FROM evaluations e
+        JOIN interns i ON i.id = e.intern_id
+        WHERE {}
+        ORDER BY e.intern_id, e.created_at, e.id
+        "#,
+        conds.join(" AND ")
+    );
+    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params_from_iter(vals.iter()), |row| {
+        Ok(EvalRow {
+            intern_id: row.get(0)?,
+            intern_name: row.get(1)?,
+            department: row.get(2)?,
+            school: row.get(3)?,
+            date: row.get(4)?,
+            score: row.get(5)?,
+        })
+    }).map_err(|e| e.to_string())?;
+
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+fn mean(xs: &[i64]) -> f64 {
+    if xs.is_empty() { 0.0 } else { xs.iter().sum::<i64>() as f64 / xs.len() as f64 }
+}
+
+// doğrusal enterpolasyonlu yüzdelik (sıralı dizi üzerinde)
+fn percentile(sorted: &[i64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    let frac = rank - lo as f64;
+    sorted[lo] as f64 + (sorted[hi] - sorted[lo]) as f64 * frac
+}
+
+pub(crate) fn stats(scores: &[i64]) -> Stats {
+    let mut sorted = scores.to_vec();
+    sorted.sort_unstable();
+    Stats {
+        count: sorted.len(),
+        mean: round2(mean(&sorted)),
+        median: round2(percentile(&sorted, 0.5)),
+        p25: round2(percentile(&sorted, 0.25)),
+        p75: round2(percentile(&sorted, 0.75)),
+        p90: round2(percentile(&sorted, 0.9)),
+        min: sorted.first().copied().unwrap_or(0),
+        max: sorted.last().copied().unwrap_or(0),
+    }
+}
+
+fn round2(x: f64) -> f64 {
+    (x * 100.0).round() / 100.0
+}
+
+fn group_key(r: &EvalRow, by: GroupBy) -> String {
+    match by {
+        GroupBy::Department => r.department.clone(),
+        GroupBy::School => r.school.clone(),
+        GroupBy::Month => r.date.get(..7).unwrap_or(&r.date).to_string(),
+    }
+}
+
+fn bucket_key(date: &str, bucket: Bucket) -> String {
+    match bucket {
+        Bucket::Month => date.get(..7).unwrap_or(date).to_string(),
+        Bucket::Week => match parse_date(date) {
+            Ok(d) => {
+                let w = d.iso_week();
+                format!("{}-W{:02}", w.year(), w.week())
+            }
+            Err(_) => date.to_string(),
+        },
+    }
+}
+
+// en küçük kareler eğimi: puan / gün
+fn slope_per_day(points: &[(f64, f64)]) -> f64 {
+    if points.len() < 2 {
+        return 0.0;
+    }
+    let n = points.len() as f64;
+    let mx = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let my = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut num, mut den) = (0.0, 0.0);
+    for (x, y) in points {
+        num += (x - mx) * (y - my);
+        den += (x - mx) * (x - mx);
+    }
+    if den == 0.0 { 0.0 } else { num / den }
+}
+
+pub(crate) fn score_series_conn(conn: &Connection, intern_id: i64, window: usize) -> Result<Vec<ScorePoint>, String> {
+    let window = window.max(1);
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, date(created_at), label, score
+        FROM evaluations
+        WHERE intern_id = ?1
+        ORDER BY created_at, id
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![intern_id], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?))
+    }).map_err(|e| e.to_string())?;
+
+    let mut scores = Vec::new();
+    let mut out = Vec::new();
+    for r in rows {
+        let (id, date, label, score) = r.map_err(|e| e.to_string())?;
+        scores.push(score);
+        let tail = &scores[scores.len().saturating_sub(window)..];
+        out.push(ScorePoint { id, date, label, score, moving_average: round2(mean(tail)) });
+    }
+    Ok(out)
+}
+
+pub(crate) fn distribution_conn(conn: &Connection, q: &AnalyticsQuery, by: GroupBy) -> Result<Vec<DistributionGroup>, String> {
+    let mut groups: BTreeMap<String, Vec<i64>> = BTreeMap::new();
+    for r in load_rows(conn, q)? {
+        groups.entry(group_key(&r, by)).or_default().push(r.score);
+    }
+    Ok(groups
+        .into_iter()
+        .map(|(key, scores)| DistributionGroup { key, stats: stats(&scores) })
+        .collect())
+}
+
+pub(crate) fn trend_chart_conn(
+    conn: &Connection,
+    q: &AnalyticsQuery,
+    bucket: Bucket,
+    split_by: Option<GroupBy>,
+) -> Result<ChartSeries, String> {
+    let rows = load_rows(conn, q)?;
+
+    let mut labels: Vec<String> = rows.iter().map(|r| bucket_key(&r.date, bucket)).collect();
+    labels.sort();
+    labels.dedup();
+    let index: HashMap<&str, usize> = labels.iter().enumerate().map(|(i, l)| (l.as_str(), i)).collect();
+
+    // seri adı -> her kova için puanlar
+    let mut sums: BTreeMap<String, Vec<Vec<i64>>> = BTreeMap::new();
+    for r in &rows {
+        let name = match split_by {
+            Some(by) => group_key(r, by),
+            None => "Ortalama".to_string(),
+        };
+        let slot = index[bucket_key(&r.date, bucket).as_str()];
+        sums.entry(name).or_insert_with(|| vec![Vec::new(); labels.len()])[slot].push(r.score);
+    }
+
+    let datasets = sums
+        .into_iter()
+        .map(|(label, buckets)| ChartDataset {
+            label,
+            data: buckets
+                .iter()
+                .map(|b| if b.is_empty() { None } else { Some(round2(mean(b))) })
+                .collect(),
+        })
+        .collect();
+
+    Ok(ChartSeries { labels, datasets })
+}
+
+pub(crate) fn improvement_conn(conn: &Connection, q: &AnalyticsQuery, opts: &ImprovementOptions) -> Result<ImprovementReport, String> {
+    let window = opts.window.max(1);
+    let mut by_intern: BTreeMap<i64, Vec<EvalRow>> = BTreeMap::new();
+    for r in load_rows(conn, q)? {
+        by_intern.entry(r.intern_id).or_default().push(r);
+    }
+
+    let mut trends = Vec::new();
+    for (intern_id, rows) in by_intern {
+        if rows.len() < opts.min_evaluations.max(1) {
+            continue;
+        }
+        let scores: Vec<i64> = rows.iter().map(|r| r.score).collect();
+        let first = parse_date(&rows[0].date).ok();
+        let points: Vec<(f64, f64)> = rows
+            .iter()
+            .map(|r| {
+                let days = match (first, parse_date(&r.date).ok()) {
+                    (Some(f), Some(d)) => (d - f).num_days() as f64,
+                    _ => 0.0,
+                };
+                (days, r.score as f64)
+            })
+            .collect();
+        let head = &scores[..window.min(scores.len())];
+        let tail = &scores[scores.len().saturating_sub(window)..];
+        trends.push(InternTrend {
+            intern_id,
+            intern_name: rows[0].intern_name.clone(),
+            department: rows[0].department.clone(),
+            school: rows[0].school.clone(),
+            evaluations: scores.len(),
+            first_average: round2(mean(head)),
+            recent_average: round2(mean(tail)),
+            slope_per_week: round2(slope_per_day(&points) * 7.0),
+        });
+    }
+
+    let gain = |t: &InternTrend| t.recent_average - t.first_average;
+
+    let mut most_improved: Vec<InternTrend> = trends.iter().filter(|t| gain(t) > 0.0).cloned().collect();
+    most_improved.sort_by(|a, b| gain(b).total_cmp(&gain(a)));
+    most_improved.truncate(opts.limit);
+
+    let mut at_risk: Vec<InternTrend> = trends
+        .into_iter()
+        .filter(|t| t.recent_average < opts.at_risk_below || (t.slope_per_week < 0.0 && gain(t) < 0.0))
+        .collect();
+    at_risk.sort_by(|a, b| a.recent_average.total_cmp(&b.recent_average));
+    at_risk.truncate(opts.limit);
+
+    Ok(ImprovementReport { most_improved, at_risk })
+}
+
+#[tauri::command]
+pub fn intern_score_series(handle: AppHandle, intern_id: i64, window: Option<usize>) -> Result<Vec<ScorePoint>, String> {
+    let conn = open_conn(&handle)?;
+    score_series_conn(&conn, intern_id, window.unwrap_or(DEFAULT_WINDOW))
+}
+
+#[tauri::command]
+pub fn score_distribution(handle: AppHandle, query: Option<AnalyticsQuery>, group_by: GroupBy) -> Result<Vec<DistributionGroup>, String> {
+    let conn = open_conn(&handle)?;
+    distribution_conn(&conn, &query.unwrap_or_default(), group_by)
+}
+
+#[tauri::command]
+pub fn score_trend_chart(
+    handle: AppHandle,
+    query: Option<AnalyticsQuery>,
+    bucket: Option<Bucket>,
+    split_by: Option<GroupBy>,
+) -> Result<ChartSeries, String> {
+    let conn = open_conn(&handle)?;
+    trend_chart_conn(&conn, &query.unwrap_or_default(), bucket.unwrap_or_default(), split_by)
+}
+
+#[tauri::command]
+pub fn improvement_report(
+    handle: AppHandle,
+    query: Option<AnalyticsQuery>,
+    options: Option<ImprovementOptions>,
+) -> Result<ImprovementReport, String> {
+    let conn = open_conn(&handle)?;
+    improvement_conn(&conn, &query.unwrap_or_default(), &options.unwrap_or_default())
+}
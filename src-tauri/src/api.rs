@@ -0,0 +1,659 @@
+// İsteğe bağlı yerel REST API: betikler ve panolar için stajyer, görev ve
+// değerlendirme verisini HTTP üzerinden açar. Varsayılan olarak kapalıdır ve
+// yalnızca 127.0.0.1'e bağlanır; yerel ağa açmak ayrıca onay ister. Uç noktalar
+// Tauri komutlarıyla aynı *_conn fonksiyonlarını çağırır, böylece doğrulama ve
+// olay/denetim kaydı ("api:<anahtar adı>" olarak) aynıdır.
+//
+// Kimlik doğrulama: Authorization: Bearer <anahtar>. Anahtarlar yalnızca
+// SHA-256 özeti olarak saklanır. Tanım: GET /openapi.json
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use rand::Rng;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    delete_assignment_conn, delete_evaluation_conn, delete_intern_conn, events, get_intern_conn, insert_assignment,
+    insert_evaluation, insert_intern, list_assignments_conn, list_evaluations_conn, list_interns_conn, open_conn,
+    save_intern_update, set_assignment_status, settings, Assignment, Evaluation, InternPayload,
+};
+
+const SETTINGS_KEY: &str = "api";
+const WORKERS: usize = 4;
+// CV / fotoğraf içeren stajyer gövdeleri için
+const MAX_BODY_BYTES: u64 = 20 * 1024 * 1024;
+
+static SERVER: Mutex<Option<Running>> = Mutex::new(None);
+// arka planda oluşan son hata; arayüz get_api_status ile okur
+// (ileti, zaman)
+static LAST_ERROR: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+fn record_error(message: String) {
+    let at = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    if let Ok(mut e) = LAST_ERROR.lock() {
+        *e = Some((message, at));
+    }
+}
+
+struct Running {
+    server: Arc<Server>,
+    address: String,
+    workers: Vec<JoinHandle<()>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    // 127.0.0.1 dışındaki adresler için açık onay
+    pub allow_remote: bool,
+    // tarayıcıdan erişecek panolar için (ör. "http://localhost:3000")
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        ApiSettings {
+            enabled: false,
+            bind_address: "127.0.0.1".to_string(),
+            port: 8787,
+            allow_remote: false,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiStatus {
+    pub running: bool,
+    pub address: Option<String>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiToken {
+    pub id: i64,
+    pub name: String,
+    // anahtarın ilk karakterleri (tanımak için)
+    pub prefix: String,
+    pub can_write: bool,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApiTokenCreated {
+    pub id: i64,
+    // yalnızca oluşturulurken gösterilir
+    pub token: String,
+}
+
+struct Caller {
+    name: String,
+    can_write: bool,
+}
+
+struct ApiError(u16, String);
+
+impl ApiError {
+    fn bad_request(msg: impl Into<String>) -> Self {
+        ApiError(400, msg.into())
+    }
+    fn not_found() -> Self {
+        ApiError(404, "Kayıt bulunamadı".to_string())
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(e: String) -> Self {
+        ApiError(400, e)
+    }
+}
+
+type ApiResult = Result<(u16, Value), ApiError>;
+
+#[derive(Deserialize)]
+struct StatusPayload {
+    status: String,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS api_tokens (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            token_hash TEXT NOT NULL UNIQUE,
+            prefix TEXT NOT NULL,
+            can_write INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            last_used_at TEXT,
+            revoked_at TEXT
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<ApiSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn validate(s: &ApiSettings) -> Result<SocketAddr, String> {
+    let ip: IpAddr = s
+        .bind_address
+        .trim()
+        .parse()
+        .map_err(|_| format!("Geçersiz dinleme adresi: {}", s.bind_address))?;
+    if !ip.is_loopback() && !s.allow_remote {
+        return Err("Yerel ağa açmak için uzak erişime izin verilmeli".to_string());
+    }
+    if s.port < 1024 {
+        return Err("Port 1024 ile 65535 arasında olmalı".to_string());
+    }
+    Ok(SocketAddr::new(ip, s.port))
+}
+
+fn authenticate(conn: &Connection, req: &Request) -> Result<Caller, ApiError> {
+    let header = req
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .map(|h| h.value.as_str().to_string())
+        .ok_or_else(|| ApiError(401, "Kimlik doğrulama gerekli".to_string()))?;
+    let token = header
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .ok_or_else(|| ApiError(401, "Bearer anahtarı bekleniyor".to_string()))?;
+    let (id, name, can_write): (i64, String, i64) = conn
+        .query_row(
+            "SELECT id, name, can_write FROM api_tokens WHERE token_hash = ?1 AND revoked_at IS NULL",
+            params![hash_token(token)],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| ApiError(500, e.to_string()))?
+        .ok_or_else(|| ApiError(401, "Geçersiz ya da iptal edilmiş anahtar".to_string()))?;
+    conn.execute("UPDATE api_tokens SET last_used_at = datetime('now') WHERE id = ?1", params![id])
+        .map_err(|e| ApiError(500, e.to_string()))?;
+    Ok(Caller { name, can_write: can_write == 1 })
+}
+
+fn read_body<T: serde::de::DeserializeOwned>(req: &mut Request) -> Result<T, ApiError> {
+    let mut raw = Vec::new();
+    req.as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut raw)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+    if raw.len() as u64 > MAX_BODY_BYTES {
+        return Err(ApiError(413, "İstek gövdesi çok büyük".to_string()));
+    }
+    serde_json::from_slice(&raw).map_err(|e| ApiError(422, format!("Geçersiz JSON: {e}")))
+}
+
+fn query_i64(query: &HashMap<&str, &str>, key: &str) -> Result<Option<i64>, ApiError> {
+    query
+        .get(key)
+        .map(|v| v.parse().map_err(|_| ApiError::bad_request(format!("{key} sayı olmalı"))))
+        .transpose()
+}
+
+fn to_value<T: Serialize>(v: &T) -> Result<Value, ApiError> {
+    serde_json::to_value(v).map_err(|e| ApiError(500, e.to_string()))
+}
+
+// Yazma işlemleri tek işlemde ve çağıranın adıyla
+fn write<T>(handle: &AppHandle, caller: &Caller, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, ApiError> {
+    if !caller.can_write {
+        return Err(ApiError(403, "Bu anahtarın yazma yetkisi yok".to_string()));
+    }
+    let mut conn = open_conn(handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let out = events::as_actor(&format!("api:{}", caller.name), || f(&tx))?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+fn route(handle: &AppHandle, req: &mut Request) -> ApiResult {
+    let url = req.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let query: HashMap<&str, &str> = query.split('&').filter_map(|p| p.split_once('=')).collect();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+    let method = req.method().clone();
+
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["health"]) => return Ok((200, json!({ "status": "ok" }))),
+        (Method::Get, ["openapi.json"]) => return Ok((200, openapi())),
+        _ => {}
+    }
+
+    let conn = open_conn(handle)?;
+    let caller = authenticate(&conn, req)?;
+    let id = |s: &str| s.parse::<i64>().map_err(|_| ApiError::not_found());
+
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["interns"]) => Ok((200, to_value(&list_interns_conn(&conn, query_i64(&query, "period_id")?)?)?)),
+        (Method::Post, ["interns"]) => {
+            let mut intern: InternPayload = read_body(req)?;
+            let new_id = write(handle, &caller, |c| insert_intern(handle, c, &mut intern))?;
+            Ok((201, json!({ "id": new_id })))
+        }
+        (Method::Get, ["interns", x]) => {
+            let intern = get_intern_conn(&conn, id(x)?)?.ok_or_else(ApiError::not_found)?;
+            Ok((200, to_value(&intern)?))
+        }
+        (Method::Put, ["interns", x]) => {
+            let intern_id = id(x)?;
+            get_intern_conn(&conn, intern_id)?.ok_or_else(ApiError::not_found)?;
+            let mut intern: InternPayload = read_body(req)?;
+            write(handle, &caller, |c| save_intern_update(handle, c, intern_id, &mut intern))?;
+            Ok((200, to_value(&get_intern_conn(&conn, intern_id)?)?))
+        }
+        (Method::Delete, ["interns", x]) => {
+            let intern_id = id(x)?;
+            if !write(handle, &caller, |c| delete_intern_conn(c, intern_id))? {
+                return Err(ApiError::not_found());
+            }
+            Ok((204, Value::Null))
+        }
+        (Method::Get, ["assignments"]) => {
+            Ok((200, to_value(&list_assignments_conn(&conn, query_i64(&query, "intern_id")?)?)?))
+        }
+        (Method::Post, ["assignments"]) => {
+            let a: Assignment = read_body(req)?;
+            let new_id = write(handle, &caller, |c| insert_assignment(c, &a))?;
+            Ok((201, json!({ "id": new_id })))
+        }
+        (Method::Patch, ["assignments", x]) => {
+            let assignment_id = id(x)?;
+            let p: StatusPayload = read_body(req)?;
+            write(handle, &caller, |c| set_assignment_status(c, assignment_id, &p.status)).map_err(|e| {
+                if e.1 == "Görev bulunamadı" { ApiError::not_found() } else { e }
+            })?;
+            Ok((204, Value::Null))
+        }
+        (Method::Delete, ["assignments", x]) => {
+            let assignment_id = id(x)?;
+            if !write(handle, &caller, |c| delete_assignment_conn(c, assignment_id))? {
+                return Err(ApiError::not_found());
+            }
+            Ok((204, Value::Null))
+        }
+        (Method::Get, ["evaluations"]) => {
+            let intern_id = query_i64(&query, "intern_id")?
+                .ok_or_else(|| ApiError::bad_request("intern_id gerekli"))?;
+            Ok((200, to_value(&list_evaluations_conn(&conn, intern_id)?)?))
+        }
+        (Method::Post, ["evaluations"]) => {
+            let e: Evaluation = read_body(req)?;
+            let new_id = write(handle, &caller, |c| insert_evaluation(c, &e))?;
+            Ok((201, json!({ "id": new_id })))
+        }
+        (Method::Delete, ["evaluations", x]) => {
+            let evaluation_id = id(x)?;
+            if !write(handle, &caller, |c| delete_evaluation_conn(c, evaluation_id))? {
+                return Err(ApiError::not_found());
+            }
+            Ok((204, Value::Null))
+        }
+        (_, ["interns" | "assignments" | "evaluations", ..]) => Err(ApiError(405, "Yöntem desteklenmiyor".to_string())),
+        _ => Err(ApiError::not_found()),
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("geçerli başlık")
+}
+
+fn handle_request(handle: &AppHandle, origins: &[String], mut req: Request) {
+    let origin = req
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Origin"))
+        .map(|h| h.value.as_str().to_string())
+        .filter(|o| origins.iter().any(|a| a == o));
+
+    let (status, body) = if *req.method() == Method::Options {
+        (204, Value::Null)
+    } else {
+        match route(handle, &mut req) {
+            Ok(r) => r,
+            Err(ApiError(status, message)) => (status, json!({ "error": message })),
+        }
+    };
+    let text = if body.is_null() { String::new() } else { body.to_string() };
+    let mut resp = Response::from_string(text)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json; charset=utf-8"));
+    if let Some(o) = origin {
+        resp.add_header(header("Access-Control-Allow-Origin", &o));
+        resp.add_header(header("Vary", "Origin"));
+        resp.add_header(header("Access-Control-Allow-Headers", "Authorization, Content-Type"));
+        resp.add_header(header("Access-Control-Allow-Methods", "GET, POST, PUT, PATCH, DELETE, OPTIONS"));
+    }
+    if let Err(e) = req.respond(resp) {
+        record_error(format!("API yanıtı gönderilemedi: {e}"));
+    }
+}
+
+fn stop() {
+    let running = SERVER.lock().ok().and_then(|mut s| s.take());
+    if let Some(r) = running {
+        // her unblock tek bir bekleyen recv'i çözer
+        for _ in &r.workers {
+            r.server.unblock();
+        }
+        for w in r.workers {
+            let _ = w.join();
+        }
+    }
+}
+
+fn bind(s: &ApiSettings) -> Result<Arc<Server>, String> {
+    let addr = validate(s)?;
+    Server::http(addr).map(Arc::new).map_err(|e| format!("API sunucusu başlatılamadı ({addr}): {e}"))
+}
+
+// tiny_http dinleyici soketi bırakmayı arka planda bitirir; durdurduktan hemen
+// sonra aynı porta bağlanmak kısa bir süre başarısız olabilir
+fn rebind(s: &ApiSettings) -> Result<Arc<Server>, String> {
+    let mut last = bind(s);
+    for _ in 0..20 {
+        if last.is_ok() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        last = bind(s);
+    }
+    last
+}
+
+fn spawn(handle: &AppHandle, s: &ApiSettings, server: Arc<Server>) -> Result<(), String> {
+    let workers = (0..WORKERS)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let handle = handle.clone();
+            let origins = s.allowed_origins.clone();
+            std::thread::spawn(move || {
+                while let Ok(req) = server.recv() {
+                    handle_request(&handle, &origins, req);
+                }
+            })
+        })
+        .collect();
+    let address = server.server_addr().to_ip().map(|a| a.to_string()).unwrap_or_default();
+    *SERVER.lock().map_err(|e| e.to_string())? = Some(Running { server, address, workers });
+    Ok(())
+}
+
+// Yeni sunucu önce bağlanır, eskisi ancak bundan sonra durdurulur; bağlanamazsa
+// çalışan sunucuya dokunulmaz. Aynı adres/port yeniden kullanılıyorsa eskisini
+// bırakmak gerekir; o durumda yeni bağlanamazsa eski ayarlarla geri açılır.
+fn start(handle: &AppHandle, previous: Option<&ApiSettings>, s: &ApiSettings) -> Result<(), String> {
+    if !s.enabled {
+        stop();
+        return Ok(());
+    }
+    let server = match bind(s) {
+        Ok(server) => server,
+        Err(e) => {
+            let same_port = SERVER
+                .lock()
+                .map_err(|e| e.to_string())?
+                .as_ref()
+                .is_some_and(|r| r.address.rsplit(':').next() == Some(&s.port.to_string()));
+            if !same_port {
+                return Err(e);
+            }
+            stop();
+            match rebind(s) {
+                Ok(server) => server,
+                Err(e) => {
+                    if let Some(p) = previous.filter(|p| p.enabled) {
+                        if let Ok(old) = rebind(p) {
+                            spawn(handle, p, old)?;
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    };
+    stop();
+    spawn(handle, s, server)
+}
+
+// setup içinde bir kez çağrılır
+pub(crate) fn start_from_settings(handle: &AppHandle) {
+    let result = open_conn(handle).and_then(|conn| load_settings(&conn)).and_then(|s| start(handle, None, &s));
+    if let Err(e) = result {
+        record_error(e);
+    }
+}
+
+fn openapi() -> Value {
+    let id_param = json!({ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } });
+    let created = json!({
+        "201": { "description": "Oluşturuldu", "content": { "application/json": { "schema": {
+            "type": "object", "properties": { "id": { "type": "integer" } } } } } },
+        "400": { "$ref": "#/components/responses/Error" },
+        "403": { "$ref": "#/components/responses/Error" }
+    });
+    let deleted = json!({
+        "204": { "description": "Silindi" },
+        "403": { "$ref": "#/components/responses/Error" },
+        "404": { "$ref": "#/components/responses/Error" }
+    });
+    let list = |schema: &str| json!({ "200": { "description": "Liste", "content": { "application/json": {
+        "schema": { "type": "array", "items": { "$ref": format!("#/components/schemas/{schema}") } } } } } });
+    let body = |schema: &str| json!({ "required": true, "content": { "application/json": {
+        "schema": { "$ref": format!("#/components/schemas/{schema}") } } } });
+    let string = json!({ "type": "string" });
+    let nullable_string = json!({ "type": "string", "nullable": true });
+    let integer = json!({ "type": "integer" });
+    let nullable_integer = json!({ "type": "integer", "nullable": true });
+    json!({
+        "openapi": "3.0.3",
+        "info": { "title": "InternTracker yerel API", "version": env!("CARGO_PKG_VERSION") },
+        "security": [{ "bearer": [] }],
+        "paths": {
+            "/interns": {
+                "get": {
+                    "summary": "Stajyerler",
+                    "parameters": [{ "name": "period_id", "in": "query", "schema": integer }],
+                    "responses": list("Intern")
+                },
+                "post": { "summary": "Stajyer ekle", "requestBody": body("InternInput"), "responses": created }
+            },
+            "/interns/{id}": {
+                "parameters": [id_param],
+                "get": { "summary": "Stajyer", "responses": {
+                    "200": { "description": "Stajyer", "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Intern" } } } },
+                    "404": { "$ref": "#/components/responses/Error" } } },
+                "put": { "summary": "Stajyer güncelle", "requestBody": body("InternInput"), "responses": {
+                    "200": { "description": "Güncel kayıt", "content": { "application/json": {
+                        "schema": { "$ref": "#/components/schemas/Intern" } } } },
+                    "404": { "$ref": "#/components/responses/Error" } } },
+                "delete": { "summary": "Stajyer sil", "responses": deleted }
+            },
+            "/assignments": {
+                "get": {
+                    "summary": "Görevler",
+                    "parameters": [{ "name": "intern_id", "in": "query", "schema": integer }],
+                    "responses": list("Assignment")
+                },
+                "post": { "summary": "Görev ekle", "requestBody": body("Assignment"), "responses": created }
+            },
+            "/assignments/{id}": {
+                "parameters": [id_param],
+                "patch": {
+                    "summary": "Görev durumunu değiştir (Completed: assignment.completed olayı)",
+                    "requestBody": { "required": true, "content": { "application/json": { "schema": {
+                        "type": "object", "required": ["status"], "properties": { "status": string } } } } },
+                    "responses": { "204": { "description": "Güncellendi" }, "404": { "$ref": "#/components/responses/Error" } }
+                },
+                "delete": { "summary": "Görev sil", "responses": deleted }
+            },
+            "/evaluations": {
+                "get": {
+                    "summary": "Stajyerin değerlendirmeleri",
+                    "parameters": [{ "name": "intern_id", "in": "query", "required": true, "schema": integer }],
+                    "responses": list("Evaluation")
+                },
+                "post": { "summary": "Değerlendirme ekle", "requestBody": body("Evaluation"), "responses": created }
+            },
+            "/evaluations/{id}": {
+                "parameters": [id_param],
+                "delete": { "summary": "Değerlendirme sil", "responses": deleted }
+            }
+        },
+        "components": {
+            "securitySchemes": { "bearer": { "type": "http", "scheme": "bearer" } },
+            "responses": {
+                "Error": { "description": "Hata", "content": { "application/json": { "schema": {
+                    "type": "object", "properties": { "error": string } } } } }
+            },
+            "schemas": {
+                "Intern": { "type": "object", "properties": {
+                    "id": integer, "first_name": string, "last_name": string, "school": string,
+                    "department": string, "start_date": string, "end_date": nullable_string,
+                    "status": string, "contact": string, "email": string,
+                    "cv_name": nullable_string, "photo_name": nullable_string, "period_id": nullable_integer,
+                    "uuid": { "type": "string", "format": "uuid", "readOnly": true } } },
+                "InternInput": { "type": "object",
+                    "required": ["first_name", "last_name", "school", "department", "start_date", "status", "contact", "email"],
+                    "properties": {
+                        "first_name": string, "last_name": string, "school": string, "department": string,
+                        "start_date": string, "end_date": nullable_string, "status": string,
+                        "contact": string, "email": string, "period_id": nullable_integer,
+                        "cv_name": nullable_string, "cv_mime": nullable_string,
+                        "cv_blob": { "type": "array", "items": integer, "nullable": true },
+                        "photo_name": nullable_string, "photo_mime": nullable_string,
+                        "photo_blob": { "type": "array", "items": integer, "nullable": true } } },
+                "Assignment": { "type": "object",
+                    "required": ["intern_id", "project_type", "task_description", "due_date", "status"],
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true }, "intern_id": integer,
+                        "project_type": string, "task_description": string, "due_date": string,
+                        "status": string, "file_path": nullable_string,
+                        "created_at": { "type": "string", "readOnly": true },
+                        "uuid": { "type": "string", "format": "uuid", "readOnly": true } } },
+                "Evaluation": { "type": "object", "required": ["intern_id", "etiket", "puan"],
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true }, "intern_id": integer,
+                        "etiket": string, "puan": { "type": "integer", "minimum": 0, "maximum": 100 },
+                        "evaluator_id": nullable_integer,
+                        "evaluator_name": { "type": "string", "nullable": true, "readOnly": true },
+                        "created_at": { "type": "string", "readOnly": true },
+                        "uuid": { "type": "string", "format": "uuid", "readOnly": true } } }
+            }
+        }
+    })
+}
+
+#[tauri::command]
+pub fn get_api_settings(handle: AppHandle) -> Result<ApiSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+// Sunucuyu yeni ayarlarla yeniden başlatır; ayarlar yalnızca başarılıysa kaydedilir
+#[tauri::command]
+pub fn update_api_settings(handle: AppHandle, settings: ApiSettings) -> Result<ApiStatus, String> {
+    validate(&settings)?;
+    let conn = open_conn(&handle)?;
+    let previous = load_settings(&conn)?;
+    start(&handle, Some(&previous), &settings)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)?;
+    if let Ok(mut e) = LAST_ERROR.lock() {
+        *e = None;
+    }
+    get_api_status()
+}
+
+#[tauri::command]
+pub fn get_api_status() -> Result<ApiStatus, String> {
+    let guard = SERVER.lock().map_err(|e| e.to_string())?;
+    let last = LAST_ERROR.lock().map_err(|e| e.to_string())?.clone();
+    Ok(ApiStatus {
+        running: guard.is_some(),
+        address: guard.as_ref().map(|r| r.address.clone()),
+        last_error: last.as_ref().map(|(m, _)| m.clone()),
+        last_error_at: last.map(|(_, at)| at),
+    })
+}
+
+#[tauri::command]
+pub fn create_api_token(handle: AppHandle, name: String, can_write: bool) -> Result<ApiTokenCreated, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Anahtar adı boş olamaz".to_string());
+    }
+    let bytes: [u8; 24] = rand::thread_rng().gen();
+    let token = format!("itk_{}", bytes.iter().map(|b| format!("{b:02x}")).collect::<String>());
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        "INSERT INTO api_tokens (name, token_hash, prefix, can_write) VALUES (?1, ?2, ?3, ?4)",
+        params![name, hash_token(&token), &token[..10], can_write as i64],
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::SqliteFailure(f, _) if f.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE => {
+            "Bu adla bir anahtar zaten var".to_string()
+        }
+        other => other.to_string(),
+    })?;
+    Ok(ApiTokenCreated { id: conn.last_insert_rowid(), token })
+}
+
+#[tauri::command]
+pub fn list_api_tokens(handle: AppHandle) -> Result<Vec<ApiToken>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, name, prefix, can_write, created_at, last_used_at, revoked_at
+        FROM api_tokens ORDER BY revoked_at IS NOT NULL, name
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |r| {
+        Ok(ApiToken {
+            id: r.get(0)?,
+            name: r.get(1)?,
+            prefix: r.get(2)?,
+            can_write: r.get::<_, i64>(3)? == 1,
+            created_at: r.get(4)?,
+            last_used_at: r.get(5)?,
+            revoked_at: r.get(6)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn revoke_api_token(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let n = conn
+        .execute("UPDATE api_tokens SET revoked_at = datetime('now') WHERE id = ?1 AND revoked_at IS NULL", params![id])
+        .map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Etkin anahtar bulunamadı".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,557 @@
+// Başvuru hattı: stajyer kaydı oluşmadan önceki adaylar. Aşamalar
+// received → screening → interview → offer (veya rejected); her aşamaya not
+// ve puan girilir. Teklif aşamasındaki aday convert_applicant_to_intern ile
+// stajyere dönüştürülür, CV mevcut dosya akışıyla stajyer klasörüne taşınır.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date};
+use crate::reference::{self, RefKind};
+use crate::{current_os_user, insert_intern, open_conn, persist_files_to_disk, slug_tr, storage_root, InternPayload};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Received,
+    Screening,
+    Interview,
+    Offer,
+    Rejected,
+}
+
+impl Stage {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "received" => Ok(Stage::Received),
+            "screening" => Ok(Stage::Screening),
+            "interview" => Ok(Stage::Interview),
+            "offer" => Ok(Stage::Offer),
+            "rejected" => Ok(Stage::Rejected),
+            other => Err(format!("Geçersiz aşama: {other}")),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Stage::Received => "received",
+            Stage::Screening => "screening",
+            Stage::Interview => "interview",
+            Stage::Offer => "offer",
+            Stage::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplicantPayload {
+    pub first_name: String,
+    pub last_name: String,
+    pub school: String,
+    pub department: String,
+    // istenen staj tarihleri
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub contact: String,
+    pub email: String,
+    pub period_id: Option<i64>,
+
+    // gönderilirse diske yazılır
+    pub cv_name: Option<String>,
+    pub cv_mime: Option<String>,
+    pub cv_blob: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Applicant {
+    pub id: i64,
+    pub first_name: String,
+    pub last_name: String,
+    pub school: String,
+    pub department: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub contact: String,
+    pub email: String,
+    pub period_id: Option<i64>,
+    pub cv_name: Option<String>,
+    pub cv_path: Option<String>,
+    pub stage: String,
+    pub average_score: Option<f64>,
+    pub note_count: i64,
+    // dönüştürüldüyse oluşan stajyer
+    pub intern_id: Option<i64>,
+    pub converted_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplicantNote {
+    pub id: i64,
+    pub applicant_id: i64,
+    pub stage: String,
+    pub note: String,
+    pub score: Option<i64>,
+    pub author: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConvertOptions {
+    // verilmezse başvurudaki tarihler kullanılır
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub period_id: Option<i64>,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS applicants (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            first_name TEXT NOT NULL,
+            last_name TEXT NOT NULL,
+            school TEXT NOT NULL,
+            department TEXT NOT NULL,
+            start_date TEXT,
+            end_date TEXT,
+            contact TEXT NOT NULL DEFAULT '',
+            email TEXT NOT NULL DEFAULT '',
+            period_id INTEGER REFERENCES periods(id) ON DELETE SET NULL,
+            cv_name TEXT,
+            cv_mime TEXT,
+            cv_path TEXT,
+            stage TEXT NOT NULL DEFAULT 'received'
+                CHECK (stage IN ('received', 'screening', 'interview', 'offer', 'rejected')),
+            intern_id INTEGER UNIQUE REFERENCES interns(id) ON DELETE SET NULL,
+            converted_at TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_applicants_stage ON applicants(stage);
+
+        CREATE TABLE IF NOT EXISTS applicant_notes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            applicant_id INTEGER NOT NULL,
+            stage TEXT NOT NULL,
+            note TEXT NOT NULL DEFAULT '',
+            score INTEGER CHECK (score IS NULL OR (score >= 0 AND score <= 100)),
+            author TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (applicant_id) REFERENCES applicants(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_applicant_notes_applicant ON applicant_notes(applicant_id);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+// storage_root\applicants\{ID}_{ad}_{soyad}
+fn applicant_dir(handle: &AppHandle, id: i64, first: &str, last: &str) -> Result<PathBuf, String> {
+    let dir = storage_root(handle)?
+        .join("applicants")
+        .join(format!("{}_{}_{}", id, slug_tr(first), slug_tr(last)));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn optional_date(s: Option<&str>) -> Result<Option<String>, String> {
+    s.map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| parse_date(d).map(format_date))
+        .transpose()
+}
+
+// Zorunlu alanlar, tarih sırası ve okul/bölüm referans adları
+fn normalize(conn: &Connection, p: &mut ApplicantPayload) -> Result<(), String> {
+    if p.first_name.trim().is_empty() || p.last_name.trim().is_empty() {
+        return Err("Ad ve soyad zorunlu".to_string());
+    }
+    p.start_date = optional_date(p.start_date.as_deref())?;
+    p.end_date = optional_date(p.end_date.as_deref())?;
+    if let (Some(s), Some(e)) = (&p.start_date, &p.end_date) {
+        if e < s {
+            return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+        }
+    }
+    p.school = reference::resolve(conn, RefKind::School, &p.school)?;
+    p.department = reference::resolve(conn, RefKind::Department, &p.department)?;
+    Ok(())
+}
+
+fn load_stage(conn: &Connection, id: i64) -> Result<(Stage, Option<i64>), String> {
+    let (stage, intern_id): (String, Option<i64>) = conn
+        .query_row("SELECT stage, intern_id FROM applicants WHERE id = ?1", params![id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Başvuru bulunamadı".to_string())?;
+    Ok((Stage::parse(&stage)?, intern_id))
+}
+
+fn check_score(score: Option<i64>) -> Result<(), String> {
+    if score.is_some_and(|s| !(0..=100).contains(&s)) {
+        return Err("Puan 0 ile 100 arasında olmalı".to_string());
+    }
+    Ok(())
+}
+
+fn insert_note(conn: &Connection, applicant_id: i64, stage: Stage, note: &str, score: Option<i64>) -> Result<(), String> {
+    check_score(score)?;
+    conn.execute(
+        "INSERT INTO applicant_notes (applicant_id, stage, note, score, author) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![applicant_id, stage.as_str(), note.trim(), score, current_os_user()],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub(crate) fn create_conn(conn: &Connection, p: &mut ApplicantPayload) -> Result<i64, String> {
+    normalize(conn, p)?;
+    conn.execute(
+        r#"
+        INSERT INTO applicants
+        (first_name, last_name, school, department, start_date, end_date, contact, email, period_id)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        "#,
+        params![
+            p.first_name.trim(),
+            p.last_name.trim(),
+            p.school,
+            p.department,
+            p.start_date,
+            p.end_date,
+            p.contact.trim(),
+            p.email.trim(),
+            p.period_id,
+        ],
+    ).map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    insert_note(conn, id, Stage::Received, "Başvuru alındı", None)?;
+    Ok(id)
+}
+
+// Aşama değişikliği not olarak geçmişe yazılır; dönüştürülmüş başvuru değişmez
+pub(crate) fn move_stage_conn(conn: &Connection, id: i64, stage: Stage, note: Option<&str>, score: Option<i64>) -> Result<(), String> {
+    let (current, intern_id) = load_stage(conn, id)?;
+    if intern_id.is_some() {
+        return Err("Başvuru stajyere dönüştürülmüş".to_string());
+    }
+    if current == stage {
+        return Err("Başvuru zaten bu aşamada".to_string());
+    }
+    check_score(score)?;
+    conn.execute(
+        "UPDATE applicants SET stage = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![stage.as_str(), id],
+    ).map_err(|e| e.to_string())?;
+    insert_note(conn, id, stage, note.unwrap_or(""), score)
+}
+
+fn query_applicants(conn: &Connection, filter: &str, args: &[&dyn rusqlite::ToSql]) -> Result<Vec<Applicant>, String> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT a.id, a.first_name, a.last_name, a.school, a.department, a.start_date, a.end_date,
+               a.contact, a.email, a.period_id, a.cv_name, a.cv_path, a.stage,
+               (SELECT AVG(score) FROM applicant_notes n WHERE n.applicant_id = a.id AND n.score IS NOT NULL),
+               (SELECT COUNT(*) FROM applicant_notes n WHERE n.applicant_id = a.id AND n.note <> ''),
+               a.intern_id, a.converted_at, a.created_at
+        FROM applicants a
+        WHERE {filter}
+        ORDER BY a.created_at DESC, a.id DESC
+        "#
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(args, |r| {
+        Ok(Applicant {
+            id: r.get(0)?,
+            first_name: r.get(1)?,
+            last_name: r.get(2)?,
+            school: r.get(3)?,
+            department: r.get(4)?,
+            start_date: r.get(5)?,
+            end_date: r.get(6)?,
+            contact: r.get(7)?,
+            email: r.get(8)?,
+            period_id: r.get(9)?,
+            cv_name: r.get(10)?,
+            cv_path: r.get(11)?,
+            stage: r.get(12)?,
+            average_score: r.get(13)?,
+            note_count: r.get(14)?,
+            intern_id: r.get(15)?,
+            converted_at: r.get(16)?,
+            created_at: r.get(17)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+pub(crate) fn list_conn(conn: &Connection, stage: Option<Stage>, period_id: Option<i64>) -> Result<Vec<Applicant>, String> {
+    query_applicants(
+        conn,
+        "(?1 IS NULL OR a.stage = ?1) AND (?2 IS NULL OR a.period_id = ?2)",
+        params![stage.map(Stage::as_str), period_id],
+    )
+}
+
+// Yazılan CV ve satırdaki önceki CV yolu
+struct SavedCv {
+    path: PathBuf,
+    old: Option<PathBuf>,
+}
+
+// Satır güncellenir, dosya en son yazılır; işlem commit_cv ile tamamlanır
+fn save_cv(handle: &AppHandle, conn: &Connection, id: i64, p: &ApplicantPayload) -> Result<Option<SavedCv>, String> {
+    let Some(bytes) = &p.cv_blob else { return Ok(None) };
+    let name = p.cv_name.as_deref()
+        .and_then(|n| Path::new(n).file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("cv.bin");
+    let path = applicant_dir(handle, id, &p.first_name, &p.last_name)?.join(name);
+
+    let old: Option<String> = conn
+        .query_row("SELECT cv_path FROM applicants WHERE id = ?1", params![id], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE applicants SET cv_name = ?1, cv_mime = ?2, cv_path = ?3 WHERE id = ?4",
+        params![name, p.cv_mime, path.to_string_lossy(), id],
+    ).map_err(|e| e.to_string())?;
+    fs::write(&path, bytes).map_err(|e| format!("CV yazılamadı: {e}"))?;
+    Ok(Some(SavedCv { path, old: old.map(PathBuf::from) }))
+}
+
+// İşlem geri alınırsa yeni CV, işlenirse farklı addaki eski CV silinir
+fn commit_cv(tx: Transaction, cv: Option<SavedCv>) -> Result<(), String> {
+    let committed = tx.commit();
+    if let Some(SavedCv { path, old }) = cv.filter(|cv| cv.old.as_ref() != Some(&cv.path)) {
+        match (&committed, old) {
+            (Ok(()), Some(old)) => { let _ = fs::remove_file(old); }
+            (Ok(()), None) => {}
+            (Err(_), _) => { let _ = fs::remove_file(path); }
+        }
+    }
+    committed.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_applicant(handle: AppHandle, mut applicant: ApplicantPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = create_conn(&tx, &mut applicant)?;
+    let cv = save_cv(&handle, &tx, id, &applicant)?;
+    commit_cv(tx, cv)?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn update_applicant(handle: AppHandle, id: i64, mut applicant: ApplicantPayload) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    if load_stage(&tx, id)?.1.is_some() {
+        return Err("Başvuru stajyere dönüştürülmüş".to_string());
+    }
+    normalize(&tx, &mut applicant)?;
+    tx.execute(
+        r#"
+        UPDATE applicants
+        SET first_name = ?1, last_name = ?2, school = ?3, department = ?4, start_date = ?5,
+            end_date = ?6, contact = ?7, email = ?8, period_id = ?9, updated_at = datetime('now')
+        WHERE id = ?10
+        "#,
+        params![
+            applicant.first_name.trim(),
+            applicant.last_name.trim(),
+            applicant.school,
+            applicant.department,
+            applicant.start_date,
+            applicant.end_date,
+            applicant.contact.trim(),
+            applicant.email.trim(),
+            applicant.period_id,
+            id,
+        ],
+    ).map_err(|e| e.to_string())?;
+    let cv = save_cv(&handle, &tx, id, &applicant)?;
+    commit_cv(tx, cv)
+}
+
+#[tauri::command]
+pub fn delete_applicant(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let (intern_id, cv_path): (Option<i64>, Option<String>) = conn
+        .query_row("SELECT intern_id, cv_path FROM applicants WHERE id = ?1", params![id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Başvuru bulunamadı".to_string())?;
+    // dönüştürülmüş başvurunun CV'si artık stajyere ait
+    if intern_id.is_none() {
+        if let Some(p) = cv_path {
+            let _ = fs::remove_file(p);
+        }
+    }
+    conn.execute("DELETE FROM applicants WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_applicants(handle: AppHandle, stage: Option<Stage>, period_id: Option<i64>) -> Result<Vec<Applicant>, String> {
+    let conn = open_conn(&handle)?;
+    list_conn(&conn, stage, period_id)
+}
+
+#[tauri::command]
+pub fn move_applicant_stage(
+    handle: AppHandle,
+    id: i64,
+    stage: Stage,
+    note: Option<String>,
+    score: Option<i64>,
+) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    move_stage_conn(&conn, id, stage, note.as_deref(), score)
+}
+
+// Not / puan başvurunun mevcut aşamasına yazılır
+#[tauri::command]
+pub fn add_applicant_note(handle: AppHandle, applicant_id: i64, note: String, score: Option<i64>) -> Result<(), String> {
+    if note.trim().is_empty() && score.is_none() {
+        return Err("Not veya puan girilmeli".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    let (stage, _) = load_stage(&conn, applicant_id)?;
+    insert_note(&conn, applicant_id, stage, &note, score)
+}
+
+#[tauri::command]
+pub fn get_applicant_notes(handle: AppHandle, applicant_id: i64) -> Result<Vec<ApplicantNote>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, applicant_id, stage, note, score, author, created_at
+         FROM applicant_notes WHERE applicant_id = ?1 ORDER BY created_at, id"
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![applicant_id], |r| {
+        Ok(ApplicantNote {
+            id: r.get(0)?,
+            applicant_id: r.get(1)?,
+            stage: r.get(2)?,
+            note: r.get(3)?,
+            score: r.get(4)?,
+            author: r.get(5)?,
+            created_at: r.get(6)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+// Stajyerin geldiği başvuru (varsa)
+#[tauri::command]
+pub fn get_intern_application(handle: AppHandle, intern_id: i64) -> Result<Option<Applicant>, String> {
+    let conn = open_conn(&handle)?;
+    Ok(query_applicants(&conn, "a.intern_id = ?1", params![intern_id])?.pop())
+}
+
+// Teklif aşamasındaki adaydan stajyer kaydı oluşturur
+#[tauri::command]
+pub fn convert_applicant_to_intern(handle: AppHandle, id: i64, options: Option<ConvertOptions>) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    let (stage, intern_id) = load_stage(&conn, id)?;
+    if intern_id.is_some() {
+        return Err("Başvuru zaten stajyere dönüştürülmüş".to_string());
+    }
+    if stage != Stage::Offer {
+        return Err("Yalnızca teklif aşamasındaki başvurular dönüştürülebilir".to_string());
+    }
+
+    let a = query_applicants(&conn, "a.id = ?1", params![id])?
+        .pop()
+        .ok_or_else(|| "Başvuru bulunamadı".to_string())?;
+    let cv_mime: Option<String> = conn
+        .query_row("SELECT cv_mime FROM applicants WHERE id = ?1", params![id], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+
+    let options = options.unwrap_or(ConvertOptions { start_date: None, end_date: None, period_id: None });
+    let start_date = optional_date(options.start_date.as_deref())?
+        .or(a.start_date)
+        .ok_or_else(|| "Staj başlangıç tarihi gerekli".to_string())?;
+    let end_date = optional_date(options.end_date.as_deref())?.or(a.end_date);
+    let cv_path = a.cv_path;
+    let cv_blob = match &cv_path {
+        Some(p) => Some(fs::read(p).map_err(|e| format!("CV okunamadı: {e}"))?),
+        None => None,
+    };
+
+    let mut intern = InternPayload {
+        id: None,
+        first_name: a.first_name,
+        last_name: a.last_name,
+        school: a.school,
+        department: a.department,
+        start_date,
+        end_date,
+        status: "aktif".to_string(),
+        contact: a.contact,
+        email: a.email,
+        cv_path: None,
+        photo_path: None,
+        cv_name: a.cv_name,
+        cv_mime,
+        // dosya işlem sonrasında yazılır; geri alınan dönüşümde klasörde kalmaz
+        cv_blob: None,
+        photo_name: None,
+        photo_mime: None,
+        photo_blob: None,
+        period_id: options.period_id.or(a.period_id),
+    };
+
+    // stajyer kaydı ve başvuru bağlantısı birlikte yazılır
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let intern_id = insert_intern(&handle, &tx, &mut intern)?;
+    if cv_blob.is_some() {
+        tx.execute("UPDATE interns SET cv_blob = ?1 WHERE id = ?2", params![cv_blob, intern_id])
+            .map_err(|e| e.to_string())?;
+    }
+    tx.execute(
+        r#"
+        UPDATE applicants
+        SET intern_id = ?1, converted_at = datetime('now'), updated_at = datetime('now')
+        WHERE id = ?2
+        "#,
+        params![intern_id, id],
+    ).map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO applicant_notes (applicant_id, stage, note, author) VALUES (?1, 'offer', ?2, ?3)",
+        params![id, format!("Stajyere dönüştürüldü (#{intern_id})"), current_os_user()],
+    ).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    // CV stajyer klasörüne yazılır; yazılamazsa başvuru kopyası yerinde kalır
+    let Some(old) = cv_path else { return Ok(intern_id) };
+    intern.cv_blob = cv_blob;
+    persist_files_to_disk(&handle, &conn, intern_id, &intern)
+        .map_err(|e| format!("Stajyer oluşturuldu (#{intern_id}) ancak CV taşınamadı: {e}"))?;
+    let new_cv: Option<String> = conn
+        .query_row("SELECT cv_path FROM interns WHERE id = ?1", params![intern_id], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE applicants SET cv_path = COALESCE(?1, cv_path) WHERE id = ?2",
+        params![new_cv, id],
+    ).map_err(|e| e.to_string())?;
+    if new_cv.is_some() {
+        let _ = fs::remove_file(&old);
+        if let Some(dir) = Path::new(&old).parent() {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+    Ok(intern_id)
+}
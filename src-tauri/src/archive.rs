@@ -0,0 +1,865 @@
+// Taşınabilir tam yedek: tüm tablolar JSON olarak, masaüstündeki InternTracker
+// klasörü (CV, fotoğraf, defter ekleri, sertifikalar) dosya olarak ve SHA-256
+// özetli bir manifest tek bir .zip arşivinde toplanır. İçe aktarma önce arşivi
+// doğrular; ardından mevcut veriyi ya tamamen değiştirir (kimlikler korunur) ya
+// da birleştirir (yeni kimlik verilir, yabancı anahtarlar yeniden eşlenir).
+//
+// Arşiv düzeni:
+//   manifest.json         biçim sürümü, tablolar, ek eşlemeleri, özetler
+//   tables/<tablo>.json   {"columns": [...], "rows": [[...], ...]}
+//   blobs/<sha256>        BLOB kolonları; tabloda {"$blob": "<sha256>"}
+//   files/<göreli yol>    storage_root altındaki dosyalar
+//
+// Bu bilgisayara ait kimlik ve işletim verisi (eşitleme kimliği ve eşleri, API
+// anahtarları, webhook uçları ve sırları, olay kuyruğu) arşive girmez ve
+// içe aktarmada olduğu gibi kalır; böylece geri yüklenen ya da kopyalanan bir
+// kurulum başka bir kurulumun site_id'sini devralmaz.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{Local, Utc};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{app_db_path, events, open_conn, storage_root, sync};
+
+const FORMAT: &str = "interntracker-archive";
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+
+// türetilmiş tablolar (arama dizini tetikleyicilerle yeniden dolar)
+const SKIP_PREFIXES: &[&str] = &["sqlite_", "search_index"];
+
+// bu bilgisayara ait tablolar: dışa aktarılmaz, içe aktarmada dokunulmaz
+const MACHINE_TABLES: &[&str] = &[
+    "api_tokens",
+    "domain_events",
+    "webhook_endpoints",
+    "webhook_deliveries",
+    "webhook_attempts",
+    "sync_rows",
+    "sync_peers",
+    "sync_conflicts",
+];
+
+// app_settings içinde aynı nedenle taşınmayan anahtarlar
+const MACHINE_SETTINGS: &[&str] = &["sync", sync::SCHEMA_KEY, "api", "webhooks"];
+
+// birleştirmede alınmayan işletim tabloları
+const LOCAL_TABLES: &[&str] = &["app_settings", "email_outbox", "sent_reminders"];
+
+// içe aktarılan dosyalar işlem tamamlanana kadar burada bekler
+const STAGING_PREFIX: &str = ".import-";
+
+// disk yolu tutan kolonlar (içe aktarmada yeni konuma göre yazılır)
+const ATTACHMENT_COLUMNS: &[(&str, &str)] = &[
+    ("interns", "cv_path"),
+    ("interns", "photo_path"),
+    ("assignments", "file_path"),
+    ("applicants", "cv_path"),
+    ("journal_attachments", "file_path"),
+    ("certificates", "file_path"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    Replace,
+    Merge,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    format: String,
+    format_version: u32,
+    app_version: String,
+    created_at: String,
+    // kaynak bilgisayardaki klasör (yalnızca bilgi amaçlı)
+    storage_root: String,
+    tables: Vec<TableEntry>,
+    attachments: Vec<AttachmentRef>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableEntry {
+    pub name: String,
+    pub rows: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AttachmentRef {
+    table: String,
+    column: String,
+    row_id: i64,
+    path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TableData {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveSummary {
+    pub path: String,
+    pub tables: usize,
+    pub rows: usize,
+    pub files: usize,
+    pub bytes: u64,
+    // veritabanında kayıtlı ama diskte bulunamayan ekler
+    pub missing_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArchiveInfo {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: String,
+    pub tables: Vec<TableEntry>,
+    pub files: usize,
+    pub bytes: u64,
+    // bu sürümde karşılığı olmayan tablolar (içe aktarılmaz)
+    pub unknown_tables: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableImport {
+    pub table: String,
+    pub inserted: usize,
+    // birleştirmede mevcut bir kayıtla eşleşenler
+    pub matched: usize,
+    // üst kaydı olmadığı için alınamayanlar
+    pub skipped: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportResult {
+    pub mode: ImportMode,
+    pub tables: Vec<TableImport>,
+    pub files_restored: usize,
+    // içe aktarmadan önce alınan veritabanı kopyası
+    pub backup_path: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+struct TableInfo {
+    name: String,
+    columns: Vec<String>,
+    not_null: HashSet<String>,
+    // tek INTEGER birincil anahtar "id" mi (yeniden eşlenebilir)
+    has_id: bool,
+    // (kolon, üst tablo)
+    fks: Vec<(String, String)>,
+}
+
+// Yazılan baytların özetini ve boyutunu tutar
+struct Hashing<W> {
+    inner: W,
+    hasher: Sha256,
+    len: u64,
+}
+
+impl<W: Write> Hashing<W> {
+    fn new(inner: W) -> Self {
+        Hashing { inner, hasher: Sha256::new(), len: 0 }
+    }
+
+    fn finish(self, path: &str) -> Entry {
+        Entry { path: path.to_string(), size: self.len, sha256: hex(&self.hasher.finalize()) }
+    }
+}
+
+impl<W: Write> Write for Hashing<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.len += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Dışa aktarılacak tablolar, üst tablolar önce gelecek şekilde sıralı
+fn table_infos(conn: &Connection) -> Result<Vec<TableInfo>, String> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+        .map_err(|e| e.to_string())?;
+    let names: Vec<String> = stmt
+        .query_map([], |r| r.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut infos = Vec::new();
+    for name in names
+        .into_iter()
+        .filter(|n| !SKIP_PREFIXES.iter().any(|p| n.starts_with(p)) && !MACHINE_TABLES.contains(&n.as_str()))
+    {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", quote(&name))).map_err(|e| e.to_string())?;
+        let cols: Vec<(String, String, bool, i64)> = stmt
+            .query_map([], |r| Ok((r.get(1)?, r.get(2)?, r.get::<_, i64>(3)? == 1, r.get(5)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        let pk: Vec<&(String, String, bool, i64)> = cols.iter().filter(|c| c.3 > 0).collect();
+        let has_id = pk.len() == 1 && pk[0].0 == "id" && pk[0].1.eq_ignore_ascii_case("INTEGER");
+
+        let mut stmt = conn.prepare(&format!("PRAGMA foreign_key_list({})", quote(&name))).map_err(|e| e.to_string())?;
+        let fks: Vec<(String, String)> = stmt
+            .query_map([], |r| Ok((r.get(3)?, r.get(2)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+
+        infos.push(TableInfo {
+            name,
+            not_null: cols.iter().filter(|c| c.2).map(|c| c.0.clone()).collect(),
+            columns: cols.into_iter().map(|c| c.0).collect(),
+            has_id,
+            fks,
+        });
+    }
+
+    let mut ordered: Vec<TableInfo> = Vec::with_capacity(infos.len());
+    while !infos.is_empty() {
+        let placed: HashSet<&str> = ordered.iter().map(|t| t.name.as_str()).collect();
+        let known: HashSet<&str> = infos.iter().map(|t| t.name.as_str()).collect();
+        let ready = infos.iter().position(|t| {
+            t.fks.iter().all(|(_, p)| p == &t.name || placed.contains(p.as_str()) || !known.contains(p.as_str()))
+        });
+        // döngü olursa kalanlar sırayla eklenir
+        let next = infos.remove(ready.unwrap_or(0));
+        ordered.push(next);
+    }
+    Ok(ordered)
+}
+
+// UNIQUE kısıtları (birincil anahtar hariç)
+fn unique_keys(conn: &Connection, table: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut stmt = conn.prepare(&format!("PRAGMA index_list({})", quote(table))).map_err(|e| e.to_string())?;
+    let indexes: Vec<String> = stmt
+        .query_map([], |r| Ok((r.get::<_, String>(1)?, r.get::<_, i64>(2)?, r.get::<_, String>(3)?)))
+        .map_err(|e| e.to_string())?
+        .filter_map(|r| r.ok())
+        .filter(|(_, unique, origin)| *unique == 1 && origin != "pk")
+        .map(|(name, _, _)| name)
+        .collect();
+    let mut keys = Vec::new();
+    for index in indexes {
+        let mut stmt = conn.prepare(&format!("PRAGMA index_info({})", quote(&index))).map_err(|e| e.to_string())?;
+        let cols: Vec<String> = stmt
+            .query_map([], |r| r.get(2))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        keys.push(cols);
+    }
+    Ok(keys)
+}
+
+// app_settings'ten yalnızca taşınabilir anahtarlar
+fn row_filter(table: &str) -> String {
+    if table != "app_settings" {
+        return String::new();
+    }
+    let keys = MACHINE_SETTINGS.iter().map(|k| format!("'{k}'")).collect::<Vec<_>>().join(", ");
+    format!(" WHERE key NOT IN ({keys})")
+}
+
+fn is_attachment(table: &str, column: &str) -> bool {
+    ATTACHMENT_COLUMNS.iter().any(|(t, c)| *t == table && *c == column)
+}
+
+// --- DIŞA AKTARMA ---
+
+struct ArchiveWriter<'a> {
+    zip: ZipWriter<File>,
+    entries: Vec<Entry>,
+    blobs: HashSet<String>,
+    skip: &'a Path,
+}
+
+impl ArchiveWriter<'_> {
+    fn options() -> SimpleFileOptions {
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated).large_file(true)
+    }
+
+    fn add_bytes(&mut self, path: &str, bytes: &[u8]) -> Result<(), String> {
+        self.zip.start_file(path, Self::options()).map_err(|e| e.to_string())?;
+        let mut w = Hashing::new(&mut self.zip);
+        w.write_all(bytes).map_err(|e| e.to_string())?;
+        self.entries.push(w.finish(path));
+        Ok(())
+    }
+
+    fn add_file(&mut self, path: &str, src: &Path) -> Result<(), String> {
+        let mut f = File::open(src).map_err(|e| format!("{} okunamadı: {e}", src.display()))?;
+        self.zip.start_file(path, Self::options()).map_err(|e| e.to_string())?;
+        let mut w = Hashing::new(&mut self.zip);
+        io::copy(&mut f, &mut w).map_err(|e| e.to_string())?;
+        self.entries.push(w.finish(path));
+        Ok(())
+    }
+
+    fn add_dir(&mut self, dir: &Path, rel: &str) -> Result<(), String> {
+        let mut entries: Vec<_> = fs::read_dir(dir).map_err(|e| e.to_string())?.flatten().collect();
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let path = entry.path();
+            let name = format!("{rel}/{}", entry.file_name().to_string_lossy());
+            if path.is_dir() {
+                // yarıda kalmış bir içe aktarmanın bekleyen dosyaları
+                if !entry.file_name().to_string_lossy().starts_with(STAGING_PREFIX) {
+                    self.add_dir(&path, &name)?;
+                }
+            } else if path != self.skip {
+                self.add_file(&name, &path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn json_value(&mut self, v: ValueRef) -> Result<Value, String> {
+        Ok(match v {
+            ValueRef::Null => Value::Null,
+            ValueRef::Integer(i) => json!(i),
+            ValueRef::Real(f) => serde_json::Number::from_f64(f).map(Value::Number).unwrap_or(Value::Null),
+            ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+            ValueRef::Blob(b) => {
+                let hash = sha256(b);
+                if self.blobs.insert(hash.clone()) {
+                    self.add_bytes(&format!("blobs/{hash}"), b)?;
+                }
+                json!({ "$blob": hash })
+            }
+        })
+    }
+}
+
+// "C:\...\InternTracker\interns\1_a\cv.pdf" → "files/interns/1_a/cv.pdf"
+fn archive_path(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = rel.components().map(|c| c.as_os_str().to_string_lossy().into_owned()).collect();
+    Some(format!("files/{}", parts.join("/")))
+}
+
+pub(crate) fn export_conn(conn: &Connection, root: &Path, out: &Path) -> Result<ArchiveSummary, String> {
+    let part = out.with_extension("part");
+    let file = File::create(&part).map_err(|e| format!("Arşiv oluşturulamadı: {e}"))?;
+    let mut w = ArchiveWriter { zip: ZipWriter::new(file), entries: Vec::new(), blobs: HashSet::new(), skip: &part };
+
+    // tek okuma işlemi: tutarlı anlık görüntü
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let infos = table_infos(&tx)?;
+    let mut tables = Vec::new();
+    for info in &infos {
+        let cols = info.columns.iter().map(|c| quote(c)).collect::<Vec<_>>().join(", ");
+        let mut stmt = tx
+            .prepare(&format!("SELECT {cols} FROM {}{} ORDER BY rowid", quote(&info.name), row_filter(&info.name)))
+            .map_err(|e| e.to_string())?;
+        let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+        let mut data = TableData { columns: info.columns.clone(), rows: Vec::new() };
+        while let Some(r) = rows.next().map_err(|e| e.to_string())? {
+            let mut row = Vec::with_capacity(info.columns.len());
+            for i in 0..info.columns.len() {
+                row.push(w.json_value(r.get_ref(i).map_err(|e| e.to_string())?)?);
+            }
+            data.rows.push(row);
+        }
+        let bytes = serde_json::to_vec(&data).map_err(|e| e.to_string())?;
+        w.add_bytes(&format!("tables/{}.json", info.name), &bytes)?;
+        tables.push(TableEntry { name: info.name.clone(), rows: data.rows.len() });
+    }
+
+    // ekler: klasördekiler göreli yollarıyla, klasör dışındakiler external/ altında
+    let mut attachments = Vec::new();
+    let mut missing_files = Vec::new();
+    let mut external = Vec::new();
+    for (table, column) in ATTACHMENT_COLUMNS {
+        if !infos.iter().any(|t| t.name == *table && t.columns.iter().any(|c| c == column)) {
+            continue;
+        }
+        let mut stmt = tx
+            .prepare(&format!("SELECT id, {column} FROM {table} WHERE {column} IS NOT NULL AND {column} <> ''"))
+            .map_err(|e| e.to_string())?;
+        let rows: Vec<(i64, String)> = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.to_string())?;
+        for (row_id, value) in rows {
+            let src = PathBuf::from(&value);
+            if !src.is_file() {
+                missing_files.push(value);
+                continue;
+            }
+            let path = match archive_path(root, &src) {
+                Some(p) => p,
+                None => {
+                    let name = src.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                    let p = format!("files/external/{table}/{row_id}/{name}");
+                    external.push((p.clone(), src));
+                    p
+                }
+            };
+            attachments.push(AttachmentRef { table: table.to_string(), column: column.to_string(), row_id, path });
+        }
+    }
+    drop(tx);
+
+    if root.is_dir() {
+        w.add_dir(root, "files")?;
+    }
+    for (path, src) in external {
+        w.add_file(&path, &src)?;
+    }
+
+    let files = w.entries.iter().filter(|e| e.path.starts_with("files/")).count();
+    let manifest = Manifest {
+        format: FORMAT.to_string(),
+        format_version: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        storage_root: root.to_string_lossy().into_owned(),
+        tables,
+        attachments,
+        entries: std::mem::take(&mut w.entries),
+    };
+    let bytes = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    w.zip.start_file(MANIFEST, ArchiveWriter::options()).map_err(|e| e.to_string())?;
+    w.zip.write_all(&bytes).map_err(|e| e.to_string())?;
+    w.zip.finish().map_err(|e| format!("Arşiv yazılamadı: {e}"))?;
+    fs::rename(&part, out).map_err(|e| format!("Arşiv kaydedilemedi: {e}"))?;
+
+    Ok(ArchiveSummary {
+        path: out.to_string_lossy().into_owned(),
+        tables: manifest.tables.len(),
+        rows: manifest.tables.iter().map(|t| t.rows).sum(),
+        files,
+        bytes: fs::metadata(out).map(|m| m.len()).unwrap_or(0),
+        missing_files,
+    })
+}
+
+// --- İÇE AKTARMA ---
+
+pub(crate) fn open_archive(path: &str) -> Result<ZipArchive<File>, String> {
+    let f = File::open(path).map_err(|e| format!("Arşiv açılamadı: {e}"))?;
+    ZipArchive::new(f).map_err(|_| "Geçerli bir InternTracker arşivi değil".to_string())
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(zip: &mut ZipArchive<File>, path: &str) -> Result<T, String> {
+    let f = zip.by_name(path).map_err(|_| format!("Arşiv eksik: {path}"))?;
+    serde_json::from_reader(f).map_err(|e| format!("{path} okunamadı: {e}"))
+}
+
+// "files/interns/1_a/cv.pdf" → "interns/1_a/cv.pdf"; dışarı taşan yollar reddedilir
+fn safe_rel(path: &str) -> Result<PathBuf, String> {
+    let rel = path.strip_prefix("files/").unwrap_or(path);
+    let p = Path::new(rel);
+    if rel.is_empty() || rel.contains('\\') || p.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(format!("Geçersiz arşiv yolu: {path}"));
+    }
+    Ok(p.to_path_buf())
+}
+
+// Manifesti okur; biçim sürümünü ve tüm dosyaların özetlerini doğrular
+pub(crate) fn read_verified(zip: &mut ZipArchive<File>) -> Result<Manifest, String> {
+    let m: Manifest = read_json(zip, MANIFEST).map_err(|_| "Geçerli bir InternTracker arşivi değil".to_string())?;
+    if m.format != FORMAT {
+        return Err("Geçerli bir InternTracker arşivi değil".to_string());
+    }
+    if m.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "Arşiv daha yeni bir sürümle oluşturulmuş ({}); uygulamayı güncelleyin",
+            m.app_version
+        ));
+    }
+    let listed: HashSet<&str> = m.entries.iter().map(|e| e.path.as_str()).collect();
+    let required = m
+        .tables
+        .iter()
+        .map(|t| format!("tables/{}.json", t.name))
+        .chain(m.attachments.iter().map(|a| a.path.clone()));
+    for path in required {
+        if !listed.contains(path.as_str()) {
+            return Err(format!("Arşiv eksik: {path}"));
+        }
+    }
+    for e in &m.entries {
+        if e.path.starts_with("files/") {
+            safe_rel(&e.path)?;
+        }
+        let mut f = zip.by_name(&e.path).map_err(|_| format!("Arşiv eksik: {}", e.path))?;
+        let mut h = Hashing::new(io::sink());
+        io::copy(&mut f, &mut h).map_err(|err| format!("{} okunamadı: {err}", e.path))?;
+        let got = h.finish(&e.path);
+        if got.size != e.size || got.sha256 != e.sha256 {
+            return Err(format!("Arşiv bozuk: {} sağlama toplamı tutmuyor", e.path));
+        }
+    }
+    Ok(m)
+}
+
+fn extract(zip: &mut ZipArchive<File>, entry: &str, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Klasör oluşturulamadı: {e}"))?;
+    }
+    let mut src = zip.by_name(entry).map_err(|_| format!("Arşiv eksik: {entry}"))?;
+    let mut out = File::create(dest).map_err(|e| format!("{} yazılamadı: {e}", dest.display()))?;
+    io::copy(&mut src, &mut out).map_err(|e| format!("{} yazılamadı: {e}", dest.display()))?;
+    Ok(())
+}
+
+fn to_sql(zip: &mut ZipArchive<File>, v: &Value) -> Result<SqlValue, String> {
+    Ok(match v {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        Value::Object(o) => {
+            let hash = o.get("$blob").and_then(Value::as_str).ok_or("Arşivde geçersiz değer")?;
+            let mut bytes = Vec::new();
+            zip.by_name(&format!("blobs/{hash}"))
+                .map_err(|_| format!("Arşiv eksik: blobs/{hash}"))?
+                .read_to_end(&mut bytes)
+                .map_err(|e| e.to_string())?;
+            SqlValue::Blob(bytes)
+        }
+        Value::Array(_) => return Err("Arşivde geçersiz değer".to_string()),
+    })
+}
+
+// Birleştirmede aynı kaydı bulur: önce UNIQUE kısıtlarıyla, sonra tüm
+// kolonların (kimlik ve ek yolları hariç) birebir eşitliğiyle
+fn find_existing(
+    conn: &Connection,
+    table: &str,
+    cols: &[&String],
+    values: &[SqlValue],
+    keys: &[Vec<String>],
+) -> Result<Option<i64>, String> {
+    let lookup = |idx: &[usize], op: &str| -> Result<Option<i64>, String> {
+        let cond = idx
+            .iter()
+            .enumerate()
+            .map(|(n, &i)| format!("{} {op} ?{}", quote(cols[i]), n + 1))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        conn.query_row(
+            &format!("SELECT id FROM {} WHERE {cond} LIMIT 1", quote(table)),
+            params_from_iter(idx.iter().map(|&i| &values[i])),
+            |r| r.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())
+    };
+    for key in keys {
+        let idx: Option<Vec<usize>> = key.iter().map(|k| cols.iter().position(|c| *c == k)).collect();
+        let Some(idx) = idx else { continue };
+        if idx.iter().any(|&i| values[i] == SqlValue::Null) {
+            continue;
+        }
+        if let Some(id) = lookup(&idx, "=")? {
+            return Ok(Some(id));
+        }
+    }
+    let idx: Vec<usize> = (0..cols.len()).filter(|&i| cols[i] != "id" && !is_attachment(table, cols[i])).collect();
+    if idx.is_empty() {
+        return Ok(None);
+    }
+    lookup(&idx, "IS")
+}
+
+fn insert(conn: &Connection, verb: &str, table: &str, cols: &[&String], values: &[SqlValue]) -> Result<usize, String> {
+    let names = cols.iter().map(|c| quote(c)).collect::<Vec<_>>().join(", ");
+    let marks = (1..=cols.len()).map(|i| format!("?{i}")).collect::<Vec<_>>().join(", ");
+    conn.execute(&format!("{verb} INTO {} ({names}) VALUES ({marks})", quote(table)), params_from_iter(values))
+        .map_err(|e| format!("{table} tablosu içe aktarılamadı: {e}"))
+}
+
+pub(crate) fn inspect_conn(conn: &Connection, zip: &mut ZipArchive<File>) -> Result<ArchiveInfo, String> {
+    let m = read_verified(zip)?;
+    let infos = table_infos(conn)?;
+    Ok(ArchiveInfo {
+        format_version: m.format_version,
+        app_version: m.app_version,
+        created_at: m.created_at,
+        unknown_tables: m
+            .tables
+            .iter()
+            .filter(|t| !infos.iter().any(|i| i.name == t.name) && !MACHINE_TABLES.contains(&t.name.as_str()))
+            .map(|t| t.name.clone())
+            .collect(),
+        tables: m.tables,
+        files: m.entries.iter().filter(|e| e.path.starts_with("files/")).count(),
+        bytes: m.entries.iter().map(|e| e.size).sum(),
+    })
+}
+
+pub(crate) fn import_conn(
+    conn: &mut Connection,
+    zip: &mut ZipArchive<File>,
+    m: &Manifest,
+    root: &Path,
+    mode: ImportMode,
+    stamp: &str,
+) -> Result<ImportResult, String> {
+    let infos = table_infos(conn)?;
+    let mut warnings: Vec<String> = m
+        .tables
+        .iter()
+        .filter(|t| !infos.iter().any(|i| i.name == t.name) && !MACHINE_TABLES.contains(&t.name.as_str()))
+        .map(|t| format!("{} tablosu bu sürümde yok; atlandı", t.name))
+        .collect();
+    let archived: HashSet<&str> = m.tables.iter().map(|t| t.name.as_str()).collect();
+    let refs: HashMap<(&str, &str, i64), &str> = m
+        .attachments
+        .iter()
+        .map(|a| ((a.table.as_str(), a.column.as_str(), a.row_id), a.path.as_str()))
+        .collect();
+
+    // değiştirmede tüm klasör aynı göreli yollarla; birleştirmede yalnızca
+    // alınan kayıtların ekleri imported/<zaman> altına
+    let dest_root = match mode {
+        ImportMode::Replace => root.to_path_buf(),
+        ImportMode::Merge => root.join("imported").join(stamp),
+    };
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    if mode == ImportMode::Replace {
+        for e in m.entries.iter().filter(|e| e.path.starts_with("files/")) {
+            files.push((e.path.clone(), dest_root.join(safe_rel(&e.path)?)));
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    if mode == ImportMode::Replace {
+        for info in infos.iter().rev() {
+            tx.execute(&format!("DELETE FROM {}{}", quote(&info.name), row_filter(&info.name)), [])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    // eski kimlik → yeni kimlik (birleştirme)
+    let mut maps: HashMap<&str, HashMap<i64, i64>> = HashMap::new();
+    let mut tables = Vec::new();
+    for info in &infos {
+        let name = info.name.as_str();
+        if !archived.contains(name) {
+            if mode == ImportMode::Replace {
+                warnings.push(format!("{name} tablosu arşivde yok; boşaltıldı"));
+            }
+            continue;
+        }
+        if mode == ImportMode::Merge && LOCAL_TABLES.contains(&name) {
+            continue;
+        }
+        let data: TableData = read_json(zip, &format!("tables/{name}.json"))?;
+        let keep: Vec<usize> = (0..data.columns.len()).filter(|&i| info.columns.contains(&data.columns[i])).collect();
+        if keep.len() < data.columns.len() {
+            let dropped: Vec<&str> = data.columns.iter().filter(|c| !info.columns.contains(c)).map(String::as_str).collect();
+            warnings.push(format!("{name} tablosundaki {} kolonları bu sürümde yok; atlandı", dropped.join(", ")));
+        }
+        let cols: Vec<&String> = keep.iter().map(|&i| &data.columns[i]).collect();
+        let id_pos = cols.iter().position(|c| *c == "id");
+        let fk_pos: Vec<(usize, &str)> = info
+            .fks
+            .iter()
+            .filter_map(|(c, parent)| cols.iter().position(|x| *x == c).map(|i| (i, parent.as_str())))
+            .collect();
+        let att_pos: Vec<usize> = (0..cols.len()).filter(|&i| is_attachment(name, cols[i])).collect();
+        // eski arşivlerde bulunabilecek bu bilgisayara ait ayarlar
+        let key_pos = cols.iter().position(|c| *c == "key").filter(|_| name == "app_settings");
+        let created = match name {
+            "interns" => Some((events::INTERN_CREATED, events::intern_data as fn(&Connection, i64) -> Result<Option<Value>, String>)),
+            "assignments" => Some((events::ASSIGNMENT_CREATED, events::assignment_data as _)),
+            "evaluations" => Some((events::EVALUATION_CREATED, events::evaluation_data as _)),
+            _ => None,
+        };
+        let keys = match mode {
+            ImportMode::Merge => unique_keys(&tx, name)?,
+            ImportMode::Replace => Vec::new(),
+        };
+
+        let mut stat = TableImport { table: name.to_string(), inserted: 0, matched: 0, skipped: 0 };
+        let mut map = HashMap::new();
+        for row in &data.rows {
+            let mut values = keep.iter().map(|&i| to_sql(zip, &row[i])).collect::<Result<Vec<_>, _>>()?;
+            if let Some(k) = key_pos {
+                if matches!(&values[k], SqlValue::Text(t) if MACHINE_SETTINGS.contains(&t.as_str())) {
+                    continue;
+                }
+            }
+            let old_id = id_pos.and_then(|i| match values[i] {
+                SqlValue::Integer(v) => Some(v),
+                _ => None,
+            });
+
+            let mut pending = Vec::new();
+            if let Some(old) = old_id {
+                for &i in &att_pos {
+                    if let Some(path) = refs.get(&(name, cols[i].as_str(), old)) {
+                        let dest = dest_root.join(safe_rel(path)?);
+                        values[i] = SqlValue::Text(dest.to_string_lossy().into_owned());
+                        pending.push((path.to_string(), dest));
+                    }
+                }
+            }
+
+            if mode == ImportMode::Replace {
+                insert(&tx, "INSERT", name, &cols, &values)?;
+                stat.inserted += 1;
+                continue;
+            }
+
+            let mut orphan = false;
+            for &(i, parent) in &fk_pos {
+                if let SqlValue::Integer(old) = values[i] {
+                    match maps.get(parent).and_then(|m| m.get(&old)) {
+                        Some(&new) => values[i] = SqlValue::Integer(new),
+                        None if info.not_null.contains(cols[i]) => orphan = true,
+                        None => values[i] = SqlValue::Null,
+                    }
+                }
+            }
+            if orphan {
+                stat.skipped += 1;
+                continue;
+            }
+
+            match (info.has_id, old_id) {
+                (true, Some(old)) => {
+                    if let Some(existing) = find_existing(&tx, name, &cols, &values, &keys)? {
+                        map.insert(old, existing);
+                        stat.matched += 1;
+                        continue;
+                    }
+                    let i = id_pos.unwrap_or_default();
+                    let (c, v): (Vec<&String>, Vec<SqlValue>) =
+                        cols.iter().copied().zip(values).enumerate().filter(|(n, _)| *n != i).map(|(_, x)| x).unzip();
+                    insert(&tx, "INSERT", name, &c, &v)?;
+                    let new = tx.last_insert_rowid();
+                    map.insert(old, new);
+                    if let Some((event, load)) = created {
+                        events::emit_current(&tx, event, new, load)?;
+                    }
+                    stat.inserted += 1;
+                    files.extend(pending);
+                }
+                _ => {
+                    if insert(&tx, "INSERT OR IGNORE", name, &cols, &values)? > 0 {
+                        stat.inserted += 1;
+                        files.extend(pending);
+                    } else {
+                        stat.matched += 1;
+                    }
+                }
+            }
+        }
+        maps.insert(name, map);
+        tables.push(stat);
+    }
+
+    // dosyalar önce bekleme klasörüne açılır; canlı dosyalar ancak veritabanı
+    // işlemi tamamlandıktan sonra değişir
+    let staging = root.join(format!("{STAGING_PREFIX}{stamp}"));
+    let staged = files.iter().enumerate().try_for_each(|(n, (entry, _))| extract(zip, entry, &staging.join(n.to_string())));
+    if let Err(e) = staged.and_then(|_| tx.commit().map_err(|e| e.to_string())) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    let mut restored = 0;
+    for (n, (_, dest)) in files.iter().enumerate() {
+        let moved = dest
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::rename(staging.join(n.to_string()), dest));
+        match moved {
+            Ok(()) => restored += 1,
+            Err(e) => warnings.push(format!("{} yerine konamadı: {e}", dest.display())),
+        }
+    }
+    let _ = fs::remove_dir_all(&staging);
+
+    Ok(ImportResult { mode, tables, files_restored: restored, backup_path: None, warnings })
+}
+
+// İçe aktarmadan önce veritabanının tam kopyası (interns.db ile aynı klasöre)
+fn backup(conn: &Connection, handle: &AppHandle, stamp: &str) -> Result<String, String> {
+    let db = app_db_path(handle)?;
+    let mut path = db.with_file_name(format!("interns-before-import-{stamp}.db"));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = db.with_file_name(format!("interns-before-import-{stamp}-{n}.db"));
+    }
+    let path = path.to_string_lossy().into_owned();
+    conn.execute("VACUUM INTO ?1", params![path]).map_err(|e| format!("Yedek alınamadı: {e}"))?;
+    Ok(path)
+}
+
+#[tauri::command]
+pub fn export_archive(handle: AppHandle, export_path: String) -> Result<ArchiveSummary, String> {
+    let out = PathBuf::from(export_path);
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Klasör oluşturulamadı: {e}"))?;
+    }
+    let conn = open_conn(&handle)?;
+    export_conn(&conn, &storage_root(&handle)?, &out)
+}
+
+// İçe aktarmadan önce arşivi doğrular ve içeriğini özetler
+#[tauri::command]
+pub fn inspect_archive(handle: AppHandle, path: String) -> Result<ArchiveInfo, String> {
+    let mut zip = open_archive(&path)?;
+    let conn = open_conn(&handle)?;
+    inspect_conn(&conn, &mut zip)
+}
+
+#[tauri::command]
+pub fn import_archive(handle: AppHandle, path: String, mode: ImportMode) -> Result<ImportResult, String> {
+    let mut zip = open_archive(&path)?;
+    let manifest = read_verified(&mut zip)?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut conn = open_conn(&handle)?;
+    let backup_path = backup(&conn, &handle, &stamp)?;
+    let mut result = import_conn(&mut conn, &mut zip, &manifest, &storage_root(&handle)?, mode, &stamp)?;
+    result.backup_path = Some(backup_path);
+    Ok(result)
+}
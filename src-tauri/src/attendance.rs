@@ -0,0 +1,537 @@
+// Günlük devam takibi: gün başına tek kayıt (geldi / gelmedi / izinli, isteğe
+// bağlı giriş-çıkış saati). Zorunlu staj günü stajyerde (required_days) veya
+// ayarlardaki varsayılanla belirlenir; çalışılan gün, iş günü takvimine göre
+// kalan günlerle karşılaştırılarak risk hesaplanır.
+
+use std::collections::HashMap;
+
+use chrono::{Local, NaiveDate, NaiveTime};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date, WorkCalendar};
+use crate::{leaves, open_conn, settings};
+
+const SETTINGS_KEY: &str = "attendance";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttendanceSettings {
+    // stajyerde required_days yoksa
+    pub default_required_days: i64,
+    // izinli (raporlu) günler çalışılmış sayılsın mı
+    pub excused_counts_as_worked: bool,
+    // kalan pay bu kadar gün veya altındaysa uyarı
+    pub warning_margin_days: i64,
+}
+
+impl Default for AttendanceSettings {
+    fn default() -> Self {
+        AttendanceSettings { default_required_days: 20, excused_counts_as_worked: false, warning_margin_days: 2 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttendanceStatus {
+    Present,
+    Absent,
+    Excused,
+}
+
+impl AttendanceStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            AttendanceStatus::Present => "present",
+            AttendanceStatus::Absent => "absent",
+            AttendanceStatus::Excused => "excused",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttendanceEntry {
+    pub intern_id: i64,
+    pub date: String,
+    // verilmezse giriş saati varsa "present"
+    pub status: Option<AttendanceStatus>,
+    // SS:DD
+    pub check_in: Option<String>,
+    pub check_out: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttendanceRecord {
+    pub id: i64,
+    pub intern_id: i64,
+    pub date: String,
+    pub status: String,
+    pub check_in: Option<String>,
+    pub check_out: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyAttendance {
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub department: String,
+    // kayıt yoksa None
+    pub record: Option<AttendanceRecord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    Ok,
+    Warning,
+    // kalan günlerin tamamına gelse bile yetmiyor
+    Short,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttendanceSummary {
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub required_days: i64,
+    // start_date–end_date arası iş günü (bitiş yoksa None)
+    pub planned_working_days: Option<i64>,
+    pub worked_days: i64,
+    pub present_days: i64,
+    pub absent_days: i64,
+    pub excused_days: i64,
+    // devam kaydı olmayan onaylı izin günleri (worked/excused içinde)
+    pub leave_days: i64,
+    // bugünden önceki, kaydı girilmemiş iş günleri
+    pub unrecorded_days: i64,
+    // bugünden bitişe kadar kaydı olmayan iş günleri
+    pub remaining_working_days: Option<i64>,
+    // kaç gün daha gelmeyebilir (negatif: açık); yalnızca kayıtlı devam
+    // sayılır, geçmişte girilmemiş günler çalışılmış varsayılmaz
+    pub margin_days: Option<i64>,
+    pub risk: RiskLevel,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepartmentAttendance {
+    pub date: String,
+    pub department: String,
+    pub status: AttendanceStatus,
+    // varsayılandan farklı olanlar
+    #[serde(default)]
+    pub exceptions: Vec<AttendanceEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AttendanceFailure {
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DepartmentAttendanceResult {
+    pub recorded: Vec<i64>,
+    // yazılamayanlar; diğerlerinin kaydını engellemez
+    pub failed: Vec<AttendanceFailure>,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS attendance (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            intern_id INTEGER NOT NULL,
+            date TEXT NOT NULL,
+            status TEXT NOT NULL CHECK (status IN ('present', 'absent', 'excused')),
+            check_in TEXT,
+            check_out TEXT,
+            note TEXT,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (intern_id, date),
+            FOREIGN KEY (intern_id) REFERENCES interns(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_attendance_date ON attendance(date);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<AttendanceSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+fn parse_time(s: Option<&str>) -> Result<Option<NaiveTime>, String> {
+    match s.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(t) => NaiveTime::parse_from_str(t, "%H:%M")
+            .map(Some)
+            .map_err(|_| format!("Geçersiz saat: {t} (SS:DD bekleniyor)")),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn record_conn(conn: &Connection, e: &AttendanceEntry) -> Result<(), String> {
+    let day = parse_date(&e.date)?;
+    let (check_in, check_out) = (parse_time(e.check_in.as_deref())?, parse_time(e.check_out.as_deref())?);
+    if let (Some(a), Some(b)) = (check_in, check_out) {
+        if b <= a {
+            return Err("Çıkış saati girişten sonra olmalı".to_string());
+        }
+    }
+    let status = match (e.status, check_in) {
+        (Some(s), _) => s,
+        (None, Some(_)) => AttendanceStatus::Present,
+        (None, None) => return Err("Devam durumu veya giriş saati gerekli".to_string()),
+    };
+    if status != AttendanceStatus::Present && (check_in.is_some() || check_out.is_some()) {
+        return Err("Giriş-çıkış saati yalnızca gelen stajyer için girilebilir".to_string());
+    }
+
+    let (start, end): (String, Option<String>) = conn
+        .query_row("SELECT start_date, end_date FROM interns WHERE id = ?1", params![e.intern_id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let end = end.filter(|d| !d.trim().is_empty()).map(|d| parse_date(&d)).transpose()?;
+    if day < parse_date(&start)? || end.is_some_and(|end| day > end) {
+        return Err(format!("{} staj tarihleri dışında", format_date(day)));
+    }
+
+    let hhmm = |t: Option<NaiveTime>| t.map(|t| t.format("%H:%M").to_string());
+    conn.execute(
+        r#"
+        INSERT INTO attendance (intern_id, date, status, check_in, check_out, note)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        ON CONFLICT(intern_id, date) DO UPDATE SET
+            status = excluded.status,
+            check_in = excluded.check_in,
+            check_out = excluded.check_out,
+            note = excluded.note,
+            updated_at = datetime('now')
+        "#,
+        params![e.intern_id, format_date(day), status.as_str(), hhmm(check_in), hhmm(check_out), e.note],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn record_from_row(r: &rusqlite::Row) -> rusqlite::Result<AttendanceRecord> {
+    Ok(AttendanceRecord {
+        id: r.get(0)?,
+        intern_id: r.get(1)?,
+        date: r.get(2)?,
+        status: r.get(3)?,
+        check_in: r.get(4)?,
+        check_out: r.get(5)?,
+        note: r.get(6)?,
+    })
+}
+
+const RECORD_COLUMNS: &str = "a.id, a.intern_id, a.date, a.status, a.check_in, a.check_out, a.note";
+
+// O gün stajı devam eden stajyerler ve (varsa) devam kayıtları
+pub(crate) fn daily_conn(conn: &Connection, day: &str, department: Option<&str>) -> Result<Vec<DailyAttendance>, String> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT i.id, i.first_name || ' ' || i.last_name, i.department, {RECORD_COLUMNS}
+        FROM interns i
+        LEFT JOIN attendance a ON a.intern_id = i.id AND a.date = ?1
+        WHERE i.start_date <= ?1 AND (i.end_date IS NULL OR i.end_date = '' OR i.end_date >= ?1)
+          AND (?2 IS NULL OR i.department = ?2)
+        ORDER BY i.department, i.last_name, i.first_name
+        "#
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![day, department], |r| {
+        let record_id: Option<i64> = r.get(3)?;
+        Ok(DailyAttendance {
+            intern_id: r.get(0)?,
+            intern_name: r.get(1)?,
+            department: r.get(2)?,
+            record: match record_id {
+                Some(id) => Some(AttendanceRecord {
+                    id,
+                    intern_id: r.get(4)?,
+                    date: r.get(5)?,
+                    status: r.get(6)?,
+                    check_in: r.get(7)?,
+                    check_out: r.get(8)?,
+                    note: r.get(9)?,
+                }),
+                None => None,
+            },
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+pub(crate) fn summary_conn(
+    conn: &Connection,
+    cal: &WorkCalendar,
+    s: &AttendanceSettings,
+    intern_id: i64,
+    today: NaiveDate,
+) -> Result<AttendanceSummary, String> {
+    let (intern_name, start, end, required): (String, String, Option<String>, Option<i64>) = conn
+        .query_row(
+            "SELECT first_name || ' ' || last_name, start_date, end_date, required_days FROM interns WHERE id = ?1",
+            params![intern_id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let start = parse_date(&start)?;
+    let end = end.filter(|d| !d.trim().is_empty()).map(|d| parse_date(&d)).transpose()?;
+    let required_days = required.unwrap_or(s.default_required_days);
+
+    // staj aralığındaki kayıtlar
+    let mut recorded: HashMap<NaiveDate, String> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT date, status FROM attendance WHERE intern_id = ?1 AND date >= ?2 AND (?3 IS NULL OR date <= ?3)"
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![intern_id, format_date(start), end.map(format_date)], |r| {
+            Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    for r in rows {
+        let (d, status) = r.map_err(|e| e.to_string())?;
+        recorded.insert(parse_date(&d)?, status);
+    }
+
+    // kaydı girilmemiş onaylı izin günleri; çalışılmış sayılmayan türler
+    // mazeretli gün gibi işlenir
+    let mut leave: HashMap<NaiveDate, bool> = leaves::approved_leave_days(conn, cal, intern_id)?;
+    let last = end.unwrap_or(NaiveDate::MAX);
+    leave.retain(|d, _| *d >= start && *d <= last && !recorded.contains_key(d));
+    let leave_worked = leave.values().filter(|c| **c).count() as i64;
+    let leave_days = leave.len() as i64;
+
+    let count = |st: &str| recorded.values().filter(|v| v.as_str() == st).count() as i64;
+    let (present_days, absent_days) = (count("present"), count("absent"));
+    let excused_days = count("excused") + leave_days - leave_worked;
+    let worked_days = present_days + leave_worked + if s.excused_counts_as_worked { excused_days } else { 0 };
+    let open = |d: &&NaiveDate| !recorded.contains_key(*d) && !leave.contains_key(*d);
+
+    let yesterday = today.pred_opt().unwrap_or(today);
+    let elapsed_end = end.map_or(yesterday, |e| e.min(yesterday));
+    let unrecorded_days = if elapsed_end >= start {
+        cal.working_days(start, elapsed_end).iter().filter(open).count() as i64
+    } else {
+        0
+    };
+
+    let (planned_working_days, remaining_working_days) = match end {
+        Some(end) => {
+            let from = today.max(start);
+            let remaining = if end >= from {
+                cal.working_days(from, end).iter().filter(open).count() as i64
+            } else {
+                0
+            };
+            (Some(cal.count_working_days(start, end)), Some(remaining))
+        }
+        None => (None, None),
+    };
+    let margin_days = remaining_working_days.map(|r| worked_days + r - required_days);
+    let risk = match margin_days {
+        Some(m) if m < 0 => RiskLevel::Short,
+        Some(m) if m <= s.warning_margin_days && worked_days < required_days => RiskLevel::Warning,
+        _ => RiskLevel::Ok,
+    };
+
+    Ok(AttendanceSummary {
+        intern_id,
+        intern_name,
+        required_days,
+        planned_working_days,
+        worked_days,
+        present_days,
+        absent_days,
+        excused_days,
+        leave_days,
+        unrecorded_days,
+        remaining_working_days,
+        margin_days,
+        risk,
+    })
+}
+
+/// Bugün stajı süren ve zorunlu günü tamamlayamama riski olan stajyerler.
+pub(crate) fn warnings_conn(conn: &Connection, today: NaiveDate) -> Result<Vec<AttendanceSummary>, String> {
+    let cal = WorkCalendar::load(conn)?;
+    let s = load_settings(conn)?;
+    let day = format_date(today);
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id FROM interns
+        WHERE start_date <= ?1 AND end_date IS NOT NULL AND end_date <> '' AND end_date >= ?1
+        ORDER BY last_name, first_name
+        "#
+    ).map_err(|e| e.to_string())?;
+    let ids = stmt
+        .query_map(params![day], |r| r.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut out = Vec::new();
+    for id in ids {
+        let summary = summary_conn(conn, &cal, &s, id, today)?;
+        if summary.risk != RiskLevel::Ok {
+            out.push(summary);
+        }
+    }
+    // en kritik olan önce
+    out.sort_by_key(|s| s.margin_days.unwrap_or(i64::MAX));
+    Ok(out)
+}
+
+fn parse_today(today: Option<String>) -> Result<NaiveDate, String> {
+    match today.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(d) => parse_date(d),
+        None => Ok(Local::now().date_naive()),
+    }
+}
+
+#[tauri::command]
+pub fn record_attendance(handle: AppHandle, entry: AttendanceEntry) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    record_conn(&conn, &entry)
+}
+
+// Bölümdeki o gün stajı süren herkese aynı durum; istisnalar ayrıca yazılır.
+// Geçersiz bir kayıt yalnızca o stajyeri atlar.
+#[tauri::command]
+pub fn record_department_attendance(handle: AppHandle, entry: DepartmentAttendance) -> Result<DepartmentAttendanceResult, String> {
+    let mut conn = open_conn(&handle)?;
+    let day = format_date(parse_date(&entry.date)?);
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let interns = daily_conn(&tx, &day, Some(entry.department.trim()))?;
+    let mut result = DepartmentAttendanceResult::default();
+    for x in &entry.exceptions {
+        if !interns.iter().any(|i| i.intern_id == x.intern_id) {
+            result.failed.push(AttendanceFailure {
+                intern_id: x.intern_id,
+                intern_name: String::new(),
+                reason: format!("{day} tarihinde bu bölümde stajı süren stajyer değil"),
+            });
+        }
+    }
+    for i in &interns {
+        let exception = entry.exceptions.iter().find(|x| x.intern_id == i.intern_id);
+        let e = match exception {
+            Some(x) => AttendanceEntry {
+                intern_id: i.intern_id,
+                date: day.clone(),
+                status: x.status,
+                check_in: x.check_in.clone(),
+                check_out: x.check_out.clone(),
+                note: x.note.clone(),
+            },
+            None => AttendanceEntry {
+                intern_id: i.intern_id,
+                date: day.clone(),
+                status: Some(entry.status),
+                check_in: None,
+                check_out: None,
+                note: None,
+            },
+        };
+        match record_conn(&tx, &e) {
+            Ok(()) => result.recorded.push(i.intern_id),
+            Err(reason) => result.failed.push(AttendanceFailure {
+                intern_id: i.intern_id,
+                intern_name: i.intern_name.clone(),
+                reason,
+            }),
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn delete_attendance(handle: AppHandle, intern_id: i64, date: String) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        "DELETE FROM attendance WHERE intern_id = ?1 AND date = ?2",
+        params![intern_id, format_date(parse_date(&date)?)],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_attendance(handle: AppHandle, intern_id: i64, from: Option<String>, to: Option<String>) -> Result<Vec<AttendanceRecord>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT {RECORD_COLUMNS} FROM attendance a
+        WHERE a.intern_id = ?1 AND (?2 IS NULL OR a.date >= ?2) AND (?3 IS NULL OR a.date <= ?3)
+        ORDER BY a.date
+        "#
+    )).map_err(|e| e.to_string())?;
+    let from = from.filter(|d| !d.trim().is_empty()).map(|d| parse_date(&d).map(format_date)).transpose()?;
+    let to = to.filter(|d| !d.trim().is_empty()).map(|d| parse_date(&d).map(format_date)).transpose()?;
+    let rows = stmt.query_map(params![intern_id, from, to], record_from_row).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+#[tauri::command]
+pub fn get_daily_attendance(handle: AppHandle, date: String, department: Option<String>) -> Result<Vec<DailyAttendance>, String> {
+    let conn = open_conn(&handle)?;
+    let day = format_date(parse_date(&date)?);
+    daily_conn(&conn, &day, department.as_deref().map(str::trim).filter(|d| !d.is_empty()))
+}
+
+#[tauri::command]
+pub fn attendance_summary(handle: AppHandle, intern_id: i64, today: Option<String>) -> Result<AttendanceSummary, String> {
+    let conn = open_conn(&handle)?;
+    let cal = WorkCalendar::load(&conn)?;
+    let s = load_settings(&conn)?;
+    summary_conn(&conn, &cal, &s, intern_id, parse_today(today)?)
+}
+
+#[tauri::command]
+pub fn attendance_warnings(handle: AppHandle, today: Option<String>) -> Result<Vec<AttendanceSummary>, String> {
+    let conn = open_conn(&handle)?;
+    warnings_conn(&conn, parse_today(today)?)
+}
+
+// days = None: ayarlardaki varsayılan kullanılır
+#[tauri::command]
+pub fn set_required_days(handle: AppHandle, intern_id: i64, days: Option<i64>) -> Result<(), String> {
+    if days.is_some_and(|d| !(0..=365).contains(&d)) {
+        return Err("Zorunlu gün sayısı 0 ile 365 arasında olmalı".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    let n = conn.execute("UPDATE interns SET required_days = ?1 WHERE id = ?2", params![days, intern_id])
+        .map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Stajyer bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_attendance_settings(handle: AppHandle) -> Result<AttendanceSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_attendance_settings(handle: AppHandle, settings: AttendanceSettings) -> Result<(), String> {
+    if !(0..=365).contains(&settings.default_required_days) || settings.warning_margin_days < 0 {
+        return Err("Geçersiz devam ayarı".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
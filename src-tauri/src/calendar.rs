@@ -0,0 +1,224 @@
+// İş günü takvimi: hafta sonu + Türkiye resmi tatilleri + elle eklenen günler.
+// Dini bayramlar ay takvimine bağlı olduğu için yıl yıl tabloda tutulur;
+// tabloda olmayan yıllar için extra_holidays ile tamamlanabilir.
+
+use std::collections::HashSet;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{open_conn, settings};
+
+const SETTINGS_KEY: &str = "work_calendar";
+// add_working_days için üst sınır (~10 yıl)
+const MAX_SCAN_DAYS: usize = 3660;
+
+// Sabit tarihli resmi tatiller (ay, gün)
+const TR_FIXED_HOLIDAYS: &[(u32, u32, &str)] = &[
+    (1, 1, "Yılbaşı"),
+    (4, 23, "Ulusal Egemenlik ve Çocuk Bayramı"),
+    (5, 1, "Emek ve Dayanışma Günü"),
+    (5, 19, "Atatürk'ü Anma, Gençlik ve Spor Bayramı"),
+    (7, 15, "Demokrasi ve Milli Birlik Günü"),
+    (8, 30, "Zafer Bayramı"),
+    (10, 29, "Cumhuriyet Bayramı"),
+];
+
+// Bayramların ilk günleri (Ramazan 3 gün, Kurban 4 gün). Arife yarım gün
+// olduğu için iş günü sayılır.
+const TR_RELIGIOUS_HOLIDAYS: &[(&str, &str)] = &[
+    ("2024-04-10", "2024-06-16"),
+    ("2025-03-30", "2025-06-06"),
+    ("2026-03-20", "2026-05-27"),
+    ("2027-03-09", "2027-05-16"),
+    ("2028-02-26", "2028-05-05"),
+    ("2029-02-14", "2029-04-24"),
+    ("2030-02-04", "2030-04-13"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalendarSettings {
+    // ISO gün numaraları (1 = Pazartesi ... 7 = Pazar)
+    pub weekend_days: Vec<u32>,
+    pub include_tr_holidays: bool,
+    // YYYY-MM-DD
+    pub extra_holidays: Vec<String>,
+    // hafta sonu / tatil olsa da çalışılan günler (telafi günleri vb.)
+    pub extra_workdays: Vec<String>,
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        CalendarSettings {
+            weekend_days: vec![6, 7],
+            include_tr_holidays: true,
+            extra_holidays: Vec::new(),
+            extra_workdays: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Holiday {
+    pub date: String,
+    pub name: String,
+}
+
+pub(crate) fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    let s = s.trim();
+    // "YYYY-MM-DD" veya "YYYY-MM-DDTHH:MM..." kabul edilir
+    NaiveDate::parse_from_str(s.get(..10).unwrap_or(s), "%Y-%m-%d")
+        .map_err(|_| format!("Geçersiz tarih: {s}"))
+}
+
+pub(crate) fn format_date(d: NaiveDate) -> String {
+    d.format("%Y-%m-%d").to_string()
+}
+
+fn religious_holidays() -> Vec<(NaiveDate, String)> {
+    let mut out = Vec::new();
+    for (ramazan, kurban) in TR_RELIGIOUS_HOLIDAYS {
+        for (first, days, name) in [(ramazan, 3, "Ramazan Bayramı"), (kurban, 4, "Kurban Bayramı")] {
+            let Ok(first) = parse_date(first) else { continue };
+            for i in 0..days {
+                out.push((first + Duration::days(i), format!("{name} {}. gün", i + 1)));
+            }
+        }
+    }
+    out
+}
+
+fn religious_days() -> HashSet<NaiveDate> {
+    religious_holidays().into_iter().map(|(d, _)| d).collect()
+}
+
+pub(crate) fn tr_holidays(year: i32) -> Vec<(NaiveDate, String)> {
+    let mut out: Vec<(NaiveDate, String)> = TR_FIXED_HOLIDAYS
+        .iter()
+        .filter_map(|(m, d, name)| NaiveDate::from_ymd_opt(year, *m, *d).map(|date| (date, name.to_string())))
+        .collect();
+    out.extend(religious_holidays().into_iter().filter(|(d, _)| d.year() == year));
+    out.sort();
+    out
+}
+
+pub(crate) struct WorkCalendar {
+    weekend: HashSet<Weekday>,
+    holidays: HashSet<NaiveDate>,
+    workdays: HashSet<NaiveDate>,
+    include_tr: bool,
+    tr_religious: HashSet<NaiveDate>,
+}
+
+impl WorkCalendar {
+    pub fn new(s: &CalendarSettings) -> Result<Self, String> {
+        let weekend = s
+            .weekend_days
+            .iter()
+            .filter_map(|d| Weekday::try_from((*d as u8).wrapping_sub(1)).ok())
+            .collect();
+        let holidays = s.extra_holidays.iter().map(|d| parse_date(d)).collect::<Result<_, _>>()?;
+        let workdays = s.extra_workdays.iter().map(|d| parse_date(d)).collect::<Result<_, _>>()?;
+        Ok(WorkCalendar {
+            weekend,
+            holidays,
+            workdays,
+            include_tr: s.include_tr_holidays,
+            tr_religious: religious_days(),
+        })
+    }
+
+    pub fn load(conn: &Connection) -> Result<Self, String> {
+        Self::new(&load_settings(conn)?)
+    }
+
+    fn is_tr_holiday(&self, d: NaiveDate) -> bool {
+        if !self.include_tr {
+            return false;
+        }
+        TR_FIXED_HOLIDAYS.iter().any(|(m, day, _)| d.month() == *m && d.day() == *day)
+            || self.tr_religious.contains(&d)
+    }
+
+    pub fn is_working_day(&self, d: NaiveDate) -> bool {
+        if self.workdays.contains(&d) {
+            return true;
+        }
+        !(self.weekend.contains(&d.weekday()) || self.holidays.contains(&d) || self.is_tr_holiday(d))
+    }
+
+    // [from, to] aralığındaki iş günleri
+    pub fn working_days(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        from.iter_days()
+            .take_while(|d| *d <= to)
+            .filter(|d| self.is_working_day(*d))
+            .collect()
+    }
+
+    pub fn count_working_days(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        self.working_days(from, to).len() as i64
+    }
+
+    // d'den sonraki n'inci iş günü (n = 0: d). En fazla MAX_SCAN_DAYS ileri
+    // bakılır; bulunamazsa d döner.
+    pub fn add_working_days(&self, d: NaiveDate, n: i64) -> NaiveDate {
+        if n <= 0 {
+            return d;
+        }
+        d.iter_days()
+            .skip(1)
+            .take(MAX_SCAN_DAYS)
+            .filter(|x| self.is_working_day(*x))
+            .nth(n as usize - 1)
+            .unwrap_or(d)
+    }
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<CalendarSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+#[tauri::command]
+pub fn get_calendar_settings(handle: AppHandle) -> Result<CalendarSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_calendar_settings(handle: AppHandle, settings: CalendarSettings) -> Result<(), String> {
+    if settings.weekend_days.iter().any(|d| !(1..=7).contains(d)) {
+        return Err("Hafta sonu günleri 1 (Pazartesi) ile 7 (Pazar) arasında olmalı".to_string());
+    }
+    if (1..=7).all(|d| settings.weekend_days.contains(&d)) {
+        return Err("Haftada en az bir iş günü olmalı".to_string());
+    }
+    // tarihleri doğrula
+    WorkCalendar::new(&settings)?;
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
+
+#[tauri::command]
+pub fn list_holidays(handle: AppHandle, year: i32) -> Result<Vec<Holiday>, String> {
+    let conn = open_conn(&handle)?;
+    let s = load_settings(&conn)?;
+    let mut out: Vec<(NaiveDate, String)> = if s.include_tr_holidays { tr_holidays(year) } else { Vec::new() };
+    for d in &s.extra_holidays {
+        let d = parse_date(d)?;
+        if d.year() == year {
+            out.push((d, "Ek tatil".to_string()));
+        }
+    }
+    out.sort();
+    Ok(out.into_iter().map(|(d, name)| Holiday { date: format_date(d), name }).collect())
+}
+
+#[tauri::command]
+pub fn count_working_days(handle: AppHandle, from: String, to: String) -> Result<i64, String> {
+    let conn = open_conn(&handle)?;
+    let cal = WorkCalendar::load(&conn)?;
+    Ok(cal.count_working_days(parse_date(&from)?, parse_date(&to)?))
+}
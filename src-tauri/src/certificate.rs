@@ -0,0 +1,398 @@
+// Staj tamamlama belgesi (PDF). Şablon ayarlarda tutulur; her belge için
+// benzersiz bir doğrulama kodu certificates tablosuna yazılır. Belgelenen
+// bilgiler (tarihler, iş günü) değişirse yeni kod verilir, eskisi iptal edilir.
+
+use std::collections::HashMap;
+use std::fs;
+
+use rand::Rng;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{parse_date, WorkCalendar};
+use crate::pdf::{self, Align, Font, Page, PdfDocument, Style, BLACK, GRAY};
+use crate::{add_column_if_missing, open_conn, person_dir, settings, templates};
+
+const SETTINGS_KEY: &str = "certificate_template";
+const BODY_FIELDS: &[&str] = &["full_name", "school", "department", "start_date", "end_date", "working_days", "organization"];
+// karışması kolay harfler (0/O, 1/I) yok
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CertificateTemplate {
+    pub organization_name: String,
+    // JPEG / PNG dosya yolu
+    pub logo_path: Option<String>,
+    pub title: String,
+    // {full_name} {school} {department} {start_date} {end_date} {working_days} {organization}
+    pub body: String,
+    pub signatory_name: String,
+    pub signatory_title: String,
+    pub city: String,
+}
+
+impl Default for CertificateTemplate {
+    fn default() -> Self {
+        CertificateTemplate {
+            organization_name: String::new(),
+            logo_path: None,
+            title: "STAJ TAMAMLAMA BELGESİ".to_string(),
+            body: "{full_name}, {school} {department} öğrencisi olarak {start_date} – {end_date} \
+                   tarihleri arasında {organization} bünyesinde toplam {working_days} iş günü \
+                   stajını başarıyla tamamlamıştır."
+                .to_string(),
+            signatory_name: String::new(),
+            signatory_title: String::new(),
+            city: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertificateResult {
+    pub intern_id: i64,
+    pub code: String,
+    pub file_path: String,
+    pub working_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CertificateInfo {
+    pub code: String,
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub school: String,
+    pub department: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub working_days: i64,
+    pub issued_at: String,
+    // dolu ise belge geçersiz (yerine yenisi verildi)
+    pub revoked_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchFailure {
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BatchResult {
+    pub generated: Vec<CertificateResult>,
+    pub failed: Vec<BatchFailure>,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS certificates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            intern_id INTEGER NOT NULL,
+            code TEXT NOT NULL UNIQUE,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            working_days INTEGER NOT NULL,
+            file_path TEXT,
+            issued_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (intern_id) REFERENCES interns(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_certificates_intern ON certificates(intern_id);
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    add_column_if_missing(conn, "certificates", "revoked_at", "TEXT")
+}
+
+pub(crate) fn load_template(conn: &Connection) -> Result<CertificateTemplate, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+// GG.AA.YYYY
+pub(crate) fn display_date(s: &str) -> String {
+    parse_date(s).map(|d| d.format("%d.%m.%Y").to_string()).unwrap_or_else(|_| s.to_string())
+}
+
+fn new_code() -> String {
+    let mut rng = rand::thread_rng();
+    let part = |rng: &mut rand::rngs::ThreadRng| -> String {
+        (0..4).map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char).collect()
+    };
+    format!("ITC-{}-{}-{}", part(&mut rng), part(&mut rng), part(&mut rng))
+}
+
+struct InternInfo {
+    id: i64,
+    first_name: String,
+    last_name: String,
+    school: String,
+    department: String,
+    start_date: String,
+    end_date: Option<String>,
+}
+
+fn load_intern(conn: &Connection, id: i64) -> Result<InternInfo, String> {
+    conn.query_row(
+        "SELECT id, first_name, last_name, school, department, start_date, end_date FROM interns WHERE id = ?1",
+        params![id],
+        |r| {
+            Ok(InternInfo {
+                id: r.get(0)?,
+                first_name: r.get(1)?,
+                last_name: r.get(2)?,
+                school: r.get(3)?,
+                department: r.get(4)?,
+                start_date: r.get(5)?,
+                end_date: r.get(6)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Stajyer bulunamadı".to_string())
+}
+
+pub(crate) fn render_certificate(
+    t: &CertificateTemplate,
+    logo: Option<&[u8]>,
+    full_name: &str,
+    body: &str,
+    code: &str,
+    issued_on: &str,
+) -> Result<Vec<u8>, String> {
+    let mut doc = PdfDocument::new(pdf::A4_LANDSCAPE, &format!("{} - {full_name}", t.title));
+    let (w, h) = (doc.width(), doc.height());
+    let logo = match logo {
+        Some(bytes) => Some(doc.add_image(bytes)?),
+        None => None,
+    };
+
+    let mut p = Page::new();
+    p.rect(24.0, 24.0, w - 48.0, h - 48.0, None, Some((BLACK, 2.0)));
+    p.rect(32.0, 32.0, w - 64.0, h - 64.0, None, Some((GRAY, 0.6)));
+
+    let mut y = h - 70.0;
+    if let Some(img) = logo {
+        p.image_fit(img, w / 2.0 - 60.0, y - 70.0, 120.0, 70.0);
+        y -= 90.0;
+    }
+    if !t.organization_name.is_empty() {
+        p.text_at(w / 2.0, y, Style::new(16.0, Font::Bold).color(GRAY), Align::Center, &t.organization_name);
+        y -= 50.0;
+    }
+    p.text_at(w / 2.0, y, Style::new(30.0, Font::Bold), Align::Center, &t.title);
+    y -= 60.0;
+    p.text_at(w / 2.0, y, Style::new(24.0, Font::Italic), Align::Center, full_name);
+    y -= 40.0;
+    p.paragraph(110.0, y, w - 220.0, Style::new(14.0, Font::Regular), Align::Center, body);
+
+    // imza alanı
+    let sx = w - 250.0;
+    p.line(sx, 130.0, sx + 180.0, 130.0, 0.8, BLACK);
+    p.text_at(sx + 90.0, 114.0, Style::new(12.0, Font::Bold), Align::Center, &t.signatory_name);
+    p.text_at(sx + 90.0, 100.0, Style::new(10.0, Font::Regular).color(GRAY), Align::Center, &t.signatory_title);
+
+    let place = if t.city.is_empty() { issued_on.to_string() } else { format!("{}, {issued_on}", t.city) };
+    p.text_at(70.0, 114.0, Style::new(10.0, Font::Regular), Align::Left, &place);
+    p.text_at(w - 50.0, 44.0, Style::new(8.0, Font::Regular).color(GRAY), Align::Right, &format!("Doğrulama kodu: {code}"));
+
+    doc.add_page(p);
+    doc.to_bytes()
+}
+
+fn fill_body(t: &CertificateTemplate, i: &InternInfo, end_date: &str, working_days: i64) -> Result<String, String> {
+    let vars: HashMap<String, String> = [
+        ("full_name", format!("{} {}", i.first_name, i.last_name)),
+        ("school", i.school.clone()),
+        ("department", i.department.clone()),
+        ("start_date", display_date(&i.start_date)),
+        ("end_date", display_date(end_date)),
+        ("working_days", working_days.to_string()),
+        ("organization", t.organization_name.clone()),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect();
+    templates::render(&t.body, &vars).map(|(body, _)| body)
+}
+
+pub(crate) fn generate_for_intern(handle: &AppHandle, conn: &Connection, intern_id: i64) -> Result<CertificateResult, String> {
+    let intern = load_intern(conn, intern_id)?;
+    let end_date = intern
+        .end_date
+        .clone()
+        .filter(|d| !d.trim().is_empty())
+        .ok_or_else(|| "Stajyerin bitiş tarihi yok".to_string())?;
+    let (start, end) = (parse_date(&intern.start_date)?, parse_date(&end_date)?);
+    if end < start {
+        return Err("Bitiş tarihi başlangıçtan önce".to_string());
+    }
+
+    let template = load_template(conn)?;
+    let working_days = WorkCalendar::load(conn)?.count_working_days(start, end);
+    let logo = match template.logo_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        Some(path) => Some(fs::read(path).map_err(|e| format!("Logo okunamadı: {e}"))?),
+        None => None,
+    };
+
+    // kayıt, PDF ve dosya yolu birlikte: yarıda kalan üretim dosyasız kod bırakmaz
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    // aynı bilgilerle tekrar üretilirse kod korunur; bilgiler değiştiyse eski kod iptal
+    let existing: Option<(i64, String, String, String, i64)> = tx
+        .query_row(
+            "SELECT id, code, start_date, end_date, working_days FROM certificates
+             WHERE intern_id = ?1 AND revoked_at IS NULL ORDER BY id DESC LIMIT 1",
+            params![intern_id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let existing = match existing {
+        Some((id, code, s, e, days)) if s == intern.start_date && e == end_date && days == working_days => Some((id, code)),
+        Some((id, ..)) => {
+            tx.execute("UPDATE certificates SET revoked_at = datetime('now') WHERE id = ?1", params![id])
+                .map_err(|e| e.to_string())?;
+            None
+        }
+        None => None,
+    };
+
+    let reissued = existing.is_none();
+    let (cert_id, code) = match existing {
+        Some(found) => found,
+        None => {
+            let mut attempt = 0;
+            loop {
+                let code = new_code();
+                let res = tx.execute(
+                    "INSERT INTO certificates (intern_id, code, start_date, end_date, working_days) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![intern_id, code, intern.start_date, end_date, working_days],
+                );
+                match res {
+                    Ok(_) => break (tx.last_insert_rowid(), code),
+                    Err(rusqlite::Error::SqliteFailure(err, _))
+                        if err.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE && attempt < 5 =>
+                    {
+                        attempt += 1;
+                    }
+                    Err(e) => return Err(e.to_string()),
+                }
+            }
+        }
+    };
+
+    let full_name = format!("{} {}", intern.first_name, intern.last_name);
+    let issued_on = chrono::Local::now().format("%d.%m.%Y").to_string();
+    let body = fill_body(&template, &intern, &end_date, working_days)?;
+    let bytes = render_certificate(
+        &template,
+        logo.as_deref(),
+        &full_name,
+        &body,
+        &code,
+        &issued_on,
+    )?;
+
+    let dir = person_dir(handle, intern.id, &intern.first_name, &intern.last_name)?;
+    let path = dir.join(format!("sertifika_{code}.pdf"));
+    let file_path = path.to_string_lossy().to_string();
+    tx.execute("UPDATE certificates SET file_path = ?1 WHERE id = ?2", params![file_path, cert_id])
+        .map_err(|e| e.to_string())?;
+    fs::write(&path, bytes).map_err(|e| format!("Sertifika yazılamadı: {e}"))?;
+    if let Err(e) = tx.commit() {
+        if reissued {
+            let _ = fs::remove_file(&path);
+        }
+        return Err(e.to_string());
+    }
+
+    Ok(CertificateResult { intern_id, code, file_path, working_days })
+}
+
+#[tauri::command]
+pub fn get_certificate_template(handle: AppHandle) -> Result<CertificateTemplate, String> {
+    let conn = open_conn(&handle)?;
+    load_template(&conn)
+}
+
+#[tauri::command]
+pub fn update_certificate_template(handle: AppHandle, template: CertificateTemplate) -> Result<(), String> {
+    if template.title.trim().is_empty() || template.body.trim().is_empty() {
+        return Err("Belge başlığı ve metni boş olamaz".to_string());
+    }
+    templates::ensure_known(&template.body, BODY_FIELDS)?;
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &template)
+}
+
+#[tauri::command]
+pub fn generate_certificate(handle: AppHandle, intern_id: i64) -> Result<CertificateResult, String> {
+    let conn = open_conn(&handle)?;
+    generate_for_intern(&handle, &conn, intern_id)
+}
+
+// Bitiş tarihi [from, to] aralığında olan (ve bugün itibarıyla bitmiş) tüm stajyerler
+#[tauri::command]
+pub fn generate_certificates_for_period(handle: AppHandle, from: String, to: String) -> Result<BatchResult, String> {
+    let conn = open_conn(&handle)?;
+    let (from, to) = (parse_date(&from)?, parse_date(&to)?);
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, first_name || ' ' || last_name
+        FROM interns
+        WHERE end_date IS NOT NULL AND end_date <> ''
+          AND date(end_date) BETWEEN ?1 AND ?2
+          AND date(end_date) <= date('now', 'localtime')
+        ORDER BY end_date, last_name, first_name
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![from.to_string(), to.to_string()], |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?;
+    let mut interns = Vec::new();
+    for r in rows { interns.push(r.map_err(|e| e.to_string())?); }
+
+    let mut result = BatchResult::default();
+    for (intern_id, intern_name) in interns {
+        match generate_for_intern(&handle, &conn, intern_id) {
+            Ok(r) => result.generated.push(r),
+            Err(error) => result.failed.push(BatchFailure { intern_id, intern_name, error }),
+        }
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub fn verify_certificate(handle: AppHandle, code: String) -> Result<Option<CertificateInfo>, String> {
+    let conn = open_conn(&handle)?;
+    conn.query_row(
+        r#"
+        SELECT c.code, c.intern_id, i.first_name || ' ' || i.last_name, i.school, i.department,
+               c.start_date, c.end_date, c.working_days, c.issued_at, c.revoked_at
+        FROM certificates c
+        JOIN interns i ON i.id = c.intern_id
+        WHERE c.code = ?1 AND c.file_path IS NOT NULL
+        "#,
+        params![code.trim().to_uppercase()],
+        |r| {
+            Ok(CertificateInfo {
+                code: r.get(0)?,
+                intern_id: r.get(1)?,
+                intern_name: r.get(2)?,
+                school: r.get(3)?,
+                department: r.get(4)?,
+                start_date: r.get(5)?,
+                end_date: r.get(6)?,
+                working_days: r.get(7)?,
+                issued_at: r.get(8)?,
+                revoked_at: r.get(9)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,242 @@
+// Anasayfa özeti: tek komut, tek okuma işlemi (transaction) içinde hesaplanır.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date, WorkCalendar};
+use crate::{
+    app_db_path, intern_lite_from_row, interns_missing_evaluation_conn, open_conn, storage_root,
+    InternLite, INTERN_LITE_COLUMNS,
+};
+
+const LIST_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct DashboardOptions {
+    // başlayan / biten stajlar için kaç gün ileriye bakılacak
+    pub days_ahead: i64,
+    // varsayılan: bugün (yerel saat)
+    pub today: Option<String>,
+    // yalnızca bu staj dönemi
+    pub period_id: Option<i64>,
+}
+
+impl Default for DashboardOptions {
+    fn default() -> Self {
+        DashboardOptions { days_ahead: 14, today: None, period_id: None }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ActiveInterns {
+    pub total: i64,
+    pub by_status: BTreeMap<String, i64>,
+    pub by_department: BTreeMap<String, i64>,
+    pub by_school: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DashboardAssignment {
+    pub id: i64,
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub project_type: String,
+    pub due_date: String,
+    pub status: String,
+    // negatif: gecikme
+    pub days_left: i64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct StorageUsage {
+    pub database_bytes: u64,
+    pub attachments_bytes: u64,
+    pub attachment_files: u64,
+    // DB içindeki CV / fotoğraf BLOB'ları
+    pub blob_bytes: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DashboardSummary {
+    pub today: String,
+    pub active_interns: ActiveInterns,
+    pub starting_soon: Vec<InternLite>,
+    pub ending_soon: Vec<InternLite>,
+    pub overdue_count: i64,
+    pub overdue: Vec<DashboardAssignment>,
+    pub due_this_week_count: i64,
+    pub due_this_week: Vec<DashboardAssignment>,
+    pub missing_evaluations_today: i64,
+    pub storage: StorageUsage,
+}
+
+fn active_interns(conn: &Connection, today: &str, period_id: Option<i64>) -> Result<ActiveInterns, String> {
+    let mut out = ActiveInterns::default();
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT status, department, school, COUNT(*)
+        FROM interns
+        WHERE start_date <= ?1 AND (end_date IS NULL OR end_date = '' OR end_date >= ?1)
+          AND (?2 IS NULL OR period_id = ?2)
+        GROUP BY status, department, school
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![today, period_id], |r| {
+        Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?, r.get::<_, String>(2)?, r.get::<_, i64>(3)?))
+    }).map_err(|e| e.to_string())?;
+    for r in rows {
+        let (status, department, school, n) = r.map_err(|e| e.to_string())?;
+        out.total += n;
+        *out.by_status.entry(status).or_default() += n;
+        *out.by_department.entry(department).or_default() += n;
+        *out.by_school.entry(school).or_default() += n;
+    }
+    Ok(out)
+}
+
+fn interns_by_date(conn: &Connection, column: &str, from: &str, to: &str, period_id: Option<i64>) -> Result<Vec<InternLite>, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {INTERN_LITE_COLUMNS} FROM interns i
+         WHERE i.{column} BETWEEN ?1 AND ?2 AND (?4 IS NULL OR i.period_id = ?4)
+         ORDER BY i.{column}, i.last_name, i.first_name
+         LIMIT ?3"
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![from, to, LIST_LIMIT, period_id], intern_lite_from_row)
+        .map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+// [from, to] aralığında teslimi olan, tamamlanmamış görevler (from = None: öncesi)
+fn open_assignments(
+    conn: &Connection,
+    today: &str,
+    from: Option<&str>,
+    to: &str,
+    period_id: Option<i64>,
+) -> Result<(i64, Vec<DashboardAssignment>), String> {
+    let range = "a.status <> 'Completed' AND date(a.due_date) IS NOT NULL
+                 AND (?2 IS NULL OR date(a.due_date) >= ?2) AND date(a.due_date) <= ?3
+                 AND (?5 IS NULL OR i.period_id = ?5)";
+
+    let count: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM assignments a JOIN interns i ON i.id = a.intern_id WHERE {range}"),
+        params![today, from, to, LIST_LIMIT, period_id],
+        |r| r.get(0),
+    ).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT a.id, a.intern_id, i.first_name || ' ' || i.last_name,
+               a.project_type, date(a.due_date), a.status,
+               CAST(julianday(date(a.due_date)) - julianday(?1) AS INTEGER)
+        FROM assignments a
+        JOIN interns i ON i.id = a.intern_id
+        WHERE {range}
+        ORDER BY a.due_date, a.id
+        LIMIT ?4
+        "#
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![today, from, to, LIST_LIMIT, period_id], |r| {
+        Ok(DashboardAssignment {
+            id: r.get(0)?,
+            intern_id: r.get(1)?,
+            intern_name: r.get(2)?,
+            project_type: r.get(3)?,
+            due_date: r.get(4)?,
+            status: r.get(5)?,
+            days_left: r.get(6)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok((count, out))
+}
+
+fn dir_usage(dir: &Path) -> (u64, u64) {
+    let (mut bytes, mut files) = (0, 0);
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else { continue };
+            if meta.is_dir() {
+                let (b, f) = dir_usage(&entry.path());
+                bytes += b;
+                files += f;
+            } else {
+                bytes += meta.len();
+                files += 1;
+            }
+        }
+    }
+    (bytes, files)
+}
+
+pub(crate) fn summary_conn(
+    conn: &Connection,
+    today: NaiveDate,
+    days_ahead: i64,
+    period_id: Option<i64>,
+) -> Result<DashboardSummary, String> {
+    let days_ahead = days_ahead.clamp(0, 365);
+    let today_s = format_date(today);
+    let horizon = format_date(today + Duration::days(days_ahead));
+    let yesterday = format_date(today - Duration::days(1));
+    let week_end = format_date(today + Duration::days(6 - today.weekday().num_days_from_monday() as i64));
+
+    let cal = WorkCalendar::load(conn)?;
+    let (overdue_count, overdue) = open_assignments(conn, &today_s, None, &yesterday, period_id)?;
+    let (due_this_week_count, due_this_week) = open_assignments(conn, &today_s, Some(&today_s), &week_end, period_id)?;
+    let missing_evaluations_today = interns_missing_evaluation_conn(conn, &cal, today, today)?
+        .iter()
+        .filter(|m| period_id.is_none() || m.intern.period_id == period_id)
+        .count() as i64;
+    let blob_bytes: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(COALESCE(length(cv_blob), 0) + COALESCE(length(photo_blob), 0)), 0)
+         FROM interns WHERE ?1 IS NULL OR period_id = ?1",
+        params![period_id],
+        |r| r.get(0),
+    ).map_err(|e| e.to_string())?;
+
+    Ok(DashboardSummary {
+        active_interns: active_interns(conn, &today_s, period_id)?,
+        starting_soon: interns_by_date(conn, "start_date", &today_s, &horizon, period_id)?,
+        ending_soon: interns_by_date(conn, "end_date", &today_s, &horizon, period_id)?,
+        overdue_count,
+        overdue,
+        due_this_week_count,
+        due_this_week,
+        missing_evaluations_today,
+        storage: StorageUsage { blob_bytes, ..Default::default() },
+        today: today_s,
+    })
+}
+
+#[tauri::command]
+pub fn dashboard_summary(handle: AppHandle, options: Option<DashboardOptions>) -> Result<DashboardSummary, String> {
+    let opts = options.unwrap_or_default();
+    let today = match opts.today.as_deref() {
+        Some(d) if !d.trim().is_empty() => parse_date(d)?,
+        _ => Local::now().date_naive(),
+    };
+
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut summary = summary_conn(&tx, today, opts.days_ahead, opts.period_id)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    let db_path = app_db_path(&handle)?;
+    summary.storage.database_bytes = fs::metadata(&db_path).map(|m| m.len()).unwrap_or(0);
+    let (bytes, files) = dir_usage(&storage_root(&handle)?);
+    summary.storage.attachments_bytes = bytes;
+    summary.storage.attachment_files = files;
+    Ok(summary)
+}
@@ -0,0 +1,183 @@
+// Basit DOCX (WordprocessingML) üretimi: paragraf, tablo ve sayfa sonu.
+// Paket zip crate'iyle (deflate) bellekte yazılır.
+
+use std::io::{Cursor, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::pdf::Align;
+
+fn jc(align: Align) -> &'static str {
+    match align {
+        Align::Left => "left",
+        Align::Center => "center",
+        Align::Right => "right",
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Run {
+    // punto
+    pub size: f32,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl Run {
+    pub fn new(size: f32) -> Self {
+        Run { size, bold: false, italic: false }
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+}
+
+pub(crate) struct DocxDocument {
+    body: String,
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            // XML 1.0'da geçersiz kontrol karakterleri atlanır
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn run_xml(run: Run, text: &str) -> String {
+    let mut props = String::new();
+    if run.bold {
+        props.push_str("<w:b/>");
+    }
+    if run.italic {
+        props.push_str("<w:i/>");
+    }
+    // w:sz yarım punto cinsinden
+    props.push_str(&format!("<w:sz w:val=\"{}\"/>", (run.size * 2.0).round() as u32));
+    // satır sonları <w:br/> olarak korunur
+    let parts: Vec<String> = text
+        .split('\n')
+        .map(|line| format!("<w:t xml:space=\"preserve\">{}</w:t>", escape(line.trim_end_matches('\r'))))
+        .collect();
+    format!("<w:r><w:rPr>{props}</w:rPr>{}</w:r>", parts.join("<w:br/>"))
+}
+
+impl DocxDocument {
+    pub fn new() -> Self {
+        DocxDocument { body: String::new() }
+    }
+
+    pub fn paragraph(&mut self, run: Run, align: Align, text: &str) {
+        self.body.push_str(&format!(
+            "<w:p><w:pPr><w:jc w:val=\"{}\"/><w:spacing w:after=\"120\"/></w:pPr>{}</w:p>",
+            jc(align),
+            run_xml(run, text)
+        ));
+    }
+
+    // Etiket: değer satırı (etiket kalın)
+    pub fn field(&mut self, label: &str, value: &str) {
+        self.body.push_str(&format!(
+            "<w:p><w:pPr><w:spacing w:after=\"60\"/></w:pPr>{}{}</w:p>",
+            run_xml(Run::new(10.0).bold(), &format!("{label}: ")),
+            run_xml(Run::new(10.0), value)
+        ));
+    }
+
+    pub fn space(&mut self) {
+        self.body.push_str("<w:p/>");
+    }
+
+    pub fn page_break(&mut self) {
+        self.body.push_str("<w:p><w:r><w:br w:type=\"page\"/></w:r></w:p>");
+    }
+
+    // widths: içerik genişliğine oran; ilk satır başlık olarak kalın yazılır
+    // ve sayfa geçişlerinde tekrarlanır
+    pub fn table(&mut self, widths: &[f32], header: &[&str], rows: &[Vec<String>]) {
+        // A4, 2 cm kenar boşluğu: ~9638 twip içerik genişliği
+        const CONTENT: f32 = 9638.0;
+        let grid: String = widths
+            .iter()
+            .map(|w| format!("<w:gridCol w:w=\"{}\"/>", (w * CONTENT).round() as u32))
+            .collect();
+        let cell = |w: f32, run: Run, text: &str| {
+            format!(
+                "<w:tc><w:tcPr><w:tcW w:w=\"{}\" w:type=\"dxa\"/></w:tcPr><w:p>{}</w:p></w:tc>",
+                (w * CONTENT).round() as u32,
+                run_xml(run, text)
+            )
+        };
+        let border = "w:val=\"single\" w:sz=\"4\" w:space=\"0\" w:color=\"999999\"";
+        let mut xml = format!(
+            "<w:tbl><w:tblPr><w:tblW w:w=\"{}\" w:type=\"dxa\"/><w:tblBorders>\
+             <w:top {border}/><w:left {border}/><w:bottom {border}/><w:right {border}/>\
+             <w:insideH {border}/><w:insideV {border}/></w:tblBorders></w:tblPr><w:tblGrid>{grid}</w:tblGrid>",
+            CONTENT as u32
+        );
+        xml.push_str("<w:tr><w:trPr><w:tblHeader/></w:trPr>");
+        for (w, title) in widths.iter().zip(header) {
+            xml.push_str(&cell(*w, Run::new(9.0).bold(), title));
+        }
+        xml.push_str("</w:tr>");
+        for row in rows {
+            xml.push_str("<w:tr><w:trPr><w:cantSplit/></w:trPr>");
+            for (w, text) in widths.iter().zip(row) {
+                xml.push_str(&cell(*w, Run::new(9.0), text));
+            }
+            xml.push_str("</w:tr>");
+        }
+        xml.push_str("</w:tbl>");
+        self.body.push_str(&xml);
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let document = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+             <w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+             <w:body>{}<w:sectPr><w:pgSz w:w=\"11906\" w:h=\"16838\"/>\
+             <w:pgMar w:top=\"1134\" w:right=\"1134\" w:bottom=\"1134\" w:left=\"1134\" w:header=\"567\" w:footer=\"567\" w:gutter=\"0\"/>\
+             </w:sectPr></w:body></w:document>",
+            self.body
+        );
+        let content_types = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+            <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+            <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+            <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+            <Override PartName=\"/word/document.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/>\
+            </Types>";
+        let rels = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\
+            <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+            <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"word/document.xml\"/>\
+            </Relationships>";
+
+        // zip "time" özelliği kapalı: tarih sabit (1980-01-01), çıktı tekrarlanabilir
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in [
+            ("[Content_Types].xml", content_types),
+            ("_rels/.rels", rels),
+            ("word/document.xml", document.as_str()),
+        ] {
+            zip.start_file(name, options).map_err(|e| e.to_string())?;
+            zip.write_all(data.as_bytes()).map_err(|e| e.to_string())?;
+        }
+        Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+    }
+}
@@ -0,0 +1,262 @@
+// Alan olayları: stajyer, görev, değerlendirme ve mentor ataması değişiklikleri asıl yazımla
+// aynı işlemde domain_events tablosuna (outbox) eklenir. Olayları dağıtan
+// webhooks modülüdür; emit yalnızca kaydeder ve bekleyen dağıtıcıyı uyandırır.
+// Her olay değişikliği yapanı (actor) da taşır; tablo aynı zamanda denetim
+// kaydıdır. Dağıtılmış olaylar webhook ayarlarındaki saklama süresi sonunda
+// silinir.
+
+use std::cell::RefCell;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::{current_os_user, open_conn};
+
+pub(crate) const INTERN_CREATED: &str = "intern.created";
+pub(crate) const INTERN_UPDATED: &str = "intern.updated";
+pub(crate) const INTERN_DELETED: &str = "intern.deleted";
+pub(crate) const ASSIGNMENT_CREATED: &str = "assignment.created";
+pub(crate) const ASSIGNMENT_UPDATED: &str = "assignment.updated";
+pub(crate) const ASSIGNMENT_COMPLETED: &str = "assignment.completed";
+pub(crate) const ASSIGNMENT_DELETED: &str = "assignment.deleted";
+pub(crate) const EVALUATION_CREATED: &str = "evaluation.created";
+// yalnızca eşitlemeyle gelen değişikliklerde
+pub(crate) const EVALUATION_UPDATED: &str = "evaluation.updated";
+pub(crate) const EVALUATION_DELETED: &str = "evaluation.deleted";
+pub(crate) const MENTOR_ASSIGNED: &str = "mentor.assigned";
+// atama sonlandırıldığında (bitiş tarihi) ya da silindiğinde
+pub(crate) const MENTOR_UNASSIGNED: &str = "mentor.unassigned";
+
+pub(crate) const EVENT_TYPES: &[&str] = &[
+    INTERN_CREATED,
+    INTERN_UPDATED,
+    INTERN_DELETED,
+    ASSIGNMENT_CREATED,
+    ASSIGNMENT_UPDATED,
+    ASSIGNMENT_COMPLETED,
+    ASSIGNMENT_DELETED,
+    EVALUATION_CREATED,
+    EVALUATION_UPDATED,
+    EVALUATION_DELETED,
+    MENTOR_ASSIGNED,
+    MENTOR_UNASSIGNED,
+];
+
+// yeni olay yazıldığında dağıtıcıyı erken uyandırmak için
+static PENDING: Mutex<bool> = Mutex::new(false);
+static WAKE: Condvar = Condvar::new();
+
+thread_local! {
+    // as_actor ile ayarlanır; yoksa işletim sistemi kullanıcısı
+    static ACTOR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Serialize)]
+pub struct DomainEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub entity_id: Option<i64>,
+    pub actor: Option<String>,
+    pub data: Value,
+    pub occurred_at: String,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS domain_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event_type TEXT NOT NULL,
+            entity_id INTEGER,
+            payload TEXT NOT NULL,
+            -- UTC, RFC 3339
+            occurred_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+            -- abonelere dağıtım kayıtları oluşturuldu mu
+            dispatched INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE INDEX IF NOT EXISTS idx_domain_events_dispatched ON domain_events(dispatched, id);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// `f` içinde yazılan olaylar `actor` adına kaydedilir (ör. "api:raporlama").
+pub(crate) fn as_actor<T>(actor: &str, f: impl FnOnce() -> T) -> T {
+    let previous = ACTOR.with(|a| a.replace(Some(actor.to_string())));
+    let out = f();
+    ACTOR.with(|a| *a.borrow_mut() = previous);
+    out
+}
+
+pub(crate) fn emit(conn: &Connection, event_type: &str, entity_id: i64, data: Value) -> Result<i64, String> {
+    let actor = ACTOR.with(|a| a.borrow().clone()).unwrap_or_else(current_os_user);
+    conn.execute(
+        "INSERT INTO domain_events (event_type, entity_id, payload, actor) VALUES (?1, ?2, ?3, ?4)",
+        params![event_type, entity_id, data.to_string(), actor],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    if let Ok(mut p) = PENDING.lock() {
+        *p = true;
+        WAKE.notify_all();
+    }
+    Ok(id)
+}
+
+/// Yeni olay gelene ya da süre dolana kadar bekler.
+pub(crate) fn wait(timeout: Duration) {
+    let Ok(guard) = PENDING.lock() else { return };
+    if let Ok((mut p, _)) = WAKE.wait_timeout_while(guard, timeout, |p| !*p) {
+        *p = false;
+    }
+}
+
+// Olay gövdeleri: dosya içerikleri (blob) gönderilmez
+pub(crate) fn intern_data(conn: &Connection, id: i64) -> Result<Option<Value>, String> {
+    conn.query_row(
+        r#"
+        SELECT id, first_name, last_name, school, department, start_date, end_date,
+               status, contact, email, period_id, uuid
+        FROM interns WHERE id = ?1
+        "#,
+        params![id],
+        |r| {
+            Ok(json!({
+                "id": r.get::<_, i64>(0)?,
+                "first_name": r.get::<_, String>(1)?,
+                "last_name": r.get::<_, String>(2)?,
+                "school": r.get::<_, String>(3)?,
+                "department": r.get::<_, String>(4)?,
+                "start_date": r.get::<_, String>(5)?,
+                "end_date": r.get::<_, Option<String>>(6)?,
+                "status": r.get::<_, String>(7)?,
+                "contact": r.get::<_, String>(8)?,
+                "email": r.get::<_, String>(9)?,
+                "period_id": r.get::<_, Option<i64>>(10)?,
+                "uuid": r.get::<_, Option<String>>(11)?,
+            }))
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn assignment_data(conn: &Connection, id: i64) -> Result<Option<Value>, String> {
+    conn.query_row(
+        r#"
+        SELECT id, intern_id, project_type, task_description, due_date, status, created_at, uuid
+        FROM assignments WHERE id = ?1
+        "#,
+        params![id],
+        |r| {
+            Ok(json!({
+                "id": r.get::<_, i64>(0)?,
+                "intern_id": r.get::<_, i64>(1)?,
+                "project_type": r.get::<_, String>(2)?,
+                "task_description": r.get::<_, String>(3)?,
+                "due_date": r.get::<_, String>(4)?,
+                "status": r.get::<_, String>(5)?,
+                "created_at": r.get::<_, String>(6)?,
+                "uuid": r.get::<_, Option<String>>(7)?,
+            }))
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn evaluation_data(conn: &Connection, id: i64) -> Result<Option<Value>, String> {
+    conn.query_row(
+        r#"
+        SELECT e.id, e.intern_id, e.label, e.score, e.created_at, e.evaluator_id, m.name, e.uuid
+        FROM evaluations e
+        LEFT JOIN mentors m ON m.id = e.evaluator_id
+        WHERE e.id = ?1
+        "#,
+        params![id],
+        |r| {
+            Ok(json!({
+                "id": r.get::<_, i64>(0)?,
+                "intern_id": r.get::<_, i64>(1)?,
+                "label": r.get::<_, String>(2)?,
+                "score": r.get::<_, i64>(3)?,
+                "created_at": r.get::<_, String>(4)?,
+                "evaluator_id": r.get::<_, Option<i64>>(5)?,
+                "evaluator_name": r.get::<_, Option<String>>(6)?,
+                "uuid": r.get::<_, Option<String>>(7)?,
+            }))
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn mentor_assignment_data(conn: &Connection, id: i64) -> Result<Option<Value>, String> {
+    conn.query_row(
+        r#"
+        SELECT x.id, x.intern_id, x.mentor_id, m.name, x.role, x.start_date, x.end_date, i.uuid
+        FROM intern_mentors x
+        JOIN mentors m ON m.id = x.mentor_id
+        JOIN interns i ON i.id = x.intern_id
+        WHERE x.id = ?1
+        "#,
+        params![id],
+        |r| {
+            Ok(json!({
+                "id": r.get::<_, i64>(0)?,
+                "intern_id": r.get::<_, i64>(1)?,
+                "mentor_id": r.get::<_, i64>(2)?,
+                "mentor_name": r.get::<_, String>(3)?,
+                "role": r.get::<_, String>(4)?,
+                "start_date": r.get::<_, String>(5)?,
+                "end_date": r.get::<_, Option<String>>(6)?,
+                "intern_uuid": r.get::<_, Option<String>>(7)?,
+            }))
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Kaydın güncel hâlini olay gövdesi yapar; kayıt yoksa olay yazılmaz.
+pub(crate) fn emit_current(
+    conn: &Connection,
+    event_type: &str,
+    id: i64,
+    load: fn(&Connection, i64) -> Result<Option<Value>, String>,
+) -> Result<(), String> {
+    if let Some(data) = load(conn, id)? {
+        emit(conn, event_type, id, data)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_domain_events(handle: AppHandle, event_type: Option<String>, limit: Option<i64>) -> Result<Vec<DomainEvent>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, event_type, entity_id, payload, occurred_at, actor FROM domain_events
+        WHERE ?1 IS NULL OR event_type = ?1
+        ORDER BY id DESC LIMIT ?2
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![event_type, limit.unwrap_or(100).clamp(1, 1000)], |r| {
+        let payload: String = r.get(3)?;
+        Ok(DomainEvent {
+            id: r.get(0)?,
+            event_type: r.get(1)?,
+            entity_id: r.get(2)?,
+            actor: r.get(5)?,
+            data: serde_json::from_str(&payload).unwrap_or(Value::Null),
+            occurred_at: r.get(4)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
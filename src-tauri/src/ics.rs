@@ -0,0 +1,379 @@
+// iCalendar (RFC 5545) metin üretimi: kaçış, 75 oktette satır katlama ve
+// yerel saatin UTC'ye çevrilmesi. export_calendar teslim tarihlerini, staj
+// başlangıç/bitişlerini ve günlük değerlendirme hatırlatmalarını sabit UID'lerle
+// yazar; dosya yeniden içe aktarıldığında olaylar çoğalmaz, güncellenir.
+
+use std::fs;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{self, format_date, parse_date, WorkCalendar};
+use crate::{open_conn, reminders, slug_tr, storage_root};
+
+pub(crate) const PRODID: &str = "-//InternTracker//TR";
+
+pub(crate) struct IcsWriter {
+    out: String,
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// 75 oktetten uzun satırlar CRLF + boşluk ile bölünür (UTF-8 karakter ortadan kesilmez)
+pub(crate) fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / 74 * 3);
+    let mut width = 0;
+    for ch in line.chars() {
+        let n = ch.len_utf8();
+        if width + n > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += n;
+    }
+    out
+}
+
+impl IcsWriter {
+    // METHOD: PUBLISH (takvim), REQUEST / CANCEL (davetiye)
+    pub fn new(method: &str, name: &str) -> Self {
+        let mut w = IcsWriter { out: String::new() };
+        w.raw("BEGIN", "VCALENDAR");
+        w.raw("VERSION", "2.0");
+        w.raw("PRODID", PRODID);
+        w.raw("CALSCALE", "GREGORIAN");
+        w.raw("METHOD", method);
+        if !name.is_empty() {
+            w.text("X-WR-CALNAME", name);
+        }
+        w
+    }
+
+    // değer kaçışsız yazılır (tarih, sabit değer, mailto: vb.)
+    pub fn raw(&mut self, name: &str, value: &str) {
+        self.out.push_str(&fold(&format!("{name}:{value}")));
+        self.out.push_str("\r\n");
+    }
+
+    pub fn text(&mut self, name: &str, value: &str) {
+        self.raw(name, &escape(value));
+    }
+
+    pub fn begin(&mut self, component: &str) {
+        self.raw("BEGIN", component);
+    }
+
+    pub fn end(&mut self, component: &str) {
+        self.raw("END", component);
+    }
+
+    // ATTENDEE / ORGANIZER: CN parametresi tırnak içinde
+    pub fn person(&mut self, name: &str, params: &str, cn: &str, email: &str) {
+        let cn = cn.replace('"', "'");
+        let sep = if params.is_empty() { "" } else { ";" };
+        self.raw(&format!("{name};CN=\"{cn}\"{sep}{params}"), &format!("mailto:{email}"));
+    }
+
+    pub fn finish(mut self) -> String {
+        self.raw("END", "VCALENDAR");
+        self.out
+    }
+}
+
+// yerel saat → 20250601T070000Z
+pub(crate) fn utc(dt: NaiveDateTime) -> String {
+    let local = Local
+        .from_local_datetime(&dt)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&dt));
+    local.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+pub(crate) fn now_utc() -> String {
+    Utc::now().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// tüm gün olayları için VALUE=DATE biçimi
+pub(crate) fn date(d: NaiveDate) -> String {
+    d.format("%Y%m%d").to_string()
+}
+
+const BYDAY: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CalendarExportOptions {
+    // yalnızca bu mentora atanmış stajyerler
+    pub mentor_id: Option<i64>,
+    pub department: Option<String>,
+    // YYYY-MM-DD; verilmezse bugünden 30 gün öncesi / 180 gün sonrası
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub include_completed: bool,
+    // None: açık
+    pub include_evaluations: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CalendarExportResult {
+    pub file_path: String,
+    pub events: usize,
+}
+
+struct FeedIntern {
+    id: i64,
+    uuid: Option<String>,
+    name: String,
+    department: String,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+}
+
+// UID'lerde kaydın uuid'si; eşitlenen ofislerde aynı kayıt farklı id taşır.
+// uuid'si olmayan eski satırlarda id kullanılır.
+fn row_key(uuid: Option<&str>, id: i64) -> String {
+    uuid.map_or_else(|| id.to_string(), str::to_string)
+}
+
+fn all_day(w: &mut IcsWriter, uid: &str, stamp: &str, day: NaiveDate, summary: &str, description: &str) {
+    w.begin("VEVENT");
+    w.raw("UID", uid);
+    w.raw("DTSTAMP", stamp);
+    w.raw("DTSTART;VALUE=DATE", &date(day));
+    w.raw("DTEND;VALUE=DATE", &date(day + Duration::days(1)));
+    w.text("SUMMARY", summary);
+    if !description.is_empty() {
+        w.text("DESCRIPTION", description);
+    }
+    w.raw("TRANSP", "TRANSPARENT");
+}
+
+// tüm gün olayı gece yarısı başlar; alarm, teslimden lead gün önce 09:00'da
+fn alarm_trigger(lead: i64) -> String {
+    match lead {
+        0 => "PT9H".to_string(),
+        1 => "-PT15H".to_string(),
+        n => format!("-P{}DT15H", n - 1),
+    }
+}
+
+fn filtered_interns(conn: &Connection, opts: &CalendarExportOptions) -> Result<Vec<FeedIntern>, String> {
+    let department = opts.department.as_deref().map(str::trim).filter(|d| !d.is_empty());
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT i.id, i.first_name || ' ' || i.last_name, i.department, i.start_date, i.end_date, i.uuid
+        FROM interns i
+        WHERE (?1 IS NULL OR i.department = ?1)
+          AND (?2 IS NULL OR EXISTS (
+              SELECT 1 FROM intern_mentors m WHERE m.intern_id = i.id AND m.mentor_id = ?2))
+        ORDER BY i.id
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![department, opts.mentor_id], |r| {
+            Ok((
+                r.get::<_, i64>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, String>(2)?,
+                r.get::<_, String>(3)?,
+                r.get::<_, Option<String>>(4)?,
+                r.get::<_, Option<String>>(5)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows {
+        let (id, name, department, start, end, uuid) = r.map_err(|e| e.to_string())?;
+        out.push(FeedIntern {
+            id,
+            uuid,
+            name,
+            department,
+            start: parse_date(&start).ok(),
+            end: end.as_deref().and_then(|d| parse_date(d).ok()),
+        });
+    }
+    Ok(out)
+}
+
+/// Filtrelenmiş stajyerler için takvim; (ics metni, olay sayısı).
+pub(crate) fn calendar_ics(conn: &Connection, opts: &CalendarExportOptions, today: NaiveDate) -> Result<(String, usize), String> {
+    let from = match opts.from.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(d) => parse_date(d)?,
+        None => today - Duration::days(30),
+    };
+    let to = match opts.to.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(d) => parse_date(d)?,
+        None => today + Duration::days(180),
+    };
+    if to < from {
+        return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+    }
+    let in_range = |d: NaiveDate| d >= from && d <= to;
+    let interns = filtered_interns(conn, opts)?;
+    let reminder = reminders::load_settings(conn)?;
+    let stamp = now_utc();
+    let mut events = 0;
+    let mut w = IcsWriter::new("PUBLISH", "InternTracker");
+
+    for i in &interns {
+        for (kind, day, label) in [("start", i.start, "Staj başlangıcı"), ("end", i.end, "Staj bitişi")] {
+            let Some(day) = day.filter(|d| in_range(*d)) else { continue };
+            all_day(&mut w, &format!("intern-{}-{kind}@interntracker", row_key(i.uuid.as_deref(), i.id)), &stamp, day, &format!("{label}: {}", i.name), &i.department);
+            w.raw("CATEGORIES", "Staj");
+            w.end("VEVENT");
+            events += 1;
+        }
+    }
+
+    // görev teslim tarihleri; hatırlatıcı ayarlarındaki gün öncesinde alarm
+    let mut stmt = conn.prepare(
+        "SELECT id, intern_id, project_type, task_description, substr(due_date, 1, 10), status, uuid
+         FROM assignments WHERE substr(due_date, 1, 10) BETWEEN ?1 AND ?2 ORDER BY due_date, id"
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![format_date(from), format_date(to)], |r| {
+            Ok((
+                r.get::<_, i64>(0)?,
+                r.get::<_, i64>(1)?,
+                r.get::<_, String>(2)?,
+                r.get::<_, String>(3)?,
+                r.get::<_, String>(4)?,
+                r.get::<_, String>(5)?,
+                r.get::<_, Option<String>>(6)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    for r in rows {
+        let (id, intern_id, project, task, due, status, uuid) = r.map_err(|e| e.to_string())?;
+        let Some(intern) = interns.iter().find(|i| i.id == intern_id) else { continue };
+        let completed = status == "Completed";
+        if completed && !opts.include_completed {
+            continue;
+        }
+        let Ok(day) = parse_date(&due) else { continue };
+        all_day(
+            &mut w,
+            &format!("assignment-{}@interntracker", row_key(uuid.as_deref(), id)),
+            &stamp,
+            day,
+            &format!("{}Teslim: {project} – {}", if completed { "✓ " } else { "" }, intern.name),
+            &format!("{task}\nDurum: {status}"),
+        );
+        w.raw("CATEGORIES", "Görev");
+        if !completed {
+            for lead in reminder.lead_days.iter().filter(|d| **d >= 0) {
+                w.begin("VALARM");
+                w.raw("ACTION", "DISPLAY");
+                w.text("DESCRIPTION", &format!("Teslim: {project} – {}", intern.name));
+                w.raw("TRIGGER", &alarm_trigger(*lead));
+                w.end("VALARM");
+            }
+        }
+        w.end("VEVENT");
+        events += 1;
+    }
+
+    // günlük değerlendirme: iş günlerinde tekrarlanan tek olay (tatiller EXDATE,
+    // telafi günleri RDATE)
+    if opts.include_evaluations.unwrap_or(true) && reminder.missing_evaluation {
+        let cal_settings = calendar::load_settings(conn)?;
+        let cal = WorkCalendar::new(&cal_settings)?;
+        let weekday_on = |d: NaiveDate| !cal_settings.weekend_days.contains(&d.weekday().number_from_monday());
+        let byday: Vec<&str> = (1..=7u32)
+            .filter(|n| !cal_settings.weekend_days.contains(n))
+            .map(|n| BYDAY[n as usize - 1])
+            .collect();
+        let hour = reminder.missing_evaluation_hour.min(23);
+        for i in &interns {
+            let (Some(start), Some(end)) = (i.start, i.end) else { continue };
+            let (first, last) = (start.max(from), end.min(to));
+            let days = if last >= first { cal.working_days(first, last) } else { Vec::new() };
+            let Some(first_day) = days.first().copied() else { continue };
+            let at = |d: NaiveDate| utc(d.and_hms_opt(hour, 0, 0).unwrap_or_default());
+
+            w.begin("VEVENT");
+            w.raw("UID", &format!("evaluation-{}@interntracker", row_key(i.uuid.as_deref(), i.id)));
+            w.raw("DTSTAMP", &stamp);
+            w.raw("DTSTART", &at(first_day));
+            w.raw("DURATION", "PT15M");
+            // eski ayarlarda tüm günler hafta sonu olabilir: kural yok, günler RDATE ile
+            if !byday.is_empty() {
+                w.raw(
+                    "RRULE",
+                    &format!("FREQ=WEEKLY;BYDAY={};UNTIL={}", byday.join(","), at(last)),
+                );
+            }
+            let mut exdates = Vec::new();
+            let mut rdates = Vec::new();
+            for d in first_day.iter_days().take_while(|d| *d <= last) {
+                match (weekday_on(d), cal.is_working_day(d)) {
+                    (true, false) => exdates.push(at(d)),
+                    // DTSTART zaten ilk tekrar
+                    (false, true) if d != first_day => rdates.push(at(d)),
+                    _ => {}
+                }
+            }
+            if !exdates.is_empty() {
+                w.raw("EXDATE", &exdates.join(","));
+            }
+            if !rdates.is_empty() {
+                w.raw("RDATE", &rdates.join(","));
+            }
+            w.text("SUMMARY", &format!("Değerlendirme notu: {}", i.name));
+            w.raw("CATEGORIES", "Değerlendirme");
+            w.begin("VALARM");
+            w.raw("ACTION", "DISPLAY");
+            w.text("DESCRIPTION", &format!("{} için günlük değerlendirme notunu girin", i.name));
+            w.raw("TRIGGER", "PT0M");
+            w.end("VALARM");
+            w.end("VEVENT");
+            events += 1;
+        }
+    }
+
+    Ok((w.finish(), events))
+}
+
+// output_path verilmezse Masaüstü\InternTracker\calendar altına yazılır
+#[tauri::command]
+pub fn export_calendar(
+    handle: AppHandle,
+    options: Option<CalendarExportOptions>,
+    output_path: Option<String>,
+) -> Result<CalendarExportResult, String> {
+    let conn = open_conn(&handle)?;
+    let options = options.unwrap_or_default();
+    let (body, events) = calendar_ics(&conn, &options, Local::now().date_naive())?;
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p.into(),
+        None => {
+            let dir = storage_root(&handle)?.join("calendar");
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let suffix = match (options.mentor_id, options.department.as_deref().filter(|d| !d.trim().is_empty())) {
+                (Some(id), _) => format!("_mentor_{id}"),
+                (None, Some(dep)) => format!("_{}", slug_tr(dep)),
+                _ => String::new(),
+            };
+            dir.join(format!("interntracker{suffix}.ics"))
+        }
+    };
+    fs::write(&path, body).map_err(|e| format!("Takvim yazılamadı: {e}"))?;
+    Ok(CalendarExportResult { file_path: path.to_string_lossy().to_string(), events })
+}
@@ -0,0 +1,572 @@
+// Mülakat planlama: aday (başvuru) veya stajyer (ara değerlendirme) için
+// görüşmeler. Aynı görüşmeci, aynı salon ya da aynı aday için çakışan saatler
+// reddedilir; her görüşme için iCalendar davetiyesi (.ics) üretilir.
+
+use std::fs;
+
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::ics::{self, IcsWriter};
+use crate::mentors::ensure_mentor_exists;
+use crate::{open_conn, storage_root};
+
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Passed,
+    Failed,
+    NoShow,
+}
+
+impl Outcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Passed => "passed",
+            Outcome::Failed => "failed",
+            Outcome::NoShow => "no_show",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InterviewPayload {
+    // ikisinden biri dolu olmalı
+    pub applicant_id: Option<i64>,
+    pub intern_id: Option<i64>,
+    pub title: String,
+    // "YYYY-MM-DDTHH:MM" (yerel saat)
+    pub start_at: String,
+    pub end_at: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub meeting_link: String,
+    // mentors.id
+    pub interviewer_ids: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReschedulePayload {
+    pub start_at: String,
+    pub end_at: String,
+    // verilmezse mevcut değer korunur
+    pub location: Option<String>,
+    pub meeting_link: Option<String>,
+    pub interviewer_ids: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Interviewer {
+    pub mentor_id: i64,
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Interview {
+    pub id: i64,
+    pub applicant_id: Option<i64>,
+    pub intern_id: Option<i64>,
+    pub candidate_name: String,
+    pub candidate_email: String,
+    pub title: String,
+    pub start_at: String,
+    pub end_at: String,
+    pub location: String,
+    pub meeting_link: String,
+    // scheduled | cancelled | completed
+    pub status: String,
+    pub outcome: Option<String>,
+    pub outcome_note: Option<String>,
+    pub sequence: i64,
+    pub uid: String,
+    pub interviewers: Vec<Interviewer>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InterviewConflict {
+    pub interview_id: i64,
+    // interviewer | location | candidate
+    pub kind: String,
+    pub detail: String,
+    pub start_at: String,
+    pub end_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvitationResult {
+    pub interview_id: i64,
+    pub file_path: String,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS interviews (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            applicant_id INTEGER REFERENCES applicants(id) ON DELETE CASCADE,
+            intern_id INTEGER REFERENCES interns(id) ON DELETE CASCADE,
+            title TEXT NOT NULL,
+            start_at TEXT NOT NULL,
+            end_at TEXT NOT NULL,
+            location TEXT NOT NULL DEFAULT '',
+            meeting_link TEXT NOT NULL DEFAULT '',
+            status TEXT NOT NULL DEFAULT 'scheduled'
+                CHECK (status IN ('scheduled', 'cancelled', 'completed')),
+            outcome TEXT CHECK (outcome IS NULL OR outcome IN ('passed', 'failed', 'no_show')),
+            outcome_note TEXT,
+            -- davetiye güncellemeleri için (RFC 5546)
+            sequence INTEGER NOT NULL DEFAULT 0,
+            uid TEXT NOT NULL UNIQUE,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            CHECK ((applicant_id IS NULL) <> (intern_id IS NULL)),
+            CHECK (end_at > start_at)
+        );
+        CREATE INDEX IF NOT EXISTS idx_interviews_start ON interviews(start_at);
+
+        CREATE TABLE IF NOT EXISTS interview_interviewers (
+            interview_id INTEGER NOT NULL,
+            mentor_id INTEGER NOT NULL,
+            PRIMARY KEY (interview_id, mentor_id),
+            FOREIGN KEY (interview_id) REFERENCES interviews(id) ON DELETE CASCADE,
+            FOREIGN KEY (mentor_id) REFERENCES mentors(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_interview_interviewers_mentor ON interview_interviewers(mentor_id);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn parse_datetime(s: &str) -> Result<NaiveDateTime, String> {
+    let s = s.trim();
+    NaiveDateTime::parse_from_str(s, DATETIME_FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M"))
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S"))
+        .map_err(|_| format!("Geçersiz tarih/saat: {s}"))
+}
+
+fn normalize_range(start: &str, end: &str) -> Result<(String, String), String> {
+    let (start, end) = (parse_datetime(start)?, parse_datetime(end)?);
+    if end <= start {
+        return Err("Bitiş saati başlangıçtan sonra olmalı".to_string());
+    }
+    Ok((start.format(DATETIME_FORMAT).to_string(), end.format(DATETIME_FORMAT).to_string()))
+}
+
+/// Planlanmış görüşmelerle çakışmalar (aynı görüşmeci, salon veya aday).
+pub(crate) fn conflicts_conn(
+    conn: &Connection,
+    start_at: &str,
+    end_at: &str,
+    interviewer_ids: &[i64],
+    location: &str,
+    candidate: (Option<i64>, Option<i64>),
+    exclude: Option<i64>,
+) -> Result<Vec<InterviewConflict>, String> {
+    let mut out = Vec::new();
+    let overlap = "v.status = 'scheduled' AND v.start_at < ?2 AND v.end_at > ?1 AND (?3 IS NULL OR v.id <> ?3)";
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT v.id, m.name, v.start_at, v.end_at
+         FROM interviews v
+         JOIN interview_interviewers x ON x.interview_id = v.id
+         JOIN mentors m ON m.id = x.mentor_id
+         WHERE {overlap} AND x.mentor_id = ?4"
+    )).map_err(|e| e.to_string())?;
+    for mentor_id in interviewer_ids {
+        let rows = stmt
+            .query_map(params![start_at, end_at, exclude, mentor_id], |r| {
+                Ok(InterviewConflict {
+                    interview_id: r.get(0)?,
+                    kind: "interviewer".to_string(),
+                    detail: r.get(1)?,
+                    start_at: r.get(2)?,
+                    end_at: r.get(3)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    }
+
+    // salon adı büyük/küçük harf ve boşluk farkı gözetmeden karşılaştırılır
+    if !location.trim().is_empty() {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT v.id, v.location, v.start_at, v.end_at FROM interviews v
+             WHERE {overlap} AND lower(trim(v.location)) = lower(trim(?4))"
+        )).map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![start_at, end_at, exclude, location], |r| {
+                Ok(InterviewConflict {
+                    interview_id: r.get(0)?,
+                    kind: "location".to_string(),
+                    detail: r.get(1)?,
+                    start_at: r.get(2)?,
+                    end_at: r.get(3)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    }
+
+    let (applicant_id, intern_id) = candidate;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT v.id, v.title, v.start_at, v.end_at FROM interviews v
+         WHERE {overlap} AND (v.applicant_id = ?4 OR v.intern_id = ?5)"
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![start_at, end_at, exclude, applicant_id, intern_id], |r| {
+            Ok(InterviewConflict {
+                interview_id: r.get(0)?,
+                kind: "candidate".to_string(),
+                detail: r.get(1)?,
+                start_at: r.get(2)?,
+                end_at: r.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+fn conflict_error(conflicts: &[InterviewConflict]) -> Result<(), String> {
+    let Some(c) = conflicts.first() else { return Ok(()) };
+    let what = match c.kind.as_str() {
+        "interviewer" => format!("görüşmeci {}", c.detail),
+        "location" => format!("salon {}", c.detail),
+        _ => format!("aday ({})", c.detail),
+    };
+    Err(format!(
+        "Çakışma: {what} {} – {} arasında dolu{}",
+        c.start_at.replace('T', " "),
+        c.end_at.get(11..).unwrap_or(&c.end_at),
+        if conflicts.len() > 1 { format!(" (+{} çakışma)", conflicts.len() - 1) } else { String::new() }
+    ))
+}
+
+fn set_interviewers(conn: &Connection, id: i64, ids: &[i64]) -> Result<(), String> {
+    conn.execute("DELETE FROM interview_interviewers WHERE interview_id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    for mentor_id in ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO interview_interviewers (interview_id, mentor_id) VALUES (?1, ?2)",
+            params![id, mentor_id],
+        ).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+pub(crate) fn create_conn(conn: &Connection, p: &InterviewPayload) -> Result<i64, String> {
+    if p.applicant_id.is_some() == p.intern_id.is_some() {
+        return Err("Görüşme bir adaya veya bir stajyere bağlanmalı".to_string());
+    }
+    if p.title.trim().is_empty() {
+        return Err("Başlık boş olamaz".to_string());
+    }
+    if p.interviewer_ids.is_empty() {
+        return Err("En az bir görüşmeci seçilmeli".to_string());
+    }
+    for id in &p.interviewer_ids {
+        ensure_mentor_exists(conn, *id)?;
+    }
+    let (start_at, end_at) = normalize_range(&p.start_at, &p.end_at)?;
+    conflict_error(&conflicts_conn(
+        conn, &start_at, &end_at, &p.interviewer_ids, &p.location, (p.applicant_id, p.intern_id), None,
+    )?)?;
+
+    let uid = format!("interview-{}-{}@interntracker", chrono::Utc::now().timestamp_millis(), rand::random::<u32>());
+    conn.execute(
+        r#"
+        INSERT INTO interviews (applicant_id, intern_id, title, start_at, end_at, location, meeting_link, uid)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        "#,
+        params![
+            p.applicant_id,
+            p.intern_id,
+            p.title.trim(),
+            start_at,
+            end_at,
+            p.location.trim(),
+            p.meeting_link.trim(),
+            uid
+        ],
+    ).map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    set_interviewers(conn, id, &p.interviewer_ids)?;
+    Ok(id)
+}
+
+pub(crate) fn reschedule_conn(conn: &Connection, id: i64, p: &ReschedulePayload) -> Result<(), String> {
+    let current = get_conn(conn, id)?;
+    if current.status != "scheduled" {
+        return Err("Yalnızca planlanmış görüşmeler değiştirilebilir".to_string());
+    }
+    let interviewer_ids = match &p.interviewer_ids {
+        Some(ids) if ids.is_empty() => return Err("En az bir görüşmeci seçilmeli".to_string()),
+        Some(ids) => {
+            for m in ids {
+                ensure_mentor_exists(conn, *m)?;
+            }
+            ids.clone()
+        }
+        None => current.interviewers.iter().map(|i| i.mentor_id).collect(),
+    };
+    let location = p.location.as_deref().unwrap_or(&current.location).trim().to_string();
+    let link = p.meeting_link.as_deref().unwrap_or(&current.meeting_link).trim().to_string();
+    let (start_at, end_at) = normalize_range(&p.start_at, &p.end_at)?;
+    conflict_error(&conflicts_conn(
+        conn, &start_at, &end_at, &interviewer_ids, &location, (current.applicant_id, current.intern_id), Some(id),
+    )?)?;
+
+    conn.execute(
+        r#"
+        UPDATE interviews
+        SET start_at = ?1, end_at = ?2, location = ?3, meeting_link = ?4,
+            sequence = sequence + 1, updated_at = datetime('now')
+        WHERE id = ?5
+        "#,
+        params![start_at, end_at, location, link, id],
+    ).map_err(|e| e.to_string())?;
+    set_interviewers(conn, id, &interviewer_ids)
+}
+
+pub(crate) fn cancel_conn(conn: &Connection, id: i64, reason: Option<&str>) -> Result<(), String> {
+    let n = conn.execute(
+        r#"
+        UPDATE interviews
+        SET status = 'cancelled', outcome_note = ?1, sequence = sequence + 1, updated_at = datetime('now')
+        WHERE id = ?2 AND status = 'scheduled'
+        "#,
+        params![reason.map(str::trim).filter(|r| !r.is_empty()), id],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Planlanmış görüşme bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+fn interviewers(conn: &Connection, id: i64) -> Result<Vec<Interviewer>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT m.id, m.name, m.email FROM interview_interviewers x
+         JOIN mentors m ON m.id = x.mentor_id
+         WHERE x.interview_id = ?1 ORDER BY m.name"
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![id], |r| Ok(Interviewer { mentor_id: r.get(0)?, name: r.get(1)?, email: r.get(2)? }))
+        .map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+const INTERVIEW_SELECT: &str = r#"
+    SELECT v.id, v.applicant_id, v.intern_id,
+           COALESCE(a.first_name || ' ' || a.last_name, i.first_name || ' ' || i.last_name, ''),
+           COALESCE(a.email, i.email, ''),
+           v.title, v.start_at, v.end_at, v.location, v.meeting_link,
+           v.status, v.outcome, v.outcome_note, v.sequence, v.uid
+    FROM interviews v
+    LEFT JOIN applicants a ON a.id = v.applicant_id
+    LEFT JOIN interns i ON i.id = v.intern_id
+"#;
+
+fn interview_from_row(r: &rusqlite::Row) -> rusqlite::Result<Interview> {
+    Ok(Interview {
+        id: r.get(0)?,
+        applicant_id: r.get(1)?,
+        intern_id: r.get(2)?,
+        candidate_name: r.get(3)?,
+        candidate_email: r.get(4)?,
+        title: r.get(5)?,
+        start_at: r.get(6)?,
+        end_at: r.get(7)?,
+        location: r.get(8)?,
+        meeting_link: r.get(9)?,
+        status: r.get(10)?,
+        outcome: r.get(11)?,
+        outcome_note: r.get(12)?,
+        sequence: r.get(13)?,
+        uid: r.get(14)?,
+        interviewers: Vec::new(),
+    })
+}
+
+pub(crate) fn get_conn(conn: &Connection, id: i64) -> Result<Interview, String> {
+    let mut interview = conn
+        .query_row(&format!("{INTERVIEW_SELECT} WHERE v.id = ?1"), params![id], interview_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Görüşme bulunamadı".to_string())?;
+    interview.interviewers = interviewers(conn, id)?;
+    Ok(interview)
+}
+
+/// iCalendar davetiyesi; iptal edilen görüşme için METHOD:CANCEL. REQUEST ve
+/// CANCEL bir düzenleyen (ORGANIZER) ister; hiçbir görüşmecinin e-postası
+/// yoksa davet yerine METHOD:PUBLISH ile katılımcısız bir takvim kaydı üretilir.
+pub(crate) fn invitation_ics(conn: &Connection, id: i64) -> Result<String, String> {
+    let v = get_conn(conn, id)?;
+    let cancelled = v.status == "cancelled";
+    // düzenleyen: e-postası olan ilk görüşmeci
+    let with_email: Vec<&Interviewer> = v.interviewers.iter().filter(|i| !i.email.trim().is_empty()).collect();
+    let method = match (with_email.first(), cancelled) {
+        (None, _) => "PUBLISH",
+        (Some(_), true) => "CANCEL",
+        (Some(_), false) => "REQUEST",
+    };
+    let mut w = IcsWriter::new(method, "");
+    w.begin("VEVENT");
+    w.raw("UID", &v.uid);
+    w.raw("SEQUENCE", &v.sequence.to_string());
+    w.raw("DTSTAMP", &ics::now_utc());
+    w.raw("DTSTART", &ics::utc(parse_datetime(&v.start_at)?));
+    w.raw("DTEND", &ics::utc(parse_datetime(&v.end_at)?));
+    w.text("SUMMARY", &format!("{} – {}", v.title, v.candidate_name));
+    if !v.location.is_empty() {
+        w.text("LOCATION", &v.location);
+    }
+    let mut description = format!("Aday: {}", v.candidate_name);
+    if !v.interviewers.is_empty() {
+        let names: Vec<&str> = v.interviewers.iter().map(|i| i.name.as_str()).collect();
+        description.push_str(&format!("\nGörüşmeciler: {}", names.join(", ")));
+    }
+    if !v.meeting_link.is_empty() {
+        description.push_str(&format!("\nBağlantı: {}", v.meeting_link));
+        w.raw("URL", &v.meeting_link);
+    }
+    w.text("DESCRIPTION", &description);
+    w.raw("STATUS", if cancelled { "CANCELLED" } else { "CONFIRMED" });
+
+    if let Some(org) = with_email.first() {
+        w.person("ORGANIZER", "", &org.name, org.email.trim());
+        for i in &with_email {
+            w.person("ATTENDEE", "ROLE=REQ-PARTICIPANT;PARTSTAT=ACCEPTED", &i.name, i.email.trim());
+        }
+        if !v.candidate_email.trim().is_empty() {
+            w.person("ATTENDEE", "ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE", &v.candidate_name, v.candidate_email.trim());
+        }
+    }
+    w.end("VEVENT");
+    Ok(w.finish())
+}
+
+// görüşme ve görüşmeci satırları birlikte yazılır
+#[tauri::command]
+pub fn create_interview(handle: AppHandle, interview: InterviewPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = create_conn(&tx, &interview)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn reschedule_interview(handle: AppHandle, id: i64, changes: ReschedulePayload) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    reschedule_conn(&tx, id, &changes)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_interview(handle: AppHandle, id: i64, reason: Option<String>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    cancel_conn(&conn, id, reason.as_deref())
+}
+
+#[tauri::command]
+pub fn set_interview_outcome(handle: AppHandle, id: i64, outcome: Outcome, note: Option<String>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        r#"
+        UPDATE interviews
+        SET status = 'completed', outcome = ?1, outcome_note = ?2, updated_at = datetime('now')
+        WHERE id = ?3 AND status <> 'cancelled'
+        "#,
+        params![outcome.as_str(), note.as_deref().map(str::trim).filter(|n| !n.is_empty()), id],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Görüşme bulunamadı veya iptal edilmiş".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_interview(handle: AppHandle, id: i64) -> Result<Interview, String> {
+    let conn = open_conn(&handle)?;
+    get_conn(&conn, id)
+}
+
+#[tauri::command]
+pub fn list_interviews(
+    handle: AppHandle,
+    from: Option<String>,
+    to: Option<String>,
+    applicant_id: Option<i64>,
+    intern_id: Option<i64>,
+    mentor_id: Option<i64>,
+) -> Result<Vec<Interview>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        {INTERVIEW_SELECT}
+        WHERE (?1 IS NULL OR v.start_at >= ?1) AND (?2 IS NULL OR substr(v.start_at, 1, 10) <= ?2)
+          AND (?3 IS NULL OR v.applicant_id = ?3) AND (?4 IS NULL OR v.intern_id = ?4)
+          AND (?5 IS NULL OR EXISTS (
+              SELECT 1 FROM interview_interviewers x WHERE x.interview_id = v.id AND x.mentor_id = ?5))
+        ORDER BY v.start_at
+        "#
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![from, to, applicant_id, intern_id, mentor_id], interview_from_row)
+        .map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows {
+        let mut v = r.map_err(|e| e.to_string())?;
+        v.interviewers = interviewers(&conn, v.id)?;
+        out.push(v);
+    }
+    Ok(out)
+}
+
+// Kaydetmeden önce arayüzde uyarı göstermek için (başlık / görüşmeci zorunlu değil)
+#[tauri::command]
+pub fn check_interview_conflicts(
+    handle: AppHandle,
+    interview: InterviewPayload,
+    exclude_id: Option<i64>,
+) -> Result<Vec<InterviewConflict>, String> {
+    let conn = open_conn(&handle)?;
+    let (start_at, end_at) = normalize_range(&interview.start_at, &interview.end_at)?;
+    conflicts_conn(
+        &conn,
+        &start_at,
+        &end_at,
+        &interview.interviewer_ids,
+        &interview.location,
+        (interview.applicant_id, interview.intern_id),
+        exclude_id,
+    )
+}
+
+// output_path verilmezse Masaüstü\InternTracker\interviews altına yazılır
+#[tauri::command]
+pub fn generate_interview_invitation(handle: AppHandle, id: i64, output_path: Option<String>) -> Result<InvitationResult, String> {
+    let conn = open_conn(&handle)?;
+    let body = invitation_ics(&conn, id)?;
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p.into(),
+        None => {
+            let dir = storage_root(&handle)?.join("interviews");
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            dir.join(format!("gorusme_{id}.ics"))
+        }
+    };
+    fs::write(&path, body).map_err(|e| format!("Davetiye yazılamadı: {e}"))?;
+    Ok(InvitationResult { interview_id: id, file_path: path.to_string_lossy().to_string() })
+}
@@ -0,0 +1,708 @@
+// Staj defteri: stajyerin günlük çalışma kayıtları, ekleri ve mentor onayı.
+// Onaylanan kayıt kilitlenir; defter okulların beklediği düzende (kapak +
+// çizelge + her gün için imzalı sayfa) PDF veya DOCX olarak dışa aktarılır.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Local};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date};
+use crate::certificate::{display_date, load_template};
+use crate::docx::{DocxDocument, Run};
+use crate::mentors::{ensure_mentor_exists, OPEN_END};
+use crate::pdf::{self, Align, Column, Flow, Font, PdfDocument, Style, BLACK, GRAY};
+use crate::{add_column_if_missing, current_os_user, open_conn, person_dir, slug_tr};
+
+const WEEKDAYS: [&str; 7] = ["Pazartesi", "Salı", "Çarşamba", "Perşembe", "Cuma", "Cumartesi", "Pazar"];
+
+#[derive(Debug, Deserialize)]
+pub struct JournalAttachmentPayload {
+    pub name: String,
+    pub mime: Option<String>,
+    pub blob: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JournalEntryPayload {
+    pub intern_id: i64,
+    pub date: String,
+    pub work_done: String,
+    pub hours: f64,
+    #[serde(default)]
+    pub attachments: Vec<JournalAttachmentPayload>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JournalAttachment {
+    pub id: i64,
+    pub entry_id: i64,
+    pub file_name: String,
+    pub file_path: String,
+    pub mime: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JournalEntry {
+    pub id: i64,
+    pub intern_id: i64,
+    pub date: String,
+    pub work_done: String,
+    pub hours: f64,
+    // pending | approved | rejected
+    pub status: String,
+    pub mentor_id: Option<i64>,
+    pub mentor_name: Option<String>,
+    pub decided_at: Option<String>,
+    pub mentor_note: Option<String>,
+    // onaylı kaydın kilidini en son açan ve zamanı
+    pub reopened_by: Option<String>,
+    pub reopened_at: Option<String>,
+    pub attachments: Vec<JournalAttachment>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalFormat {
+    Pdf,
+    Docx,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JournalExportResult {
+    pub intern_id: i64,
+    pub file_path: String,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS journal_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            intern_id INTEGER NOT NULL,
+            date TEXT NOT NULL,
+            work_done TEXT NOT NULL,
+            hours REAL NOT NULL CHECK (hours > 0 AND hours <= 24),
+            status TEXT NOT NULL DEFAULT 'pending'
+                CHECK (status IN ('pending', 'approved', 'rejected')),
+            mentor_id INTEGER,
+            decided_at TEXT,
+            mentor_note TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (intern_id, date),
+            FOREIGN KEY (intern_id) REFERENCES interns(id) ON DELETE CASCADE,
+            FOREIGN KEY (mentor_id) REFERENCES mentors(id) ON DELETE SET NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS journal_attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entry_id INTEGER NOT NULL,
+            file_name TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            mime TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (entry_id) REFERENCES journal_entries(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_journal_attachments_entry ON journal_attachments(entry_id);
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    add_column_if_missing(conn, "journal_entries", "reopened_by", "TEXT")?;
+    add_column_if_missing(conn, "journal_entries", "reopened_at", "TEXT")
+}
+
+// Tarih staj aralığında olmalı; normalize edilmiş tarihi döner
+fn validate(conn: &Connection, p: &JournalEntryPayload) -> Result<String, String> {
+    if p.work_done.trim().is_empty() {
+        return Err("Yapılan iş boş olamaz".to_string());
+    }
+    if !(p.hours > 0.0 && p.hours <= 24.0) {
+        return Err("Çalışma süresi 0 ile 24 saat arasında olmalı".to_string());
+    }
+    let date = parse_date(&p.date)?;
+    let (start, end): (String, Option<String>) = conn
+        .query_row("SELECT start_date, end_date FROM interns WHERE id = ?1", params![p.intern_id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let end = end.filter(|d| !d.trim().is_empty()).map(|d| parse_date(&d)).transpose()?;
+    if date < parse_date(&start)? || end.is_some_and(|e| date > e) {
+        return Err("Tarih staj dönemi dışında".to_string());
+    }
+    Ok(format_date(date))
+}
+
+// Onaylı kayıt kilitlidir; (intern_id, date) döner
+fn ensure_editable(conn: &Connection, id: i64) -> Result<(i64, String), String> {
+    let (intern_id, date, status): (i64, String, String) = conn
+        .query_row("SELECT intern_id, date, status FROM journal_entries WHERE id = ?1", params![id], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Defter kaydı bulunamadı".to_string())?;
+    if status == "approved" {
+        return Err("Onaylanmış kayıt değiştirilemez".to_string());
+    }
+    Ok((intern_id, date))
+}
+
+fn unique_violation(e: rusqlite::Error) -> String {
+    match e {
+        rusqlite::Error::SqliteFailure(f, _) if f.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE => {
+            "Bu tarih için zaten defter kaydı var".to_string()
+        }
+        e => e.to_string(),
+    }
+}
+
+pub(crate) fn add_entry_conn(conn: &Connection, p: &JournalEntryPayload) -> Result<i64, String> {
+    let date = validate(conn, p)?;
+    conn.execute(
+        "INSERT INTO journal_entries (intern_id, date, work_done, hours) VALUES (?1, ?2, ?3, ?4)",
+        params![p.intern_id, date, p.work_done.trim(), p.hours],
+    ).map_err(unique_violation)?;
+    Ok(conn.last_insert_rowid())
+}
+
+// Reddedilen kayıt düzenlenince yeniden onaya düşer
+pub(crate) fn update_entry_conn(conn: &Connection, id: i64, p: &JournalEntryPayload) -> Result<(), String> {
+    let (intern_id, _) = ensure_editable(conn, id)?;
+    if intern_id != p.intern_id {
+        return Err("Kayıt başka bir stajyere taşınamaz".to_string());
+    }
+    let date = validate(conn, p)?;
+    conn.execute(
+        r#"
+        UPDATE journal_entries
+        SET date = ?1, work_done = ?2, hours = ?3, status = 'pending',
+            mentor_id = NULL, decided_at = NULL, mentor_note = NULL, updated_at = datetime('now')
+        WHERE id = ?4
+        "#,
+        params![date, p.work_done.trim(), p.hours, id],
+    ).map_err(unique_violation)?;
+    Ok(())
+}
+
+// Yalnızca kaydın tarihinde stajyere atanmış mentor onaylayabilir
+pub(crate) fn decide_conn(conn: &Connection, id: i64, mentor_id: i64, approve: bool, note: Option<&str>) -> Result<(), String> {
+    let (intern_id, date, status): (i64, String, String) = conn
+        .query_row("SELECT intern_id, date, status FROM journal_entries WHERE id = ?1", params![id], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Defter kaydı bulunamadı".to_string())?;
+    if status != "pending" {
+        return Err(format!("Kayıt bu durumda değiştirilemez ({status})"));
+    }
+    ensure_mentor_exists(conn, mentor_id)?;
+    let assigned: bool = conn.query_row(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM intern_mentors
+            WHERE intern_id = ?1 AND mentor_id = ?2
+              AND start_date <= ?3 AND COALESCE(end_date, ?4) >= ?3
+        )
+        "#,
+        params![intern_id, mentor_id, date, OPEN_END],
+        |r| r.get(0),
+    ).map_err(|e| e.to_string())?;
+    if !assigned {
+        return Err("Mentor bu tarihte stajyere atanmış değil".to_string());
+    }
+    conn.execute(
+        r#"
+        UPDATE journal_entries
+        SET status = ?1, mentor_id = ?2, decided_at = datetime('now'), mentor_note = ?3
+        WHERE id = ?4
+        "#,
+        params![
+            if approve { "approved" } else { "rejected" },
+            mentor_id,
+            note.map(str::trim).filter(|n| !n.is_empty()),
+            id
+        ],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn list_attachments(conn: &Connection, entry_id: i64) -> Result<Vec<JournalAttachment>, String> {
+    let mut stmt = conn
+        .prepare("SELECT id, entry_id, file_name, file_path, mime FROM journal_attachments WHERE entry_id = ?1 ORDER BY id")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![entry_id], |r| {
+        Ok(JournalAttachment { id: r.get(0)?, entry_id: r.get(1)?, file_name: r.get(2)?, file_path: r.get(3)?, mime: r.get(4)? })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+pub(crate) fn list_conn(
+    conn: &Connection,
+    intern_id: i64,
+    from: Option<&str>,
+    to: Option<&str>,
+    approved_only: bool,
+) -> Result<Vec<JournalEntry>, String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT j.id, j.intern_id, j.date, j.work_done, j.hours, j.status,
+               j.mentor_id, m.name, j.decided_at, j.mentor_note, j.reopened_by, j.reopened_at
+        FROM journal_entries j
+        LEFT JOIN mentors m ON m.id = j.mentor_id
+        WHERE j.intern_id = ?1
+          AND (?2 IS NULL OR j.date >= ?2) AND (?3 IS NULL OR j.date <= ?3)
+          AND (?4 = 0 OR j.status = 'approved')
+        ORDER BY j.date
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![intern_id, from, to, approved_only], |r| {
+        Ok(JournalEntry {
+            id: r.get(0)?,
+            intern_id: r.get(1)?,
+            date: r.get(2)?,
+            work_done: r.get(3)?,
+            hours: r.get(4)?,
+            status: r.get(5)?,
+            mentor_id: r.get(6)?,
+            mentor_name: r.get(7)?,
+            decided_at: r.get(8)?,
+            mentor_note: r.get(9)?,
+            reopened_by: r.get(10)?,
+            reopened_at: r.get(11)?,
+            attachments: Vec::new(),
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows {
+        let mut entry = r.map_err(|e| e.to_string())?;
+        entry.attachments = list_attachments(conn, entry.id)?;
+        out.push(entry);
+    }
+    Ok(out)
+}
+
+// person_dir\staj_defteri\{tarih}_{kayıt}_{ek}_{dosya}; yazılan dosyaları döner.
+// Hata olursa o ana kadar yazılanlar silinir.
+fn save_attachments(
+    handle: &AppHandle,
+    conn: &Connection,
+    entry_id: i64,
+    files: &[JournalAttachmentPayload],
+) -> Result<Vec<PathBuf>, String> {
+    let mut written = Vec::new();
+    if files.is_empty() {
+        return Ok(written);
+    }
+    let result = write_attachments(handle, conn, entry_id, files, &mut written);
+    if result.is_err() {
+        remove_files(&written);
+    }
+    result.map(|_| written)
+}
+
+fn remove_files(paths: &[PathBuf]) {
+    for p in paths {
+        let _ = fs::remove_file(p);
+    }
+}
+
+fn write_attachments(
+    handle: &AppHandle,
+    conn: &Connection,
+    entry_id: i64,
+    files: &[JournalAttachmentPayload],
+    written: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    let (intern_id, date, first, last): (i64, String, String, String) = conn.query_row(
+        r#"
+        SELECT j.intern_id, j.date, i.first_name, i.last_name
+        FROM journal_entries j JOIN interns i ON i.id = j.intern_id
+        WHERE j.id = ?1
+        "#,
+        params![entry_id],
+        |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+    ).map_err(|e| e.to_string())?;
+    let dir = person_dir(handle, intern_id, &first, &last)?.join("staj_defteri");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    for f in files {
+        // yol bileşenleri atılır, yalnızca dosya adı kullanılır
+        let name = Path::new(&f.name)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .filter(|n| !n.is_empty())
+            .unwrap_or("ek.bin");
+        // aynı gün aynı adla gelen ekler ek kimliğiyle ayrılır
+        conn.execute(
+            "INSERT INTO journal_attachments (entry_id, file_name, file_path, mime) VALUES (?1, ?2, '', ?3)",
+            params![entry_id, name, f.mime],
+        ).map_err(|e| e.to_string())?;
+        let attachment_id = conn.last_insert_rowid();
+        let path = dir.join(format!("{date}_{entry_id}_{attachment_id}_{name}"));
+        conn.execute(
+            "UPDATE journal_attachments SET file_path = ?1 WHERE id = ?2",
+            params![path.to_string_lossy(), attachment_id],
+        ).map_err(|e| e.to_string())?;
+        fs::write(&path, &f.blob).map_err(|e| format!("Ek yazılamadı: {e}"))?;
+        written.push(path);
+    }
+    Ok(())
+}
+
+fn fmt_hours(h: f64) -> String {
+    if h.fract() == 0.0 { format!("{h:.0}") } else { format!("{h:.1}").replace('.', ",") }
+}
+
+fn weekday(date: &str) -> &'static str {
+    parse_date(date).map(|d| WEEKDAYS[d.weekday().num_days_from_monday() as usize]).unwrap_or("")
+}
+
+fn status_label(status: &str) -> &'static str {
+    match status {
+        "approved" => "Onaylandı",
+        "rejected" => "Reddedildi",
+        _ => "Onay bekliyor",
+    }
+}
+
+// Kapak ve çizelge için ortak stajyer bilgileri
+struct Notebook {
+    full_name: String,
+    organization: String,
+    fields: Vec<(&'static str, String)>,
+    entries: Vec<JournalEntry>,
+}
+
+fn load_notebook(conn: &Connection, intern_id: i64, approved_only: bool) -> Result<Notebook, String> {
+    let (first, last, school, department, start, end): (String, String, String, String, String, Option<String>) = conn
+        .query_row(
+            "SELECT first_name, last_name, school, department, start_date, end_date FROM interns WHERE id = ?1",
+            params![intern_id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let entries = list_conn(conn, intern_id, None, None, approved_only)?;
+    let total: f64 = entries.iter().map(|e| e.hours).sum();
+    let approved = entries.iter().filter(|e| e.status == "approved").count();
+    let end = end.filter(|d| !d.trim().is_empty());
+    let full_name = format!("{first} {last}");
+    Ok(Notebook {
+        fields: vec![
+            ("Ad Soyad", full_name.clone()),
+            ("Okul", school),
+            ("Bölüm", department),
+            (
+                "Staj dönemi",
+                format!("{} – {}", display_date(&start), end.as_deref().map(display_date).unwrap_or_else(|| "devam ediyor".to_string())),
+            ),
+            ("Kayıtlı gün", entries.len().to_string()),
+            ("Onaylı gün", approved.to_string()),
+            ("Toplam süre", format!("{} saat", fmt_hours(total))),
+        ],
+        full_name,
+        organization: load_template(conn)?.organization_name,
+        entries,
+    })
+}
+
+pub(crate) fn render_pdf(conn: &Connection, intern_id: i64, today: &str, approved_only: bool) -> Result<(Vec<u8>, usize), String> {
+    let nb = load_notebook(conn, intern_id, approved_only)?;
+    let mut doc = PdfDocument::new(pdf::A4_PORTRAIT, &format!("Staj Defteri - {}", nb.full_name));
+    let mut flow = Flow::new(&doc, 50.0, &format!("{} · Staj Defteri · {}", nb.full_name, display_date(today)));
+    let (left, right) = (flow.left, flow.right);
+    let center = (left + right) / 2.0;
+
+    // kapak
+    flow.space(60.0);
+    let y = flow.y;
+    flow.page().text_at(center, y, Style::new(24.0, Font::Bold), Align::Center, "STAJ DEFTERİ");
+    if !nb.organization.is_empty() {
+        flow.page().text_at(center, y - 26.0, Style::new(12.0, Font::Regular).color(GRAY), Align::Center, &nb.organization);
+    }
+    flow.space(60.0);
+    flow.heading("Stajyer Bilgileri");
+    let fields: Vec<(&str, String)> = nb.fields.iter().map(|(k, v)| (*k, v.clone())).collect();
+    flow.fields(left, 100.0, &fields);
+    flow.space(16.0);
+
+    flow.heading("Çalışma Çizelgesi");
+    if nb.entries.is_empty() {
+        flow.paragraph(Style::new(10.0, Font::Italic).color(GRAY), "Kayıtlı defter girdisi yok.");
+    } else {
+        let rows: Vec<Vec<String>> = nb.entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| vec![
+                (i + 1).to_string(),
+                display_date(&e.date),
+                e.work_done.lines().next().unwrap_or("").to_string(),
+                fmt_hours(e.hours),
+                status_label(&e.status).to_string(),
+            ])
+            .collect();
+        flow.table(&[
+            Column { title: "Gün", width: 0.07, align: Align::Right },
+            Column { title: "Tarih", width: 0.14, align: Align::Left },
+            Column { title: "Yapılan İş", width: 0.53, align: Align::Left },
+            Column { title: "Saat", width: 0.08, align: Align::Right },
+            Column { title: "Durum", width: 0.18, align: Align::Left },
+        ], &rows);
+    }
+
+    // her gün ayrı sayfa
+    let label = Style::new(9.0, Font::Regular).color(GRAY);
+    for (i, e) in nb.entries.iter().enumerate() {
+        flow.new_page();
+        flow.heading(&format!("{}. Gün — {} {}", i + 1, display_date(&e.date), weekday(&e.date)));
+        let mut fields = vec![("Çalışma süresi", format!("{} saat", fmt_hours(e.hours)))];
+        if !e.attachments.is_empty() {
+            let names: Vec<&str> = e.attachments.iter().map(|a| a.file_name.as_str()).collect();
+            fields.push(("Ekler", names.join(", ")));
+        }
+        flow.fields(left, 100.0, &fields);
+        flow.space(12.0);
+        flow.heading("Yapılan İş");
+        for para in e.work_done.lines() {
+            flow.paragraph(Style::new(10.5, Font::Regular), para);
+        }
+
+        // onay / imza alanı
+        flow.ensure(110.0);
+        flow.space(30.0);
+        let mut sign = vec![("Durum", status_label(&e.status).to_string())];
+        if let Some(name) = &e.mentor_name {
+            sign.push(("Staj sorumlusu", name.clone()));
+        }
+        if let Some(at) = &e.decided_at {
+            sign.push(("Onay tarihi", display_date(at.get(..10).unwrap_or(at))));
+        }
+        if let Some(note) = &e.mentor_note {
+            sign.push(("Not", note.clone()));
+        }
+        flow.fields(left, 100.0, &sign);
+        flow.space(40.0);
+        let y = flow.y;
+        let page = flow.page();
+        page.line(right - 180.0, y, right, y, 0.6, BLACK);
+        page.text_at(right - 90.0, y - 12.0, label, Align::Center, "Staj Sorumlusu İmza / Kaşe");
+        flow.space(20.0);
+    }
+
+    flow.finish(&mut doc);
+    Ok((doc.to_bytes()?, nb.entries.len()))
+}
+
+pub(crate) fn render_docx(conn: &Connection, intern_id: i64, approved_only: bool) -> Result<(Vec<u8>, usize), String> {
+    let nb = load_notebook(conn, intern_id, approved_only)?;
+    let mut doc = DocxDocument::new();
+
+    doc.space();
+    doc.paragraph(Run::new(24.0).bold(), Align::Center, "STAJ DEFTERİ");
+    if !nb.organization.is_empty() {
+        doc.paragraph(Run::new(12.0), Align::Center, &nb.organization);
+    }
+    doc.space();
+    doc.paragraph(Run::new(13.0).bold(), Align::Left, "Stajyer Bilgileri");
+    for (k, v) in &nb.fields {
+        doc.field(k, v);
+    }
+    doc.space();
+    doc.paragraph(Run::new(13.0).bold(), Align::Left, "Çalışma Çizelgesi");
+    if nb.entries.is_empty() {
+        doc.paragraph(Run::new(10.0).italic(), Align::Left, "Kayıtlı defter girdisi yok.");
+    } else {
+        let rows: Vec<Vec<String>> = nb.entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| vec![
+                (i + 1).to_string(),
+                display_date(&e.date),
+                e.work_done.lines().next().unwrap_or("").to_string(),
+                fmt_hours(e.hours),
+                status_label(&e.status).to_string(),
+            ])
+            .collect();
+        doc.table(&[0.07, 0.14, 0.53, 0.08, 0.18], &["Gün", "Tarih", "Yapılan İş", "Saat", "Durum"], &rows);
+    }
+
+    for (i, e) in nb.entries.iter().enumerate() {
+        doc.page_break();
+        doc.paragraph(
+            Run::new(13.0).bold(),
+            Align::Left,
+            &format!("{}. Gün — {} {}", i + 1, display_date(&e.date), weekday(&e.date)),
+        );
+        doc.field("Çalışma süresi", &format!("{} saat", fmt_hours(e.hours)));
+        if !e.attachments.is_empty() {
+            let names: Vec<&str> = e.attachments.iter().map(|a| a.file_name.as_str()).collect();
+            doc.field("Ekler", &names.join(", "));
+        }
+        doc.space();
+        doc.paragraph(Run::new(12.0).bold(), Align::Left, "Yapılan İş");
+        doc.paragraph(Run::new(10.5), Align::Left, &e.work_done);
+        doc.space();
+        doc.field("Durum", status_label(&e.status));
+        if let Some(name) = &e.mentor_name {
+            doc.field("Staj sorumlusu", name);
+        }
+        if let Some(at) = &e.decided_at {
+            doc.field("Onay tarihi", &display_date(at.get(..10).unwrap_or(at)));
+        }
+        if let Some(note) = &e.mentor_note {
+            doc.field("Not", note);
+        }
+        doc.space();
+        doc.space();
+        doc.paragraph(Run::new(9.0), Align::Right, "______________________________");
+        doc.paragraph(Run::new(9.0), Align::Right, "Staj Sorumlusu İmza / Kaşe");
+    }
+
+    Ok((doc.to_bytes()?, nb.entries.len()))
+}
+
+// Kayıt ve ekleri tek işlemde; ekler yazılamazsa kayıt da oluşmaz
+#[tauri::command]
+pub fn add_journal_entry(handle: AppHandle, entry: JournalEntryPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = add_entry_conn(&tx, &entry)?;
+    let written = save_attachments(&handle, &tx, id, &entry.attachments)?;
+    if let Err(e) = tx.commit() {
+        remove_files(&written);
+        return Err(e.to_string());
+    }
+    Ok(id)
+}
+
+// Gönderilen ekler mevcutlara eklenir; silmek için delete_journal_attachment
+#[tauri::command]
+pub fn update_journal_entry(handle: AppHandle, id: i64, entry: JournalEntryPayload) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    update_entry_conn(&tx, id, &entry)?;
+    let written = save_attachments(&handle, &tx, id, &entry.attachments)?;
+    if let Err(e) = tx.commit() {
+        remove_files(&written);
+        return Err(e.to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_journal_entry(handle: AppHandle, id: i64) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    ensure_editable(&tx, id)?;
+    let files: Vec<PathBuf> = list_attachments(&tx, id)?.into_iter().map(|a| PathBuf::from(a.file_path)).collect();
+    tx.execute("DELETE FROM journal_entries WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    // dosyalar kayıt silindikten sonra
+    remove_files(&files);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_journal_attachment(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let (entry_id, path): (i64, String) = conn
+        .query_row("SELECT entry_id, file_path FROM journal_attachments WHERE id = ?1", params![id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Ek bulunamadı".to_string())?;
+    ensure_editable(&conn, entry_id)?;
+    conn.execute("DELETE FROM journal_attachments WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(path);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_journal_entries(
+    handle: AppHandle,
+    intern_id: i64,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<JournalEntry>, String> {
+    let conn = open_conn(&handle)?;
+    list_conn(&conn, intern_id, from.as_deref(), to.as_deref(), false)
+}
+
+#[tauri::command]
+pub fn approve_journal_entry(handle: AppHandle, id: i64, mentor_id: i64, note: Option<String>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    decide_conn(&conn, id, mentor_id, true, note.as_deref())
+}
+
+#[tauri::command]
+pub fn reject_journal_entry(handle: AppHandle, id: i64, mentor_id: i64, note: Option<String>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    decide_conn(&conn, id, mentor_id, false, note.as_deref())
+}
+
+// Onaylı kaydın kilidini açar (düzeltme için); kayıt yeniden onaya düşer,
+// önceki onay ve mentor notu silinir, kilidi kimin açtığı kaydedilir
+#[tauri::command]
+pub fn reopen_journal_entry(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        r#"
+        UPDATE journal_entries
+        SET status = 'pending', mentor_id = NULL, decided_at = NULL, mentor_note = NULL,
+            reopened_by = ?2, reopened_at = datetime('now'), updated_at = datetime('now')
+        WHERE id = ?1 AND status = 'approved'
+        "#,
+        params![id, current_os_user()],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Onaylanmış defter kaydı bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+// output_path verilmezse stajyer klasörüne yazılır
+#[tauri::command]
+pub fn export_journal(
+    handle: AppHandle,
+    intern_id: i64,
+    format: JournalFormat,
+    output_path: Option<String>,
+    approved_only: Option<bool>,
+) -> Result<JournalExportResult, String> {
+    let conn = open_conn(&handle)?;
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let approved_only = approved_only.unwrap_or(false);
+    let ((bytes, entries), ext) = match format {
+        JournalFormat::Pdf => (render_pdf(&conn, intern_id, &today, approved_only)?, "pdf"),
+        JournalFormat::Docx => (render_docx(&conn, intern_id, approved_only)?, "docx"),
+    };
+
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p.into(),
+        None => {
+            let (first, last): (String, String) = conn.query_row(
+                "SELECT first_name, last_name FROM interns WHERE id = ?1",
+                params![intern_id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            ).map_err(|e| e.to_string())?;
+            person_dir(&handle, intern_id, &first, &last)?
+                .join(format!("staj_defteri_{}_{today}.{ext}", slug_tr(&last)))
+        }
+    };
+    fs::write(&path, &bytes).map_err(|e| format!("Defter yazılamadı: {e}"))?;
+    Ok(JournalExportResult { intern_id, file_path: path.to_string_lossy().to_string(), entries, bytes: bytes.len() })
+}
@@ -0,0 +1,437 @@
+// İzin / mazeret talepleri: talep → onay / ret. İzin türü, izin günlerinin
+// çalışılmış sayılıp sayılmadığını belirler; onaylı izinler devam
+// toplamlarına katılır. Çalışılmamış izin günleri izin hakkını aşarsa
+// bitiş tarihi için uzatma önerilir.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date, WorkCalendar};
+use crate::{current_os_user, open_conn, settings};
+
+const SETTINGS_KEY: &str = "leaves";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeaveSettings {
+    // staj boyunca bitiş tarihini etkilemeyen (çalışılmamış) izin günü
+    pub allowed_days: i64,
+}
+
+impl Default for LeaveSettings {
+    fn default() -> Self {
+        LeaveSettings { allowed_days: 2 }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaveType {
+    pub id: i64,
+    pub name: String,
+    pub counts_as_worked: bool,
+    // tür başına üst sınır (iş günü); None: sınırsız
+    pub max_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaveTypePayload {
+    pub name: String,
+    #[serde(default)]
+    pub counts_as_worked: bool,
+    pub max_days: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LeaveRequestPayload {
+    pub intern_id: i64,
+    pub leave_type_id: i64,
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaveRequest {
+    pub id: i64,
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub leave_type_id: i64,
+    pub leave_type: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub working_days: i64,
+    pub reason: String,
+    pub status: String,
+    pub requested_at: String,
+    pub decided_at: Option<String>,
+    pub decided_by: Option<String>,
+    pub decision_note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExtensionSuggestion {
+    pub intern_id: i64,
+    pub current_end_date: String,
+    pub suggested_end_date: String,
+    // izin hakkını aşan çalışılmamış izin günü
+    pub excess_days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaveOutcome {
+    pub id: i64,
+    pub working_days: i64,
+    // engellemeyen uyarılar (tür sınırı, girilmiş devam kaydı vb.)
+    pub warnings: Vec<String>,
+    pub extension: Option<ExtensionSuggestion>,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS leave_types (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            counts_as_worked INTEGER NOT NULL DEFAULT 0,
+            max_days INTEGER CHECK (max_days IS NULL OR max_days >= 0)
+        );
+
+        CREATE TABLE IF NOT EXISTS leave_requests (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            intern_id INTEGER NOT NULL,
+            leave_type_id INTEGER NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            working_days INTEGER NOT NULL,
+            reason TEXT NOT NULL DEFAULT '',
+            status TEXT NOT NULL DEFAULT 'pending'
+                CHECK (status IN ('pending', 'approved', 'rejected', 'cancelled')),
+            requested_at TEXT NOT NULL DEFAULT (datetime('now')),
+            decided_at TEXT,
+            decided_by TEXT,
+            decision_note TEXT,
+            FOREIGN KEY (intern_id) REFERENCES interns(id) ON DELETE CASCADE,
+            FOREIGN KEY (leave_type_id) REFERENCES leave_types(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_leave_requests_intern ON leave_requests(intern_id, start_date);
+
+        -- varsayılan türler (yalnızca ilk kurulumda)
+        INSERT INTO leave_types (name, counts_as_worked, max_days)
+        SELECT 'Sınav İzni', 1, 5 WHERE NOT EXISTS (SELECT 1 FROM leave_types)
+        UNION ALL SELECT 'Hastalık (Rapor)', 0, NULL WHERE NOT EXISTS (SELECT 1 FROM leave_types)
+        UNION ALL SELECT 'Mazeret İzni', 0, 3 WHERE NOT EXISTS (SELECT 1 FROM leave_types);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<LeaveSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+/// Onaylı izinlerin iş günleri → izin türü çalışılmış sayılıyor mu.
+pub(crate) fn approved_leave_days(conn: &Connection, cal: &WorkCalendar, intern_id: i64) -> Result<HashMap<NaiveDate, bool>, String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT r.start_date, r.end_date, t.counts_as_worked
+        FROM leave_requests r JOIN leave_types t ON t.id = r.leave_type_id
+        WHERE r.intern_id = ?1 AND r.status = 'approved'
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![intern_id], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?, r.get::<_, bool>(2)?)))
+        .map_err(|e| e.to_string())?;
+    let mut out = HashMap::new();
+    for r in rows {
+        let (start, end, counts) = r.map_err(|e| e.to_string())?;
+        for d in cal.working_days(parse_date(&start)?, parse_date(&end)?) {
+            // çakışma olmaz; yine de çalışılmış sayılan tür öncelikli
+            *out.entry(d).or_insert(counts) |= counts;
+        }
+    }
+    Ok(out)
+}
+
+pub(crate) fn extension_conn(conn: &Connection, cal: &WorkCalendar, intern_id: i64) -> Result<Option<ExtensionSuggestion>, String> {
+    let end: Option<String> = conn
+        .query_row("SELECT end_date FROM interns WHERE id = ?1", params![intern_id], |r| r.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let Some(end) = end.filter(|d| !d.trim().is_empty()) else { return Ok(None) };
+    let end = parse_date(&end)?;
+
+    let unpaid = approved_leave_days(conn, cal, intern_id)?.values().filter(|counts| !**counts).count() as i64;
+    let excess = unpaid - load_settings(conn)?.allowed_days;
+    if excess <= 0 {
+        return Ok(None);
+    }
+    Ok(Some(ExtensionSuggestion {
+        intern_id,
+        current_end_date: format_date(end),
+        suggested_end_date: format_date(cal.add_working_days(end, excess)),
+        excess_days: excess,
+    }))
+}
+
+pub(crate) fn request_conn(conn: &Connection, cal: &WorkCalendar, p: &LeaveRequestPayload) -> Result<LeaveOutcome, String> {
+    let (start, end) = (parse_date(&p.start_date)?, parse_date(&p.end_date)?);
+    if end < start {
+        return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+    }
+
+    let (intern_start, intern_end): (String, Option<String>) = conn
+        .query_row("SELECT start_date, end_date FROM interns WHERE id = ?1", params![p.intern_id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let intern_end = intern_end.filter(|d| !d.trim().is_empty()).map(|d| parse_date(&d)).transpose()?;
+    if start < parse_date(&intern_start)? || intern_end.is_some_and(|e| end > e) {
+        return Err("İzin tarihleri staj dönemi dışında".to_string());
+    }
+
+    let (type_name, max_days): (String, Option<i64>) = conn
+        .query_row("SELECT name, max_days FROM leave_types WHERE id = ?1", params![p.leave_type_id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "İzin türü bulunamadı".to_string())?;
+
+    let (start_s, end_s) = (format_date(start), format_date(end));
+    let overlap: Option<(String, String)> = conn.query_row(
+        r#"
+        SELECT start_date, end_date FROM leave_requests
+        WHERE intern_id = ?1 AND status IN ('pending', 'approved')
+          AND start_date <= ?3 AND end_date >= ?2
+        LIMIT 1
+        "#,
+        params![p.intern_id, start_s, end_s],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    ).optional().map_err(|e| e.to_string())?;
+    if let Some((a, b)) = overlap {
+        return Err(format!("Bu tarihlerle çakışan bir izin var ({a} – {b})"));
+    }
+
+    let working_days = cal.count_working_days(start, end);
+    if working_days == 0 {
+        return Err("Seçilen aralıkta iş günü yok".to_string());
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(max) = max_days {
+        let used: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(working_days), 0) FROM leave_requests
+             WHERE intern_id = ?1 AND leave_type_id = ?2 AND status IN ('pending', 'approved')",
+            params![p.intern_id, p.leave_type_id],
+            |r| r.get(0),
+        ).map_err(|e| e.to_string())?;
+        if used + working_days > max {
+            warnings.push(format!("{type_name} sınırı aşılıyor ({} / {max} gün)", used + working_days));
+        }
+    }
+    let present: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM attendance WHERE intern_id = ?1 AND status = 'present' AND date BETWEEN ?2 AND ?3",
+        params![p.intern_id, start_s, end_s],
+        |r| r.get(0),
+    ).map_err(|e| e.to_string())?;
+    if present > 0 {
+        warnings.push(format!("İzin aralığında {present} gün için \"geldi\" kaydı var"));
+    }
+
+    conn.execute(
+        r#"
+        INSERT INTO leave_requests (intern_id, leave_type_id, start_date, end_date, working_days, reason)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+        params![p.intern_id, p.leave_type_id, start_s, end_s, working_days, p.reason.trim()],
+    ).map_err(|e| e.to_string())?;
+    Ok(LeaveOutcome { id: conn.last_insert_rowid(), working_days, warnings, extension: None })
+}
+
+fn decide(conn: &Connection, id: i64, status: &str, note: Option<&str>) -> Result<i64, String> {
+    let (intern_id, current): (i64, String) = conn
+        .query_row("SELECT intern_id, status FROM leave_requests WHERE id = ?1", params![id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "İzin talebi bulunamadı".to_string())?;
+    let allowed = match status {
+        "approved" | "rejected" => current == "pending",
+        // onaylanmış izin de geri alınabilir
+        _ => current == "pending" || current == "approved",
+    };
+    if !allowed {
+        return Err(format!("Talep bu durumda değiştirilemez ({current})"));
+    }
+    conn.execute(
+        r#"
+        UPDATE leave_requests
+        SET status = ?1, decided_at = datetime('now'), decided_by = ?2, decision_note = ?3
+        WHERE id = ?4
+        "#,
+        params![status, current_os_user(), note.map(str::trim).filter(|n| !n.is_empty()), id],
+    ).map_err(|e| e.to_string())?;
+    Ok(intern_id)
+}
+
+pub(crate) fn approve_conn(conn: &Connection, cal: &WorkCalendar, id: i64, note: Option<&str>) -> Result<Option<ExtensionSuggestion>, String> {
+    let intern_id = decide(conn, id, "approved", note)?;
+    extension_conn(conn, cal, intern_id)
+}
+
+#[tauri::command]
+pub fn list_leave_types(handle: AppHandle) -> Result<Vec<LeaveType>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn
+        .prepare("SELECT id, name, counts_as_worked, max_days FROM leave_types ORDER BY name")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |r| {
+        Ok(LeaveType { id: r.get(0)?, name: r.get(1)?, counts_as_worked: r.get(2)?, max_days: r.get(3)? })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+#[tauri::command]
+pub fn create_leave_type(handle: AppHandle, leave_type: LeaveTypePayload) -> Result<i64, String> {
+    if leave_type.name.trim().is_empty() || leave_type.max_days.is_some_and(|d| d < 0) {
+        return Err("Geçersiz izin türü".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        "INSERT INTO leave_types (name, counts_as_worked, max_days) VALUES (?1, ?2, ?3)",
+        params![leave_type.name.trim(), leave_type.counts_as_worked, leave_type.max_days],
+    ).map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn update_leave_type(handle: AppHandle, id: i64, leave_type: LeaveTypePayload) -> Result<(), String> {
+    if leave_type.name.trim().is_empty() || leave_type.max_days.is_some_and(|d| d < 0) {
+        return Err("Geçersiz izin türü".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        "UPDATE leave_types SET name = ?1, counts_as_worked = ?2, max_days = ?3 WHERE id = ?4",
+        params![leave_type.name.trim(), leave_type.counts_as_worked, leave_type.max_days, id],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_leave_type(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let used: bool = conn
+        .query_row("SELECT EXISTS(SELECT 1 FROM leave_requests WHERE leave_type_id = ?1)", params![id], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    if used {
+        return Err("Bu izin türüyle açılmış talepler var".to_string());
+    }
+    conn.execute("DELETE FROM leave_types WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn request_leave(handle: AppHandle, request: LeaveRequestPayload) -> Result<LeaveOutcome, String> {
+    let mut conn = open_conn(&handle)?;
+    let cal = WorkCalendar::load(&conn)?;
+    // çakışma denetimi ve kayıt birlikte; eşzamanlı istekler ikisi birden kabul edilmez
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let outcome = request_conn(&tx, &cal, &request)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(outcome)
+}
+
+// Onay sonrası izin hakkı aşılıyorsa bitiş tarihi önerisi döner
+#[tauri::command]
+pub fn approve_leave(handle: AppHandle, id: i64, note: Option<String>) -> Result<Option<ExtensionSuggestion>, String> {
+    let conn = open_conn(&handle)?;
+    let cal = WorkCalendar::load(&conn)?;
+    approve_conn(&conn, &cal, id, note.as_deref())
+}
+
+#[tauri::command]
+pub fn reject_leave(handle: AppHandle, id: i64, note: Option<String>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    decide(&conn, id, "rejected", note.as_deref())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn cancel_leave(handle: AppHandle, id: i64, note: Option<String>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    decide(&conn, id, "cancelled", note.as_deref())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_leaves(handle: AppHandle, intern_id: Option<i64>, status: Option<String>) -> Result<Vec<LeaveRequest>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT r.id, r.intern_id, i.first_name || ' ' || i.last_name, r.leave_type_id, t.name,
+               r.start_date, r.end_date, r.working_days, r.reason, r.status,
+               r.requested_at, r.decided_at, r.decided_by, r.decision_note
+        FROM leave_requests r
+        JOIN interns i ON i.id = r.intern_id
+        JOIN leave_types t ON t.id = r.leave_type_id
+        WHERE (?1 IS NULL OR r.intern_id = ?1) AND (?2 IS NULL OR r.status = ?2)
+        ORDER BY r.start_date DESC, r.id DESC
+        "#
+    ).map_err(|e| e.to_string())?;
+    let status = status.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let rows = stmt.query_map(params![intern_id, status], |r| {
+        Ok(LeaveRequest {
+            id: r.get(0)?,
+            intern_id: r.get(1)?,
+            intern_name: r.get(2)?,
+            leave_type_id: r.get(3)?,
+            leave_type: r.get(4)?,
+            start_date: r.get(5)?,
+            end_date: r.get(6)?,
+            working_days: r.get(7)?,
+            reason: r.get(8)?,
+            status: r.get(9)?,
+            requested_at: r.get(10)?,
+            decided_at: r.get(11)?,
+            decided_by: r.get(12)?,
+            decision_note: r.get(13)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+#[tauri::command]
+pub fn suggest_leave_extension(handle: AppHandle, intern_id: i64) -> Result<Option<ExtensionSuggestion>, String> {
+    let conn = open_conn(&handle)?;
+    let cal = WorkCalendar::load(&conn)?;
+    extension_conn(&conn, &cal, intern_id)
+}
+
+#[tauri::command]
+pub fn get_leave_settings(handle: AppHandle) -> Result<LeaveSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_leave_settings(handle: AppHandle, settings: LeaveSettings) -> Result<(), String> {
+    if !(0..=365).contains(&settings.allowed_days) {
+        return Err("İzin hakkı 0 ile 365 gün arasında olmalı".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
@@ -0,0 +1,873 @@
+// E-posta bildirimleri: SMTP ayarları (parola işletim sisteminin anahtar
+// deposunda), kalıcı giden kutusu ve yeniden deneme. Tetikleyiciler yaklaşan /
+// geciken görevler, girilmemiş günlük notlar ve bitmek üzere olan stajlardır;
+// metinler templates modülündeki şablonlardan üretilir. Her bildirim dedupe_key
+// ile bir kez kuyruğa alınır. Hatırlatıcı zamanlayıcısı her turda kuyruğu
+// doldurur ve gönderir.
+
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate, Timelike};
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, WorkCalendar};
+use crate::mentors::OPEN_END;
+use crate::templates::{self, TemplateContext};
+use crate::{interns_missing_evaluation_conn, open_conn, reminders, settings};
+
+const SETTINGS_KEY: &str = "smtp";
+const KEYRING_SERVICE: &str = "InternTracker";
+// bir turda gönderilecek en fazla e-posta
+const BATCH_SIZE: i64 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Security {
+    // yalnızca yerel test sunucuları için (MailHog, smtp4dev vb.)
+    None,
+    StartTls,
+    Tls,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmtpSettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub security: Security,
+    // boşsa kimlik doğrulama yapılmaz; parola ayarlarda değil anahtar deposunda
+    pub username: String,
+    pub from_address: String,
+    pub from_name: String,
+    // mentoru olmayan stajyerler ve staj bitişi için ek alıcı
+    pub admin_address: String,
+    pub timeout_secs: u64,
+    pub max_attempts: i64,
+    // ilk yeniden deneme gecikmesi; her denemede iki katına çıkar
+    pub retry_minutes: i64,
+
+    pub upcoming_deadlines: bool,
+    pub overdue_tasks: bool,
+    // gecikme e-postası en geç teslim tarihinden bu kadar gün sonrasına kadar
+    // (0: yalnızca geciktiği ilk gün); eski gecikmeler toplu gönderilmez
+    pub overdue_window_days: i64,
+    pub missing_evaluations: bool,
+    pub internship_ending: bool,
+    pub ending_days_before: i64,
+}
+
+impl Default for SmtpSettings {
+    fn default() -> Self {
+        SmtpSettings {
+            enabled: false,
+            host: String::new(),
+            port: 587,
+            security: Security::StartTls,
+            username: String::new(),
+            from_address: String::new(),
+            from_name: "InternTracker".to_string(),
+            admin_address: String::new(),
+            timeout_secs: 30,
+            max_attempts: 5,
+            retry_minutes: 10,
+            upcoming_deadlines: true,
+            overdue_tasks: true,
+            overdue_window_days: 3,
+            missing_evaluations: true,
+            internship_ending: true,
+            ending_days_before: 5,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutboxEmail {
+    pub id: i64,
+    pub kind: String,
+    pub to_addrs: String,
+    pub cc_addrs: String,
+    pub subject: String,
+    pub body: String,
+    // pending | sent | failed
+    pub status: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub next_attempt_at: String,
+    pub created_at: String,
+    pub sent_at: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct EmailRunReport {
+    pub queued: usize,
+    pub sent: usize,
+    // yeniden denenecek
+    pub retrying: usize,
+    // deneme hakkı biten
+    pub failed: usize,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS email_outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            -- aynı bildirim ikinci kez kuyruğa alınmaz (NULL: elle gönderim)
+            dedupe_key TEXT UNIQUE,
+            kind TEXT NOT NULL,
+            to_addrs TEXT NOT NULL,
+            cc_addrs TEXT NOT NULL DEFAULT '',
+            subject TEXT NOT NULL,
+            body TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending'
+                CHECK (status IN ('pending', 'sent', 'failed')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            next_attempt_at TEXT NOT NULL DEFAULT (datetime('now')),
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            sent_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_email_outbox_pending ON email_outbox(status, next_attempt_at);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<SmtpSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+fn keyring_entry(s: &SmtpSettings) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, &format!("smtp:{}@{}", s.username.trim(), s.host.trim()))
+        .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_password(s: &SmtpSettings) -> Result<Option<String>, String> {
+    if s.username.trim().is_empty() {
+        return Ok(None);
+    }
+    match keyring_entry(s)?.get_password() {
+        Ok(p) => Ok(Some(p)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("SMTP parolası okunamadı: {e}")),
+    }
+}
+
+fn validate(s: &SmtpSettings) -> Result<(), String> {
+    if s.enabled {
+        if s.host.trim().is_empty() {
+            return Err("SMTP sunucusu boş olamaz".to_string());
+        }
+        s.from_address.trim().parse::<Mailbox>().map_err(|_| "Gönderen adresi geçersiz".to_string())?;
+    }
+    if !s.admin_address.trim().is_empty() {
+        s.admin_address.trim().parse::<Mailbox>().map_err(|_| "Yönetici adresi geçersiz".to_string())?;
+    }
+    if s.port == 0 {
+        return Err("Geçersiz port".to_string());
+    }
+    if !(1..=20).contains(&s.max_attempts) || !(1..=1440).contains(&s.retry_minutes) {
+        return Err("Deneme sayısı 1-20, bekleme 1-1440 dakika olmalı".to_string());
+    }
+    if !(0..=30).contains(&s.overdue_window_days) {
+        return Err("Gecikme e-postası süresi 0 ile 30 gün arasında olmalı".to_string());
+    }
+    if !(0..=60).contains(&s.ending_days_before) {
+        return Err("Staj bitiş uyarısı 0 ile 60 gün arasında olmalı".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn transport(s: &SmtpSettings, password: Option<String>) -> Result<SmtpTransport, String> {
+    let host = s.host.trim();
+    let builder = match s.security {
+        Security::None => SmtpTransport::builder_dangerous(host),
+        Security::StartTls => SmtpTransport::starttls_relay(host).map_err(|e| e.to_string())?,
+        Security::Tls => SmtpTransport::relay(host).map_err(|e| e.to_string())?,
+    };
+    let mut builder = builder.port(s.port).timeout(Some(Duration::from_secs(s.timeout_secs.max(1))));
+    if !s.username.trim().is_empty() {
+        builder = builder.credentials(Credentials::new(s.username.trim().to_string(), password.unwrap_or_default()));
+    }
+    Ok(builder.build())
+}
+
+fn mailboxes(list: &str) -> Result<Vec<Mailbox>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(|a| a.parse::<Mailbox>().map_err(|_| format!("Geçersiz adres: {a}")))
+        .collect()
+}
+
+pub(crate) fn build_message(s: &SmtpSettings, to: &str, cc: &str, subject: &str, body: &str) -> Result<Message, String> {
+    let from = Mailbox::new(
+        Some(s.from_name.trim().to_string()).filter(|n| !n.is_empty()),
+        s.from_address.trim().parse().map_err(|_| "Gönderen adresi geçersiz".to_string())?,
+    );
+    let mut builder = Message::builder().from(from).subject(subject).header(ContentType::TEXT_PLAIN);
+    let to = mailboxes(to)?;
+    if to.is_empty() {
+        return Err("Alıcı yok".to_string());
+    }
+    for m in to {
+        builder = builder.to(m);
+    }
+    for m in mailboxes(cc)? {
+        builder = builder.cc(m);
+    }
+    builder.body(body.to_string()).map_err(|e| e.to_string())
+}
+
+/// Kuyruğa ekler; aynı dedupe_key daha önce eklendiyse false.
+pub(crate) fn enqueue(
+    conn: &Connection,
+    dedupe_key: Option<&str>,
+    kind: &str,
+    to: &[String],
+    cc: &[String],
+    subject: &str,
+    body: &str,
+) -> Result<bool, String> {
+    let mut to: Vec<&str> = to.iter().map(|a| a.trim()).filter(|a| !a.is_empty()).collect();
+    let mut cc: Vec<&str> = cc.iter().map(|a| a.trim()).filter(|a| !a.is_empty() && !to.contains(a)).collect();
+    to.dedup();
+    cc.dedup();
+    // yalnızca bilgi alıcısı varsa asıl alıcıya taşınır
+    if to.is_empty() {
+        to = std::mem::take(&mut cc);
+    }
+    if to.is_empty() {
+        return Ok(false);
+    }
+    let n = conn.execute(
+        r#"
+        INSERT OR IGNORE INTO email_outbox (dedupe_key, kind, to_addrs, cc_addrs, subject, body)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+        params![dedupe_key, kind, to.join(", "), cc.join(", "), subject, body],
+    ).map_err(|e| e.to_string())?;
+    Ok(n == 1)
+}
+
+// Stajyerin bugün görevli, aktif ve e-postası olan mentorları
+fn mentor_emails(conn: &Connection, intern_id: i64, today: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT DISTINCT m.email FROM intern_mentors im
+        JOIN mentors m ON m.id = im.mentor_id
+        WHERE im.intern_id = ?1 AND m.active = 1 AND trim(m.email) <> ''
+          AND im.start_date <= ?2 AND COALESCE(im.end_date, ?3) >= ?2
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![intern_id, today, OPEN_END], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+fn admin(s: &SmtpSettings) -> Vec<String> {
+    Some(s.admin_address.trim().to_string()).filter(|a| !a.is_empty()).into_iter().collect()
+}
+
+fn queue_deadlines(conn: &Connection, s: &SmtpSettings, leads: &[i64], today: NaiveDate, report: &mut EmailRunReport) -> Result<(), String> {
+    let max_lead = leads.iter().copied().max().unwrap_or(-1);
+    let day = format_date(today);
+    let locale = templates::load_settings(conn)?.default_locale;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT a.id, a.intern_id, date(a.due_date), i.email,
+               CAST(julianday(date(a.due_date)) - julianday(?1) AS INTEGER)
+        FROM assignments a
+        JOIN interns i ON i.id = a.intern_id
+        WHERE a.status <> 'Completed'
+          AND date(a.due_date) IS NOT NULL
+          AND julianday(date(a.due_date)) - julianday(?1) BETWEEN -?3 - 1 AND ?2
+        ORDER BY a.due_date, a.id
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![day, max_lead, s.overdue_window_days], |r| {
+        Ok((
+            r.get::<_, i64>(0)?,
+            r.get::<_, i64>(1)?,
+            r.get::<_, String>(2)?,
+            r.get::<_, String>(3)?,
+            r.get::<_, i64>(4)?,
+        ))
+    }).map_err(|e| e.to_string())?;
+    for r in rows {
+        let (id, intern_id, due, email, days_left) = r.map_err(|e| e.to_string())?;
+        let (key, kind) = if days_left < 0 {
+            if !s.overdue_tasks { continue; }
+            (format!("overdue:{id}:{due}"), "overdue")
+        } else {
+            if !s.upcoming_deadlines { continue; }
+            let Some(lead) = leads.iter().copied().filter(|l| *l >= days_left).min() else { continue };
+            (format!("deadline:{id}:{due}:{lead}"), "deadline")
+        };
+        let template = if kind == "overdue" { "overdue_task" } else { "deadline_reminder" };
+        let ctx = TemplateContext { assignment_id: Some(id), ..Default::default() };
+        let m = templates::render_stored(conn, template, &locale, &ctx, today)?;
+        if enqueue(conn, Some(&key), kind, &[email], &mentor_emails(conn, intern_id, &day)?, &m.subject, &m.body)? {
+            report.queued += 1;
+        }
+    }
+    Ok(())
+}
+
+// Mentor başına tek e-posta; mentoru olmayan stajyerler yöneticiye
+fn queue_missing_evaluations(conn: &Connection, s: &SmtpSettings, today: NaiveDate, report: &mut EmailRunReport) -> Result<(), String> {
+    let cal = WorkCalendar::load(conn)?;
+    let missing = interns_missing_evaluation_conn(conn, &cal, today, today)?;
+    let day = format_date(today);
+    let locale = templates::load_settings(conn)?.default_locale;
+    let mut by_recipient: Vec<(String, Vec<i64>)> = Vec::new();
+    for m in missing {
+        let id = m.intern.id.unwrap_or_default();
+        let mut recipients = mentor_emails(conn, id, &day)?;
+        if recipients.is_empty() {
+            recipients = admin(s);
+        }
+        for r in recipients {
+            match by_recipient.iter_mut().find(|(a, _)| *a == r) {
+                Some((_, ids)) => ids.push(id),
+                None => by_recipient.push((r, vec![id])),
+            }
+        }
+    }
+    for (to, intern_ids) in by_recipient {
+        let ctx = TemplateContext { intern_ids, ..Default::default() };
+        let m = templates::render_stored(conn, "missing_evaluation", &locale, &ctx, today)?;
+        let key = format!("missing_evaluation:{day}:{}", to.to_lowercase());
+        if enqueue(conn, Some(&key), "missing_evaluation", &[to], &[], &m.subject, &m.body)? {
+            report.queued += 1;
+        }
+    }
+    Ok(())
+}
+
+fn queue_endings(conn: &Connection, s: &SmtpSettings, today: NaiveDate, report: &mut EmailRunReport) -> Result<(), String> {
+    let day = format_date(today);
+    let locale = templates::load_settings(conn)?.default_locale;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, end_date FROM interns
+        WHERE end_date IS NOT NULL AND end_date <> ''
+          AND julianday(end_date) - julianday(?1) BETWEEN 0 AND ?2
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![day, s.ending_days_before], |r| {
+        Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?))
+    }).map_err(|e| e.to_string())?;
+    for r in rows {
+        let (id, end) = r.map_err(|e| e.to_string())?;
+        let ctx = TemplateContext { intern_id: Some(id), ..Default::default() };
+        let m = templates::render_stored(conn, "internship_ending", &locale, &ctx, today)?;
+        let key = format!("ending:{id}:{end}");
+        if enqueue(conn, Some(&key), "internship_ending", &mentor_emails(conn, id, &day)?, &admin(s), &m.subject, &m.body)? {
+            report.queued += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Tetikleyicileri değerlendirip kuyruğa ekler (gönderim yapmaz).
+pub(crate) fn queue_conn(conn: &Connection, s: &SmtpSettings, today: NaiveDate, hour: u32) -> Result<EmailRunReport, String> {
+    let rs = reminders::load_settings(conn)?;
+    let mut leads: Vec<i64> = rs.lead_days.iter().copied().filter(|d| *d >= 0).collect();
+    leads.sort_unstable();
+    leads.dedup();
+    let mut report = EmailRunReport::default();
+    if s.upcoming_deadlines || s.overdue_tasks {
+        queue_deadlines(conn, s, &leads, today, &mut report)?;
+    }
+    // günlük not: hatırlatıcıdaki saatten sonra
+    if s.missing_evaluations && hour >= rs.missing_evaluation_hour {
+        queue_missing_evaluations(conn, s, today, &mut report)?;
+    }
+    if s.internship_ending {
+        queue_endings(conn, s, today, &mut report)?;
+    }
+    Ok(report)
+}
+
+// Gönderimi bu kurulumda başka bir tur (zamanlayıcı / run_email_now) almışsa
+// false. next_attempt_at ileri alınarak kayıt kiralanır; gönderim yarıda
+// kalırsa kira bitince yeniden denenir.
+fn claim(conn: &Connection, s: &SmtpSettings, id: i64) -> Result<bool, String> {
+    let lease = s.timeout_secs.max(1) * 4 + 60;
+    let n = conn.execute(
+        r#"
+        UPDATE email_outbox SET next_attempt_at = datetime('now', '+' || ?1 || ' seconds')
+        WHERE id = ?2 AND status = 'pending' AND next_attempt_at <= datetime('now')
+        "#,
+        params![lease as i64, id],
+    ).map_err(|e| e.to_string())?;
+    Ok(n == 1)
+}
+
+/// Zamanı gelen e-postaları gönderir; bağlantı hatalarında üstel bekleme ile
+/// yeniden dener. Geçersiz adres gibi ileti hataları doğrudan başarısız olur.
+pub(crate) fn process_conn(conn: &Connection, s: &SmtpSettings, mailer: &SmtpTransport, report: &mut EmailRunReport) -> Result<(), String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, to_addrs, cc_addrs, subject, body, attempts FROM email_outbox
+        WHERE status = 'pending' AND next_attempt_at <= datetime('now')
+        ORDER BY next_attempt_at, id LIMIT ?1
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![BATCH_SIZE], |r| {
+        Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?, r.get::<_, String>(2)?, r.get::<_, String>(3)?, r.get::<_, String>(4)?, r.get::<_, i64>(5)?))
+    }).map_err(|e| e.to_string())?;
+    let mut due = Vec::new();
+    for r in rows { due.push(r.map_err(|e| e.to_string())?); }
+
+    for (id, to, cc, subject, body, attempts) in due {
+        if !claim(conn, s, id)? {
+            continue;
+        }
+        let attempts = attempts + 1;
+        let (failed, error) = match build_message(s, &to, &cc, &subject, &body) {
+            Err(e) => (true, e),
+            Ok(m) => match mailer.send(&m) {
+                Ok(_) => {
+                    conn.execute(
+                        "UPDATE email_outbox SET status = 'sent', attempts = ?1, last_error = NULL, sent_at = datetime('now') WHERE id = ?2",
+                        params![attempts, id],
+                    ).map_err(|e| e.to_string())?;
+                    report.sent += 1;
+                    continue;
+                }
+                // 5xx: sunucu iletiyi kalıcı olarak reddetti
+                Err(e) => (attempts >= s.max_attempts || e.is_permanent(), e.to_string()),
+            },
+        };
+        // 10, 20, 40 ... dakika; en fazla bir gün
+        let delay = (s.retry_minutes << (attempts - 1).min(16)).min(1440);
+        conn.execute(
+            r#"
+            UPDATE email_outbox
+            SET status = ?1, attempts = ?2, last_error = ?3,
+                next_attempt_at = datetime('now', '+' || ?4 || ' minutes')
+            WHERE id = ?5
+            "#,
+            params![if failed { "failed" } else { "pending" }, attempts, error, delay, id],
+        ).map_err(|e| e.to_string())?;
+        if failed { report.failed += 1 } else { report.retrying += 1 }
+    }
+    Ok(())
+}
+
+pub(crate) fn run(handle: &AppHandle) -> Result<EmailRunReport, String> {
+    let conn = open_conn(handle)?;
+    let s = load_settings(&conn)?;
+    if !s.enabled {
+        return Ok(EmailRunReport::default());
+    }
+    let now = Local::now();
+    let mut report = queue_conn(&conn, &s, now.date_naive(), now.hour())?;
+    let mailer = transport(&s, load_password(&s)?)?;
+    process_conn(&conn, &s, &mailer, &mut report)?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn get_smtp_settings(handle: AppHandle) -> Result<SmtpSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+// password: None = değiştirme, "" = sil
+#[tauri::command]
+pub fn update_smtp_settings(handle: AppHandle, settings: SmtpSettings, password: Option<String>) -> Result<(), String> {
+    validate(&settings)?;
+    if let Some(p) = password {
+        if settings.username.trim().is_empty() {
+            return Err("Parola için kullanıcı adı gerekli".to_string());
+        }
+        let entry = keyring_entry(&settings)?;
+        if p.is_empty() {
+            match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(format!("SMTP parolası silinemedi: {e}")),
+            }
+        } else {
+            entry.set_password(&p).map_err(|e| format!("SMTP parolası kaydedilemedi: {e}"))?;
+        }
+    }
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
+
+#[tauri::command]
+pub fn has_smtp_password(handle: AppHandle) -> Result<bool, String> {
+    let conn = open_conn(&handle)?;
+    Ok(load_password(&load_settings(&conn)?)?.is_some())
+}
+
+// Kuyruğu atlayarak hemen gönderir (ayarları denemek için). SMTP çağrısı
+// arayüzü dondurmasın diye ayrı iş parçacığında çalışır.
+#[tauri::command]
+pub async fn send_test_email(handle: AppHandle, to: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || send_test(&handle, &to))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn send_test(handle: &AppHandle, to: &str) -> Result<(), String> {
+    let conn = open_conn(handle)?;
+    let s = load_settings(&conn)?;
+    validate(&SmtpSettings { enabled: true, ..s.clone() })?;
+    let message = build_message(
+        &s,
+        to,
+        "",
+        "[InternTracker] Test e-postası",
+        "SMTP ayarları çalışıyor. Bu e-posta InternTracker ayarlar ekranından gönderilmiştir.",
+    )?;
+    transport(&s, load_password(&s)?)?
+        .send(&message)
+        .map(|_| ())
+        .map_err(|e| format!("E-posta gönderilemedi: {e}"))
+}
+
+#[tauri::command]
+pub fn list_outbox(handle: AppHandle, status: Option<String>, limit: Option<i64>) -> Result<Vec<OutboxEmail>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, kind, to_addrs, cc_addrs, subject, body, status, attempts, last_error,
+               next_attempt_at, created_at, sent_at
+        FROM email_outbox
+        WHERE ?1 IS NULL OR status = ?1
+        ORDER BY id DESC LIMIT ?2
+        "#
+    ).map_err(|e| e.to_string())?;
+    let status = status.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let rows = stmt.query_map(params![status, limit.unwrap_or(200).clamp(1, 1000)], |r| {
+        Ok(OutboxEmail {
+            id: r.get(0)?,
+            kind: r.get(1)?,
+            to_addrs: r.get(2)?,
+            cc_addrs: r.get(3)?,
+            subject: r.get(4)?,
+            body: r.get(5)?,
+            status: r.get(6)?,
+            attempts: r.get(7)?,
+            last_error: r.get(8)?,
+            next_attempt_at: r.get(9)?,
+            created_at: r.get(10)?,
+            sent_at: r.get(11)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+// Başarısız e-postayı deneme sayacını sıfırlayarak kuyruğa geri alır
+#[tauri::command]
+pub fn retry_outbox_email(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        "UPDATE email_outbox SET status = 'pending', attempts = 0, next_attempt_at = datetime('now') WHERE id = ?1 AND status <> 'sent'",
+        params![id],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Yeniden denenecek e-posta bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_outbox_email(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    conn.execute("DELETE FROM email_outbox WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Zamanlayıcıyı beklemeden kuyruğu doldur ve gönder
+#[tauri::command]
+pub async fn run_email_now(handle: AppHandle) -> Result<EmailRunReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    // DATA içeriklerini toplayan en basit SMTP sunucusu
+    fn smtp_stand_in() -> (u16, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut out) = stream else { break };
+                let mut reader = BufReader::new(out.try_clone().unwrap());
+                out.write_all(b"220 localhost ESMTP\r\n").unwrap();
+                let mut data: Option<String> = None;
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                        break;
+                    }
+                    if let Some(buf) = data.as_mut() {
+                        if line == ".\r\n" {
+                            sink.lock().unwrap().push(data.take().unwrap());
+                            out.write_all(b"250 queued\r\n").unwrap();
+                        } else {
+                            buf.push_str(&line);
+                        }
+                        continue;
+                    }
+                    let cmd = line.to_ascii_uppercase();
+                    if cmd.starts_with("DATA") {
+                        data = Some(String::new());
+                        out.write_all(b"354 go ahead\r\n").unwrap();
+                    } else if cmd.starts_with("QUIT") {
+                        out.write_all(b"221 bye\r\n").unwrap();
+                        break;
+                    } else {
+                        out.write_all(b"250 ok\r\n").unwrap();
+                    }
+                }
+            }
+        });
+        (port, received)
+    }
+
+    // dinlenmeyen port: bağlantı reddedilir
+    fn closed_port() -> u16 {
+        TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+    }
+
+    fn test_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::ensure_schema(&conn).unwrap();
+        conn
+    }
+
+    fn smtp(port: u16) -> SmtpSettings {
+        SmtpSettings {
+            enabled: true,
+            host: "127.0.0.1".to_string(),
+            port,
+            security: Security::None,
+            from_address: "staj@example.com".to_string(),
+            admin_address: "ik@example.com".to_string(),
+            timeout_secs: 5,
+            ..Default::default()
+        }
+    }
+
+    fn run_once(conn: &Connection, s: &SmtpSettings) -> EmailRunReport {
+        let mut report = EmailRunReport::default();
+        process_conn(conn, s, &transport(s, None).unwrap(), &mut report).unwrap();
+        report
+    }
+
+    fn row(conn: &Connection, id: i64) -> (String, i64, Option<String>, i64) {
+        conn.query_row(
+            r#"
+            SELECT status, attempts, last_error,
+                   CAST(round((julianday(next_attempt_at) - julianday('now')) * 1440) AS INTEGER)
+            FROM email_outbox WHERE id = ?1
+            "#,
+            params![id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .unwrap()
+    }
+
+    fn queue(conn: &Connection, to: &str) -> i64 {
+        assert!(enqueue(conn, None, "test", &[to.to_string()], &[], "Konu", "Merhaba").unwrap());
+        conn.last_insert_rowid()
+    }
+
+    fn outbox(conn: &Connection) -> Vec<(String, String, String)> {
+        let mut stmt = conn.prepare("SELECT kind, dedupe_key, to_addrs FROM email_outbox ORDER BY id").unwrap();
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?))).unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    fn add_intern(conn: &Connection, name: &str, end: &str) -> i64 {
+        conn.execute(
+            r#"
+            INSERT INTO interns (first_name, last_name, school, department, start_date, end_date, status, contact, email)
+            VALUES (?1, 'Test', 'İTÜ', 'Bilgisayar', '2025-06-02', ?2, 'aktif', '', ?3)
+            "#,
+            params![name, end, format!("{}@example.com", name.to_lowercase())],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn add_assignment(conn: &Connection, intern_id: i64, due: &str, status: &str) -> i64 {
+        conn.execute(
+            r#"
+            INSERT INTO assignments (intern_id, project_type, task_description, due_date, status)
+            VALUES (?1, 'Web', 'Görev', ?2, ?3)
+            "#,
+            params![intern_id, due, status],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn wednesday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 11).unwrap()
+    }
+
+    #[test]
+    fn queued_email_is_sent_once() {
+        let (port, received) = smtp_stand_in();
+        let conn = test_conn();
+        let s = smtp(port);
+        let id = queue(&conn, "ayse@example.com");
+
+        let report = run_once(&conn, &s);
+        assert_eq!((report.sent, report.retrying, report.failed), (1, 0, 0));
+        let (status, attempts, error, _) = row(&conn, id);
+        assert_eq!((status.as_str(), attempts, error), ("sent", 1, None));
+        let mails = received.lock().unwrap().clone();
+        assert_eq!(mails.len(), 1);
+        assert!(mails[0].contains("To: ayse@example.com"));
+        assert!(mails[0].contains("Subject: Konu"));
+
+        assert_eq!(run_once(&conn, &s).sent, 0);
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn refused_connection_backs_off_then_fails() {
+        let conn = test_conn();
+        let s = SmtpSettings { max_attempts: 3, ..smtp(closed_port()) };
+        let id = queue(&conn, "ayse@example.com");
+
+        let report = run_once(&conn, &s);
+        assert_eq!((report.sent, report.retrying), (0, 1));
+        let (status, attempts, error, wait) = row(&conn, id);
+        assert_eq!((status.as_str(), attempts), ("pending", 1));
+        assert!(error.is_some());
+        assert_eq!(wait, s.retry_minutes);
+
+        // bekleme dolmadan yeniden denenmez
+        assert_eq!(run_once(&conn, &s).retrying, 0);
+
+        conn.execute("UPDATE email_outbox SET next_attempt_at = datetime('now', '-1 minute')", []).unwrap();
+        run_once(&conn, &s);
+        assert_eq!(row(&conn, id).3, s.retry_minutes * 2);
+
+        conn.execute("UPDATE email_outbox SET next_attempt_at = datetime('now', '-1 minute')", []).unwrap();
+        assert_eq!(run_once(&conn, &s).failed, 1);
+        assert_eq!(row(&conn, id).0, "failed");
+    }
+
+    #[test]
+    fn invalid_address_fails_without_retry() {
+        let (port, received) = smtp_stand_in();
+        let conn = test_conn();
+        let id = queue(&conn, "adres değil");
+
+        assert_eq!(run_once(&conn, &smtp(port)).failed, 1);
+        let (status, attempts, error, _) = row(&conn, id);
+        assert_eq!((status.as_str(), attempts), ("failed", 1));
+        assert!(error.unwrap().contains("Geçersiz adres"));
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn claimed_email_is_skipped_by_other_run() {
+        let (port, received) = smtp_stand_in();
+        let conn = test_conn();
+        let s = smtp(port);
+        let id = queue(&conn, "ayse@example.com");
+
+        assert!(claim(&conn, &s, id).unwrap());
+        assert!(!claim(&conn, &s, id).unwrap());
+        assert_eq!(run_once(&conn, &s).sent, 0);
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn deadline_triggers_skip_old_overdue_tasks() {
+        let conn = test_conn();
+        let s = smtp(closed_port());
+        let intern = add_intern(&conn, "Ayse", "2025-06-30");
+        let soon = add_assignment(&conn, intern, "2025-06-12", "Planned");
+        let late = add_assignment(&conn, intern, "2025-06-10", "In Progress");
+        add_assignment(&conn, intern, "2025-06-01", "Planned");
+        add_assignment(&conn, intern, "2025-06-09", "Completed");
+        add_assignment(&conn, intern, "2025-06-25", "Planned");
+
+        let report = queue_conn(&conn, &s, wednesday(), 9).unwrap();
+        assert_eq!(report.queued, 2);
+        assert_eq!(
+            outbox(&conn),
+            vec![
+                ("overdue".to_string(), format!("overdue:{late}:2025-06-10"), "ayse@example.com".to_string()),
+                ("deadline".to_string(), format!("deadline:{soon}:2025-06-12:1"), "ayse@example.com".to_string()),
+            ]
+        );
+        // aynı gün ikinci tur yeni e-posta üretmez
+        assert_eq!(queue_conn(&conn, &s, wednesday(), 10).unwrap().queued, 0);
+
+        let s = SmtpSettings { overdue_tasks: false, upcoming_deadlines: false, ..s };
+        conn.execute("DELETE FROM email_outbox", []).unwrap();
+        assert_eq!(queue_conn(&conn, &s, wednesday(), 9).unwrap().queued, 0);
+    }
+
+    #[test]
+    fn evaluation_and_ending_triggers() {
+        let conn = test_conn();
+        let s = smtp(closed_port());
+        let ending = add_intern(&conn, "Ali", "2025-06-14");
+        let evaluated = add_intern(&conn, "Zeynep", "2025-07-31");
+        add_intern(&conn, "Mert", "2025-08-29");
+        conn.execute(
+            "INSERT INTO evaluations (intern_id, label, score, created_at) VALUES (?1, 'Günlük', 80, '2025-06-11 10:00:00')",
+            params![evaluated],
+        )
+        .unwrap();
+
+        // günlük not hatırlatması ayarlanan saatten önce gönderilmez
+        queue_conn(&conn, &s, wednesday(), 9).unwrap();
+        assert_eq!(
+            outbox(&conn),
+            vec![("internship_ending".to_string(), format!("ending:{ending}:2025-06-14"), "ik@example.com".to_string())]
+        );
+
+        queue_conn(&conn, &s, wednesday(), 17).unwrap();
+        let rows = outbox(&conn);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1],
+            ("missing_evaluation".to_string(), "missing_evaluation:2025-06-11:ik@example.com".to_string(), "ik@example.com".to_string())
+        );
+        let body: String = conn.query_row("SELECT body FROM email_outbox WHERE kind = 'missing_evaluation'", [], |r| r.get(0)).unwrap();
+        assert!(body.contains("Ali Test") && body.contains("Mert Test"));
+        assert!(!body.contains("Zeynep"));
+
+        let s = SmtpSettings { missing_evaluations: false, internship_ending: false, ..s };
+        conn.execute("DELETE FROM email_outbox", []).unwrap();
+        assert_eq!(queue_conn(&conn, &s, wednesday(), 17).unwrap().queued, 0);
+    }
+}
@@ -3,13 +3,44 @@
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 use tauri_plugin_sql::{Builder as SqlBuilder, Migration, MigrationKind};
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use chrono::NaiveDate;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri_plugin_dialog;
 use tauri_plugin_fs;
 
+mod analytics;
+mod api;
+mod archive;
+mod applications;
+mod attendance;
+mod calendar;
+mod certificate;
+mod dashboard;
+mod docx;
+mod events;
+mod ics;
+mod interviews;
+mod journal;
+mod leaves;
+mod mail;
+mod mentors;
+mod pdf;
+mod periods;
+mod query;
+mod reference;
+mod reminders;
+mod report;
+mod search;
+mod settings;
+mod sync;
+mod templates;
+mod vcard;
+mod views;
+mod webhooks;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct InternLite {
     id: Option<i64>,
@@ -25,6 +56,33 @@ struct InternLite {
     // sadece meta (liste görünümü)
     cv_name: Option<String>,
     photo_name: Option<String>,
+    period_id: Option<i64>,
+    // kurulumlar arası kalıcı kimlik
+    uuid: Option<String>,
+}
+
+// InternLite için kolon listesi (`i` takma adlı interns tablosu)
+const INTERN_LITE_COLUMNS: &str = "i.id, i.first_name, i.last_name, i.school, i.department, \
+     i.start_date, i.end_date, i.status, i.contact, i.email, \
+     i.cv_name, i.photo_name, i.period_id, i.uuid";
+
+fn intern_lite_from_row(row: &rusqlite::Row) -> rusqlite::Result<InternLite> {
+    Ok(InternLite {
+        id: row.get(0)?,
+        first_name: row.get(1)?,
+        last_name: row.get(2)?,
+        school: row.get(3)?,
+        department: row.get(4)?,
+        start_date: row.get(5)?,
+        end_date: row.get(6)?,
+        status: row.get(7)?,
+        contact: row.get(8)?,
+        email: row.get(9)?,
+        cv_name: row.get(10)?,
+        photo_name: row.get(11)?,
+        period_id: row.get(12)?,
+        uuid: row.get(13)?,
+    })
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,6 +109,9 @@ struct InternPayload {
     photo_name: Option<String>,
     photo_mime: Option<String>,
     photo_blob: Option<Vec<u8>>,
+
+    // staj dönemi (periods.id)
+    period_id: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,6 +124,8 @@ struct Assignment {
     status: String,
     file_path: Option<String>,
     created_at: Option<String>,
+    // yalnızca okumada dolu
+    uuid: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -74,6 +137,11 @@ struct Evaluation {
     #[serde(rename = "puan")]
     score: i64,
     created_at: Option<String>,
+    // değerlendiren mentor (mentors.id)
+    evaluator_id: Option<i64>,
+    // yalnızca okumada dolu
+    evaluator_name: Option<String>,
+    uuid: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -194,18 +262,30 @@ fn storage_root(handle: &AppHandle) -> Result<PathBuf, String> {
 }
 
 
+// Türkçe harfleri ASCII karşılığına indirger (ı/I/İ -> i)
+fn fold_tr_char(ch: char) -> Option<char> {
+    match ch {
+        'ç' | 'Ç' => Some('c'),
+        'ğ' | 'Ğ' => Some('g'),
+        'ı' | 'I' | 'İ' => Some('i'),
+        'ö' | 'Ö' => Some('o'),
+        'ş' | 'Ş' => Some('s'),
+        'ü' | 'Ü' => Some('u'),
+        _ => None,
+    }
+}
+
+// Arama için katlama: karakter sayısı korunur (vurgulama bu sayede eşlenir)
+fn fold_tr(s: &str) -> String {
+    s.chars()
+        .map(|ch| fold_tr_char(ch).unwrap_or_else(|| ch.to_lowercase().next().unwrap_or(ch)))
+        .collect()
+}
+
 fn slug_tr(s: &str) -> String {
     let mut out = String::new();
     for ch in s.chars() {
-        let x = match ch {
-            'ç' | 'Ç' => 'c',
-            'ğ' | 'Ğ' => 'g',
-            'ı' | 'I' | 'İ' => 'i',
-            'ö' | 'Ö' => 'o',
-            'ş' | 'Ş' => 's',
-            'ü' | 'Ü' => 'u',
-            _ => ch.to_ascii_lowercase(),
-        };
+        let x = fold_tr_char(ch).unwrap_or(ch.to_ascii_lowercase());
         if x.is_ascii_alphanumeric() { out.push(x); }
         else if x == ' ' || x == '-' || x == '_' { out.push('_'); }
         else { out.push('_'); }
@@ -215,6 +295,15 @@ fn slug_tr(s: &str) -> String {
     out
 }
 
+// Kurulumu kullanan kişi (kaydedilmiş görünümlerin sahibi vb.)
+fn current_os_user() -> String {
+    std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .ok()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| "default".to_string())
+}
+
 // interns\{ID}_{ad}_{soyad}
 fn person_dir(handle: &AppHandle, id: i64, first: &str, last: &str) -> Result<PathBuf, String> {
     let name = format!("{}_{}_{}", id, slug_tr(first), slug_tr(last));
@@ -305,6 +394,70 @@ fn ensure_schema(conn: &Connection) -> Result<(), String> {
     add_column_if_missing(conn, "interns", "photo_mime", "TEXT")?;
     add_column_if_missing(conn, "interns", "photo_blob", "BLOB")?;
 
+    // Staj dönemleri
+    periods::ensure_tables(conn)?;
+    add_column_if_missing(conn, "interns", "period_id", "INTEGER REFERENCES periods(id) ON DELETE SET NULL")?;
+
+    // Mentorlar
+    mentors::ensure_tables(conn)?;
+    add_column_if_missing(conn, "evaluations", "evaluator_id", "INTEGER REFERENCES mentors(id) ON DELETE SET NULL")?;
+
+    // Devam takibi (zorunlu gün; NULL = ayarlardaki varsayılan)
+    attendance::ensure_tables(conn)?;
+    add_column_if_missing(conn, "interns", "required_days", "INTEGER")?;
+
+    // İzin talepleri
+    leaves::ensure_tables(conn)?;
+
+    // Staj defteri
+    journal::ensure_tables(conn)?;
+
+    // Başvurular
+    applications::ensure_tables(conn)?;
+
+    // Mülakatlar
+    interviews::ensure_tables(conn)?;
+
+    // E-posta giden kutusu
+    mail::ensure_tables(conn)?;
+
+    // Mesaj şablonları
+    templates::ensure_tables(conn)?;
+
+    // Alan olayları ve webhook teslimatları
+    events::ensure_tables(conn)?;
+    add_column_if_missing(conn, "domain_events", "actor", "TEXT")?;
+    webhooks::ensure_tables(conn)?;
+
+    // Yerel REST API anahtarları
+    api::ensure_tables(conn)?;
+
+    // Kurulumlar arası eşitleme: kalıcı kimlikler ve satır sürümleri
+    // (şema sürümü app_settings'te)
+    settings::ensure_tables(conn)?;
+    add_column_if_missing(conn, "interns", "uuid", "TEXT")?;
+    add_column_if_missing(conn, "assignments", "uuid", "TEXT")?;
+    add_column_if_missing(conn, "evaluations", "uuid", "TEXT")?;
+    sync::ensure_tables(conn)?;
+
+    // Liste sorguları (query_interns) için indeksler
+    conn.execute_batch(
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_interns_sort_name ON interns(last_name, first_name, id);
+        CREATE INDEX IF NOT EXISTS idx_interns_school ON interns(school);
+        CREATE INDEX IF NOT EXISTS idx_interns_department ON interns(department);
+        CREATE INDEX IF NOT EXISTS idx_interns_status ON interns(status);
+        CREATE INDEX IF NOT EXISTS idx_interns_period ON interns(start_date, end_date);
+        CREATE INDEX IF NOT EXISTS idx_interns_period_id ON interns(period_id);
+        "#
+    ).map_err(|e| e.to_string())?;
+
+    search::ensure_search_index(conn)?;
+    views::ensure_tables(conn)?;
+    reminders::ensure_tables(conn)?;
+    certificate::ensure_tables(conn)?;
+    reference::ensure_tables(conn)?;
+
     Ok(())
 }
 
@@ -349,43 +502,44 @@ fn persist_files_to_disk(handle: &AppHandle, conn: &Connection, id: i64, i: &Int
     Ok(())
 }
 
+// Okul / bölüm referans verisine göre kanonik ad (tanımsız değer hata verir)
+fn normalize_reference_fields(conn: &Connection, i: &mut InternPayload) -> Result<(), String> {
+    i.school = reference::resolve(conn, reference::RefKind::School, &i.school)?;
+    i.department = reference::resolve(conn, reference::RefKind::Department, &i.department)?;
+    Ok(())
+}
+
 // --- KOMUTLAR ---
 
 #[tauri::command]
-fn get_interns_from_db(handle: AppHandle) -> Result<Vec<InternLite>, String> {
+fn get_interns_from_db(handle: AppHandle, period_id: Option<i64>) -> Result<Vec<InternLite>, String> {
     let conn = open_conn(&handle)?;
-    let mut stmt = conn.prepare(
-        r#"
-        SELECT id, first_name, last_name, school, department,
-               start_date, end_date, status, contact, email,
-               cv_name, photo_name
-        FROM interns
-        ORDER BY last_name, first_name
-        "#
-    ).map_err(|e| e.to_string())?;
+    list_interns_conn(&conn, period_id)
+}
 
-    let rows = stmt.query_map([], |row| {
-        Ok(InternLite {
-            id: row.get(0)?,
-            first_name: row.get(1)?,
-            last_name: row.get(2)?,
-            school: row.get(3)?,
-            department: row.get(4)?,
-            start_date: row.get(5)?,
-            end_date: row.get(6)?,
-            status: row.get(7)?,
-            contact: row.get(8)?,
-            email: row.get(9)?,
-            cv_name: row.get(10)?,
-            photo_name: row.get(11)?,
-        })
-    }).map_err(|e| e.to_string())?;
+// komutlar ve yerel REST API için ortak
+fn list_interns_conn(conn: &Connection, period_id: Option<i64>) -> Result<Vec<InternLite>, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {INTERN_LITE_COLUMNS} FROM interns i
+         WHERE ?1 IS NULL OR i.period_id = ?1
+         ORDER BY i.last_name, i.first_name"
+    )).map_err(|e| e.to_string())?;
+
+    let rows = stmt.query_map(params![period_id], intern_lite_from_row).map_err(|e| e.to_string())?;
 
     let mut out = Vec::new();
     for r in rows { out.push(r.map_err(|e| e.to_string())?); }
     Ok(out)
 }
 
+fn get_intern_conn(conn: &Connection, id: i64) -> Result<Option<InternLite>, String> {
+    conn.query_row(
+        &format!("SELECT {INTERN_LITE_COLUMNS} FROM interns i WHERE i.id = ?1"),
+        params![id],
+        intern_lite_from_row,
+    ).optional().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn get_intern_files(handle: AppHandle, id: i64) -> Result<InternFiles, String> {
     let conn = open_conn(&handle)?;
@@ -416,8 +570,20 @@ fn get_intern_files(handle: AppHandle, id: i64) -> Result<InternFiles, String> {
 }
 
 #[tauri::command]
-fn add_intern(handle: AppHandle, intern: InternPayload) -> Result<i64, String> {
-    let conn = open_conn(&handle)?;
+fn add_intern(handle: AppHandle, mut intern: InternPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = insert_intern(&handle, &tx, &mut intern)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+// add_intern ve başvuru dönüştürme için ortak kayıt akışı
+fn insert_intern(handle: &AppHandle, conn: &Connection, intern: &mut InternPayload) -> Result<i64, String> {
+    normalize_reference_fields(conn, intern)?;
+    if let Some(period_id) = intern.period_id {
+        periods::check_capacity(conn, period_id, &intern.department, None)?;
+    }
     conn.execute(
         r#"
         INSERT INTO interns
@@ -425,12 +591,14 @@ fn add_intern(handle: AppHandle, intern: InternPayload) -> Result<i64, String> {
          status, contact, email,
          cv_path, photo_path,
          cv_name, cv_mime, cv_blob,
-         photo_name, photo_mime, photo_blob)
+         photo_name, photo_mime, photo_blob,
+         period_id)
         VALUES (?1, ?2, ?3, ?4, ?5, ?6,
                 ?7, ?8, ?9,
                 ?10, ?11,
                 ?12, ?13, ?14,
-                ?15, ?16, ?17)
+                ?15, ?16, ?17,
+                ?18)
         "#,
         params![
             &intern.first_name,
@@ -450,19 +618,32 @@ fn add_intern(handle: AppHandle, intern: InternPayload) -> Result<i64, String> {
             intern.photo_name.as_deref(),       
             intern.photo_mime.as_deref(),       
             intern.photo_blob.as_deref(),       
+            intern.period_id,
         ],
     ).map_err(|e| e.to_string())?;
 
     let new_id = conn.last_insert_rowid();
     // intern'i hâlâ kullanabiliyoruz çünkü hiçbir alanı move etmedik
-    persist_files_to_disk(&handle, &conn, new_id, &intern)?;
+    persist_files_to_disk(handle, conn, new_id, intern)?;
+    events::emit_current(conn, events::INTERN_CREATED, new_id, events::intern_data)?;
     Ok(new_id)
 }
 
 
 #[tauri::command]
-fn update_intern(handle: AppHandle, id: i64, intern: InternPayload) -> Result<(), String> {
-    let conn = open_conn(&handle)?;
+fn update_intern(handle: AppHandle, id: i64, mut intern: InternPayload) -> Result<(), String> {
+    let mut db = open_conn(&handle)?;
+    let conn = db.transaction().map_err(|e| e.to_string())?;
+    save_intern_update(&handle, &conn, id, &mut intern)?;
+    conn.commit().map_err(|e| e.to_string())
+}
+
+// update_intern ve REST API için ortak güncelleme akışı
+fn save_intern_update(handle: &AppHandle, conn: &Connection, id: i64, intern: &mut InternPayload) -> Result<(), String> {
+    normalize_reference_fields(conn, intern)?;
+    if let Some(period_id) = intern.period_id {
+        periods::check_capacity(conn, period_id, &intern.department, Some(id))?;
+    }
 
     let mut sets: Vec<String> = vec![
         "first_name = ?1".to_string(),
@@ -515,6 +696,12 @@ fn update_intern(handle: AppHandle, id: i64, intern: InternPayload) -> Result<()
         vals.push(Box::new(intern.photo_blob.clone()));
     }
 
+    // dönem yalnızca gönderildiyse değişir (çıkarmak için: assign_intern_period)
+    if intern.period_id.is_some() {
+        sets.push(format!("period_id = ?{}", idx)); idx += 1;
+        vals.push(Box::new(intern.period_id));
+    }
+
     let set_clause = sets.join(", ");
     let sql = format!("UPDATE interns SET {} WHERE id = ?{}", set_clause, idx);
     vals.push(Box::new(id));
@@ -523,22 +710,67 @@ fn update_intern(handle: AppHandle, id: i64, intern: InternPayload) -> Result<()
     conn.execute(&sql, params_slice.as_slice()).map_err(|e| e.to_string())?;
 
     // intern burada hâlâ elde: dosyaları diske yaz
-    persist_files_to_disk(&handle, &conn, id, &intern)?;
+    persist_files_to_disk(handle, conn, id, intern)?;
+    events::emit_current(conn, events::INTERN_UPDATED, id, events::intern_data)?;
     Ok(())
 }
 
 
 #[tauri::command]
 fn delete_intern(handle: AppHandle, id: i64) -> Result<(), String> {
-    let conn = open_conn(&handle)?;
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    delete_intern_conn(&tx, id)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// Silinen kayıt yoksa false. Görev ve değerlendirmeler CASCADE'e bırakılmaz;
+// aboneler için her biri kendi silinme olayıyla önce silinir.
+fn delete_intern_conn(conn: &Connection, id: i64) -> Result<bool, String> {
+    let Some(data) = events::intern_data(conn, id)? else { return Ok(false) };
+    for (table, delete) in [
+        ("evaluations", delete_evaluation_conn as fn(&Connection, i64) -> Result<bool, String>),
+        ("assignments", delete_assignment_conn),
+    ] {
+        let mut stmt = conn
+            .prepare(&format!("SELECT id FROM {table} WHERE intern_id = ?1 ORDER BY id"))
+            .map_err(|e| e.to_string())?;
+        let ids = stmt
+            .query_map(params![id], |r| r.get::<_, i64>(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        for child in ids {
+            delete(conn, child)?;
+        }
+    }
     conn.execute("DELETE FROM interns WHERE id = ?1", params![id])
         .map_err(|e| e.to_string())?;
+    events::emit(conn, events::INTERN_DELETED, id, data)?;
+    Ok(true)
+}
+
+fn validate_assignment(a: &Assignment) -> Result<(), String> {
+    if a.project_type.trim().is_empty() || a.task_description.trim().is_empty() {
+        return Err("Proje türü ve görev açıklaması boş olamaz".to_string());
+    }
+    if a.due_date.trim().is_empty() || a.status.trim().is_empty() {
+        return Err("Teslim tarihi ve durum gerekli".to_string());
+    }
     Ok(())
 }
 
 #[tauri::command]
 fn add_assignment(handle: AppHandle, a: Assignment) -> Result<i64, String> {
-    let conn = open_conn(&handle)?;
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = insert_assignment(&tx, &a)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+fn insert_assignment(conn: &Connection, a: &Assignment) -> Result<i64, String> {
+    validate_assignment(a)?;
     conn.execute(
         r#"
         INSERT INTO assignments
@@ -547,21 +779,58 @@ fn add_assignment(handle: AppHandle, a: Assignment) -> Result<i64, String> {
         "#,
         params![a.intern_id, a.project_type, a.task_description, a.due_date, a.status, a.file_path],
     ).map_err(|e| e.to_string())?;
-    Ok(conn.last_insert_rowid())
+    let id = conn.last_insert_rowid();
+    events::emit_current(conn, events::ASSIGNMENT_CREATED, id, events::assignment_data)?;
+    Ok(id)
+}
+
+// Tamamlanmaya geçişte ayrıca assignment.completed yayınlanır
+#[tauri::command]
+fn update_assignment_status(handle: AppHandle, id: i64, status: String) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    set_assignment_status(&tx, id, &status)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn set_assignment_status(conn: &Connection, id: i64, status: &str) -> Result<(), String> {
+    if status.trim().is_empty() {
+        return Err("Durum boş olamaz".to_string());
+    }
+    let old: String = conn
+        .query_row("SELECT status FROM assignments WHERE id = ?1", params![id], |r| r.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Görev bulunamadı".to_string())?;
+    if old == status {
+        return Ok(());
+    }
+    conn.execute("UPDATE assignments SET status = ?1 WHERE id = ?2", params![status, id])
+        .map_err(|e| e.to_string())?;
+    events::emit_current(conn, events::ASSIGNMENT_UPDATED, id, events::assignment_data)?;
+    if status == "Completed" {
+        events::emit_current(conn, events::ASSIGNMENT_COMPLETED, id, events::assignment_data)?;
+    }
+    Ok(())
 }
 
 #[tauri::command]
 fn get_assignments(handle: AppHandle) -> Result<Vec<Assignment>, String> {
     let conn = open_conn(&handle)?;
+    list_assignments_conn(&conn, None)
+}
+
+fn list_assignments_conn(conn: &Connection, intern_id: Option<i64>) -> Result<Vec<Assignment>, String> {
     let mut stmt = conn.prepare(
         r#"
-        SELECT id, intern_id, project_type, task_description, due_date, status, file_path, created_at
+        SELECT id, intern_id, project_type, task_description, due_date, status, file_path, created_at, uuid
         FROM assignments
+        WHERE ?1 IS NULL OR intern_id = ?1
         ORDER BY due_date ASC, id DESC
         "#
     ).map_err(|e| e.to_string())?;
 
-    let rows = stmt.query_map([], |row| {
+    let rows = stmt.query_map(params![intern_id], |row| {
         Ok(Assignment {
             id: row.get(0)?,
             intern_id: row.get(1)?,
@@ -571,6 +840,7 @@ fn get_assignments(handle: AppHandle) -> Result<Vec<Assignment>, String> {
             status: row.get(5)?,
             file_path: row.get(6)?,
             created_at: row.get(7)?,
+            uuid: row.get(8)?,
         })
     }).map_err(|e| e.to_string())?;
 
@@ -581,34 +851,65 @@ fn get_assignments(handle: AppHandle) -> Result<Vec<Assignment>, String> {
 
 #[tauri::command]
 fn delete_assignment(handle: AppHandle, id: i64) -> Result<(), String> {
-    let conn = open_conn(&handle)?;
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    delete_assignment_conn(&tx, id)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn delete_assignment_conn(conn: &Connection, id: i64) -> Result<bool, String> {
+    let Some(data) = events::assignment_data(conn, id)? else { return Ok(false) };
     conn.execute("DELETE FROM assignments WHERE id = ?1", params![id])
         .map_err(|e| e.to_string())?;
-    Ok(())
+    events::emit(conn, events::ASSIGNMENT_DELETED, id, data)?;
+    Ok(true)
 }
 
 #[tauri::command]
 fn add_evaluation(handle: AppHandle, e: Evaluation) -> Result<i64, String> {
-    let conn = open_conn(&handle)?;
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|er| er.to_string())?;
+    let id = insert_evaluation(&tx, &e)?;
+    tx.commit().map_err(|er| er.to_string())?;
+    Ok(id)
+}
+
+fn insert_evaluation(conn: &Connection, e: &Evaluation) -> Result<i64, String> {
+    if e.label.trim().is_empty() {
+        return Err("Değerlendirme etiketi boş olamaz".to_string());
+    }
+    if !(0..=100).contains(&e.score) {
+        return Err("Puan 0 ile 100 arasında olmalı".to_string());
+    }
+    if let Some(mentor_id) = e.evaluator_id {
+        mentors::ensure_mentor_exists(conn, mentor_id)?;
+    }
     conn.execute(
         r#"
-        INSERT INTO evaluations (intern_id, label, score)
-        VALUES (?1, ?2, ?3)
+        INSERT INTO evaluations (intern_id, label, score, evaluator_id)
+        VALUES (?1, ?2, ?3, ?4)
         "#,
-        params![e.intern_id, e.label, e.score],
+        params![e.intern_id, e.label, e.score, e.evaluator_id],
     ).map_err(|er| er.to_string())?;
-    Ok(conn.last_insert_rowid())
+    let id = conn.last_insert_rowid();
+    events::emit_current(conn, events::EVALUATION_CREATED, id, events::evaluation_data)?;
+    Ok(id)
 }
 
 #[tauri::command]
 fn get_evaluations(handle: AppHandle, intern_id: i64) -> Result<Vec<Evaluation>, String> {
     let conn = open_conn(&handle)?;
+    list_evaluations_conn(&conn, intern_id)
+}
+
+fn list_evaluations_conn(conn: &Connection, intern_id: i64) -> Result<Vec<Evaluation>, String> {
     let mut stmt = conn.prepare(
         r#"
-        SELECT id, intern_id, label, score, created_at
-        FROM evaluations
-        WHERE intern_id = ?1
-        ORDER BY created_at DESC, id DESC
+        SELECT e.id, e.intern_id, e.label, e.score, e.created_at, e.evaluator_id, m.name, e.uuid
+        FROM evaluations e
+        LEFT JOIN mentors m ON m.id = e.evaluator_id
+        WHERE e.intern_id = ?1
+        ORDER BY e.created_at DESC, e.id DESC
         "#
     ).map_err(|er| er.to_string())?;
 
@@ -619,6 +920,9 @@ fn get_evaluations(handle: AppHandle, intern_id: i64) -> Result<Vec<Evaluation>,
             label: row.get(2)?,
             score: row.get(3)?,
             created_at: row.get(4)?,
+            evaluator_id: row.get(5)?,
+            evaluator_name: row.get(6)?,
+            uuid: row.get(7)?,
         })
     }).map_err(|er| er.to_string())?;
 
@@ -629,10 +933,18 @@ fn get_evaluations(handle: AppHandle, intern_id: i64) -> Result<Vec<Evaluation>,
 
 #[tauri::command]
 fn delete_evaluation(handle: AppHandle, id: i64) -> Result<(), String> {
-    let conn = open_conn(&handle)?;
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|er| er.to_string())?;
+    delete_evaluation_conn(&tx, id)?;
+    tx.commit().map_err(|er| er.to_string())
+}
+
+fn delete_evaluation_conn(conn: &Connection, id: i64) -> Result<bool, String> {
+    let Some(data) = events::evaluation_data(conn, id)? else { return Ok(false) };
     conn.execute("DELETE FROM evaluations WHERE id = ?1", params![id])
         .map_err(|er| er.to_string())?;
-    Ok(())
+    events::emit(conn, events::EVALUATION_DELETED, id, data)?;
+    Ok(true)
 }
 
 #[tauri::command]
@@ -669,19 +981,99 @@ fn debug_db_snapshot(handle: AppHandle) -> Result<(String, i64), String> {
     Ok((db_path.to_string_lossy().to_string(), count))
 }
 
+#[derive(Debug, Serialize)]
+struct MissingEvaluation {
+    intern: InternLite,
+    // not girilmemiş iş günleri (YYYY-MM-DD)
+    missing_dates: Vec<String>,
+}
+
+// [from, to] aralığında stajı devam eden ve iş günlerinde not girilmemiş stajyerler
+fn interns_missing_evaluation_conn(
+    conn: &Connection,
+    cal: &calendar::WorkCalendar,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<MissingEvaluation>, String> {
+    if to < from {
+        return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+    }
+    if (to - from).num_days() > 366 {
+        return Err("En fazla bir yıllık aralık sorgulanabilir".to_string());
+    }
+    let days = cal.working_days(from, to);
+    if days.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (from_s, to_s) = (calendar::format_date(from), calendar::format_date(to));
+
+    let filter = query::InternFilter {
+        period_from: Some(from_s.clone()),
+        period_to: Some(to_s.clone()),
+        ..Default::default()
+    };
+    let (where_sql, vals) = query::filter_clause(&filter);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {INTERN_LITE_COLUMNS} FROM interns i WHERE {where_sql} ORDER BY i.last_name, i.first_name"
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(vals.iter()), intern_lite_from_row)
+        .map_err(|e| e.to_string())?;
+    let mut interns = Vec::new();
+    for r in rows { interns.push(r.map_err(|e| e.to_string())?); }
+
+    let mut evaluated: HashSet<(i64, String)> = HashSet::new();
+    let mut stmt = conn.prepare(r#"
+        SELECT DISTINCT intern_id, date(created_at)
+        FROM evaluations
+        WHERE date(created_at) BETWEEN ?1 AND ?2
+    "#).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![from_s, to_s], |r| Ok((r.get(0)?, r.get(1)?)))
+        .map_err(|e| e.to_string())?;
+    for r in rows { evaluated.insert(r.map_err(|e| e.to_string())?); }
+
+    let mut out = Vec::new();
+    for intern in interns {
+        let id = intern.id.unwrap_or_default();
+        let start = calendar::parse_date(&intern.start_date).unwrap_or(from);
+        let end = intern
+            .end_date
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+            .and_then(|d| calendar::parse_date(d).ok())
+            .unwrap_or(to);
+        let missing_dates: Vec<String> = days
+            .iter()
+            .filter(|d| **d >= start && **d <= end)
+            .map(|d| calendar::format_date(*d))
+            .filter(|d| !evaluated.contains(&(id, d.clone())))
+            .collect();
+        if !missing_dates.is_empty() {
+            out.push(MissingEvaluation { intern, missing_dates });
+        }
+    }
+    Ok(out)
+}
+
+#[tauri::command]
+fn interns_missing_evaluation(handle: AppHandle, from: String, to: Option<String>) -> Result<Vec<MissingEvaluation>, String> {
+    let conn = open_conn(&handle)?;
+    let cal = calendar::WorkCalendar::load(&conn)?;
+    let from = calendar::parse_date(&from)?;
+    let to = match to.as_deref() {
+        Some(t) if !t.trim().is_empty() => calendar::parse_date(t)?,
+        _ => from,
+    };
+    interns_missing_evaluation_conn(&conn, &cal, from, to)
+}
+
 #[tauri::command]
 fn count_interns_missing_note_for_date(handle: AppHandle, date: String) -> Result<i64, String> {
     let conn = open_conn(&handle)?;
-    let mut stmt = conn.prepare(r#"
-        SELECT COUNT(*)
-        FROM interns i
-        LEFT JOIN evaluations e
-          ON e.intern_id = i.id
-         AND date(e.created_at) = date(?1)
-        WHERE e.id IS NULL
-    "#).map_err(|e| e.to_string())?;
-    let count: i64 = stmt.query_row([date], |r| r.get(0)).map_err(|e| e.to_string())?;
-    Ok(count)
+    let cal = calendar::WorkCalendar::load(&conn)?;
+    let day = calendar::parse_date(&date)?;
+    Ok(interns_missing_evaluation_conn(&conn, &cal, day, day)?.len() as i64)
 }
 
 fn main() {
@@ -703,28 +1095,208 @@ fn main() {
                 .build();
             app.handle().plugin(plugin)?; 
 
+            // son tarih hatırlatıcıları (arka plan)
+            reminders::start_scheduler(app.handle().clone());
+            webhooks::start_dispatcher(app.handle().clone());
+            api::start_from_settings(app.handle());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             // interns
             get_interns_from_db,
+            query::query_interns,
             get_intern_files,
             add_intern,
             update_intern,
             delete_intern,
+            // staj dönemleri
+            periods::create_period,
+            periods::list_periods,
+            periods::update_period,
+            periods::delete_period,
+            periods::assign_intern_period,
+            periods::export_period_interns,
+            // okul / bölüm referans verisi
+            reference::list_reference,
+            reference::create_reference,
+            reference::update_reference,
+            reference::delete_reference,
+            reference::seed_reference,
+            reference::suggest_reference_merges,
+            reference::merge_reference,
             // assignments
             add_assignment,
+            update_assignment_status,
             get_assignments,
             delete_assignment,
+            // mentorlar
+            mentors::create_mentor,
+            mentors::list_mentors,
+            mentors::update_mentor,
+            mentors::delete_mentor,
+            mentors::assign_mentor,
+            mentors::unassign_mentor,
+            mentors::get_intern_mentors,
+            mentors::get_mentor_interns,
+            mentors::mentors_with_capacity,
+            // devam takibi
+            attendance::record_attendance,
+            attendance::record_department_attendance,
+            attendance::delete_attendance,
+            attendance::get_attendance,
+            attendance::get_daily_attendance,
+            attendance::attendance_summary,
+            attendance::attendance_warnings,
+            attendance::set_required_days,
+            attendance::get_attendance_settings,
+            attendance::update_attendance_settings,
+            // izinler
+            leaves::list_leave_types,
+            leaves::create_leave_type,
+            leaves::update_leave_type,
+            leaves::delete_leave_type,
+            leaves::request_leave,
+            leaves::approve_leave,
+            leaves::reject_leave,
+            leaves::cancel_leave,
+            leaves::list_leaves,
+            leaves::suggest_leave_extension,
+            leaves::get_leave_settings,
+            leaves::update_leave_settings,
+            // staj defteri
+            journal::add_journal_entry,
+            journal::update_journal_entry,
+            journal::delete_journal_entry,
+            journal::delete_journal_attachment,
+            journal::list_journal_entries,
+            journal::approve_journal_entry,
+            journal::reject_journal_entry,
+            journal::reopen_journal_entry,
+            journal::export_journal,
+            // başvurular
+            applications::create_applicant,
+            applications::update_applicant,
+            applications::delete_applicant,
+            applications::list_applicants,
+            applications::move_applicant_stage,
+            applications::add_applicant_note,
+            applications::get_applicant_notes,
+            applications::get_intern_application,
+            applications::convert_applicant_to_intern,
+            // mülakatlar
+            interviews::create_interview,
+            interviews::reschedule_interview,
+            interviews::cancel_interview,
+            interviews::set_interview_outcome,
+            interviews::get_interview,
+            interviews::list_interviews,
+            interviews::check_interview_conflicts,
+            interviews::generate_interview_invitation,
+            // takvim dışa aktarımı
+            ics::export_calendar,
+            // kişi kartları (vCard)
+            vcard::export_vcards,
+            vcard::import_vcards,
+            // e-posta
+            mail::get_smtp_settings,
+            mail::update_smtp_settings,
+            mail::has_smtp_password,
+            mail::send_test_email,
+            mail::list_outbox,
+            mail::retry_outbox_email,
+            mail::delete_outbox_email,
+            mail::run_email_now,
+            // mesaj şablonları
+            templates::list_message_templates,
+            templates::get_message_template,
+            templates::save_message_template,
+            templates::delete_message_template,
+            templates::reset_message_template,
+            templates::validate_message_template,
+            templates::preview_message_template,
+            templates::list_template_placeholders,
+            templates::get_template_settings,
+            templates::update_template_settings,
+            // webhook'lar
+            events::list_domain_events,
+            webhooks::list_webhooks,
+            webhooks::create_webhook,
+            webhooks::update_webhook,
+            webhooks::delete_webhook,
+            webhooks::rotate_webhook_secret,
+            webhooks::send_test_webhook,
+            webhooks::list_webhook_deliveries,
+            webhooks::get_webhook_delivery_attempts,
+            webhooks::retry_webhook_delivery,
+            webhooks::list_webhook_event_types,
+            webhooks::get_webhook_settings,
+            webhooks::update_webhook_settings,
+            webhooks::run_webhooks_now,
+            webhooks::get_webhook_dispatcher_status,
+            // yerel REST API
+            api::get_api_settings,
+            api::update_api_settings,
+            api::get_api_status,
+            api::create_api_token,
+            api::list_api_tokens,
+            api::revoke_api_token,
+            // kurulumlar arası eşitleme
+            sync::get_sync_settings,
+            sync::update_sync_settings,
+            sync::export_sync_changes,
+            sync::import_sync_changes,
+            sync::sync_now,
+            sync::list_sync_peers,
+            sync::list_sync_conflicts,
+            sync::resolve_sync_conflict,
             // evaluations
             add_evaluation,
             get_evaluations,
             delete_evaluation,
+            // performans analizi
+            analytics::intern_score_series,
+            analytics::score_distribution,
+            analytics::score_trend_chart,
+            analytics::improvement_report,
+            // arama
+            search::search,
+            search::rebuild_search_index,
+            // kaydedilmiş görünümler
+            views::create_view,
+            views::list_views,
+            views::update_view,
+            views::delete_view,
+            views::run_view,
+            // hatırlatıcılar
+            reminders::get_reminder_settings,
+            reminders::update_reminder_settings,
+            reminders::run_reminders_now,
+            reminders::get_reminder_scheduler_status,
+            // anasayfa
+            dashboard::dashboard_summary,
+            // sertifikalar
+            certificate::get_certificate_template,
+            certificate::update_certificate_template,
+            certificate::generate_certificate,
+            certificate::generate_certificates_for_period,
+            certificate::verify_certificate,
+            // raporlar
+            report::generate_intern_report,
             // utils
             export_database,
+            archive::export_archive,
+            archive::inspect_archive,
+            archive::import_archive,
             save_file,
             debug_db_snapshot,
-            count_interns_missing_note_for_date
+            count_interns_missing_note_for_date,
+            interns_missing_evaluation,
+            // iş günü takvimi
+            calendar::get_calendar_settings,
+            calendar::update_calendar_settings,
+            calendar::list_holidays,
+            calendar::count_working_days
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
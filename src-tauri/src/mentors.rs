@@ -0,0 +1,431 @@
+// Mentorlar ve stajyer–mentor ataması. Bir stajyerin aynı tarihlerde tek bir
+// birincil (primary) mentoru olabilir; ikincil mentor sayısı serbesttir.
+// Mentor kontenjanı, aynı tarih aralığında süren atamalara göre denetlenir.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date};
+use crate::{events, open_conn};
+
+// açık uçlu atamalar için karşılaştırma sınırı
+pub(crate) const OPEN_END: &str = "9999-12-31";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MentorRole {
+    Primary,
+    Secondary,
+}
+
+impl MentorRole {
+    fn as_str(self) -> &'static str {
+        match self {
+            MentorRole::Primary => "primary",
+            MentorRole::Secondary => "secondary",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Mentor {
+    pub id: i64,
+    pub name: String,
+    pub department: String,
+    pub email: String,
+    pub capacity: i64,
+    pub active: bool,
+    // bugün süren atama sayısı
+    pub current_load: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MentorPayload {
+    pub name: String,
+    pub department: String,
+    #[serde(default)]
+    pub email: String,
+    pub capacity: i64,
+    #[serde(default = "default_true")]
+    pub active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct MentorAssignment {
+    pub id: i64,
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub mentor_id: i64,
+    pub mentor_name: String,
+    pub role: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignMentorPayload {
+    pub intern_id: i64,
+    pub mentor_id: i64,
+    pub role: MentorRole,
+    // varsayılan: stajyerin başlangıç tarihi
+    pub start_date: Option<String>,
+    // varsayılan: stajyerin bitiş tarihi (yoksa açık uçlu)
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MentorAvailability {
+    pub mentor: Mentor,
+    // verilen tarih aralığında süren atamalar
+    pub load: i64,
+    pub free: i64,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS mentors (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            department TEXT NOT NULL,
+            email TEXT NOT NULL DEFAULT '',
+            capacity INTEGER NOT NULL DEFAULT 3 CHECK (capacity >= 0),
+            active INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        CREATE INDEX IF NOT EXISTS idx_mentors_department ON mentors(department);
+
+        CREATE TABLE IF NOT EXISTS intern_mentors (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            intern_id INTEGER NOT NULL,
+            mentor_id INTEGER NOT NULL,
+            role TEXT NOT NULL CHECK (role IN ('primary', 'secondary')),
+            start_date TEXT NOT NULL,
+            end_date TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (intern_id) REFERENCES interns(id) ON DELETE CASCADE,
+            FOREIGN KEY (mentor_id) REFERENCES mentors(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_intern_mentors_intern ON intern_mentors(intern_id);
+        CREATE INDEX IF NOT EXISTS idx_intern_mentors_mentor ON intern_mentors(mentor_id);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+// [from, to] aralığında aynı anda süren en fazla atama (to = None: açık uçlu).
+// Yük yalnızca bir atama başladığında artar; aralık başı ve aralıktaki
+// başlangıç günleri denenir; art arda gelen stajyerler birlikte sayılmaz.
+fn overlapping_load(conn: &Connection, mentor_id: i64, from: &str, to: Option<&str>) -> Result<i64, String> {
+    conn.query_row(
+        r#"
+        SELECT MAX((
+            SELECT COUNT(*) FROM intern_mentors x
+            WHERE x.mentor_id = ?1
+              AND x.start_date <= p.day
+              AND COALESCE(NULLIF(x.end_date, ''), ?4) >= p.day
+        ))
+        FROM (
+            SELECT ?2 AS day
+            UNION
+            SELECT start_date FROM intern_mentors
+            WHERE mentor_id = ?1 AND start_date > ?2 AND start_date <= ?3
+        ) p
+        "#,
+        params![mentor_id, from, to.unwrap_or(OPEN_END), OPEN_END],
+        |r| r.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn mentor_from_row(r: &rusqlite::Row) -> rusqlite::Result<Mentor> {
+    Ok(Mentor {
+        id: r.get(0)?,
+        name: r.get(1)?,
+        department: r.get(2)?,
+        email: r.get(3)?,
+        capacity: r.get(4)?,
+        active: r.get(5)?,
+        current_load: r.get(6)?,
+    })
+}
+
+const MENTOR_COLUMNS: &str = "m.id, m.name, m.department, m.email, m.capacity, m.active,
+    (SELECT COUNT(*) FROM intern_mentors x
+     WHERE x.mentor_id = m.id AND x.start_date <= ?1
+       AND COALESCE(NULLIF(x.end_date, ''), '9999-12-31') >= ?1)";
+
+pub(crate) fn list_mentors_conn(conn: &Connection, today: &str) -> Result<Vec<Mentor>, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {MENTOR_COLUMNS} FROM mentors m ORDER BY m.active DESC, m.name"
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![today], mentor_from_row).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+/// Verilen aralıkta boş kontenjanı olan aktif mentorlar (en boş olan önce).
+pub(crate) fn available_mentors_conn(
+    conn: &Connection,
+    department: Option<&str>,
+    from: &str,
+    to: Option<&str>,
+) -> Result<Vec<MentorAvailability>, String> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {MENTOR_COLUMNS} FROM mentors m
+         WHERE m.active = 1 AND (?2 IS NULL OR m.department = ?2)
+         ORDER BY m.name"
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![from, department], mentor_from_row).map_err(|e| e.to_string())?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        let mentor = r.map_err(|e| e.to_string())?;
+        let load = overlapping_load(conn, mentor.id, from, to)?;
+        let free = mentor.capacity - load;
+        if free > 0 {
+            out.push(MentorAvailability { mentor, load, free });
+        }
+    }
+    out.sort_by(|a, b| b.free.cmp(&a.free).then(a.load.cmp(&b.load)));
+    Ok(out)
+}
+
+pub(crate) fn ensure_mentor_exists(conn: &Connection, mentor_id: i64) -> Result<(), String> {
+    let exists: bool = conn
+        .query_row("SELECT EXISTS(SELECT 1 FROM mentors WHERE id = ?1)", params![mentor_id], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+    if exists { Ok(()) } else { Err("Mentor bulunamadı".to_string()) }
+}
+
+pub(crate) fn assign_conn(conn: &Connection, p: &AssignMentorPayload) -> Result<i64, String> {
+    let (intern_start, intern_end): (String, Option<String>) = conn
+        .query_row("SELECT start_date, end_date FROM interns WHERE id = ?1", params![p.intern_id], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+
+    let start = format_date(parse_date(p.start_date.as_deref().filter(|s| !s.trim().is_empty()).unwrap_or(&intern_start))?);
+    let end = match p.end_date.as_deref().or(intern_end.as_deref()).filter(|s| !s.trim().is_empty()) {
+        Some(e) => Some(format_date(parse_date(e)?)),
+        None => None,
+    };
+    if end.as_deref().is_some_and(|e| e < start.as_str()) {
+        return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+    }
+
+    let (name, capacity, active): (String, i64, bool) = conn
+        .query_row("SELECT name, capacity, active FROM mentors WHERE id = ?1", params![p.mentor_id], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Mentor bulunamadı".to_string())?;
+    if !active {
+        return Err(format!("{name} aktif değil"));
+    }
+
+    let duplicate: bool = conn.query_row(
+        r#"
+        SELECT EXISTS(SELECT 1 FROM intern_mentors
+        WHERE intern_id = ?1 AND mentor_id = ?2
+          AND start_date <= ?4 AND COALESCE(NULLIF(end_date, ''), ?5) >= ?3)
+        "#,
+        params![p.intern_id, p.mentor_id, start, end.as_deref().unwrap_or(OPEN_END), OPEN_END],
+        |r| r.get(0),
+    ).map_err(|e| e.to_string())?;
+    if duplicate {
+        return Err(format!("{name} bu tarihlerde zaten stajyere atanmış"));
+    }
+
+    if p.role == MentorRole::Primary {
+        let other: Option<String> = conn.query_row(
+            r#"
+            SELECT m.name FROM intern_mentors x JOIN mentors m ON m.id = x.mentor_id
+            WHERE x.intern_id = ?1 AND x.role = 'primary'
+              AND x.start_date <= ?3 AND COALESCE(NULLIF(x.end_date, ''), ?4) >= ?2
+            LIMIT 1
+            "#,
+            params![p.intern_id, start, end.as_deref().unwrap_or(OPEN_END), OPEN_END],
+            |r| r.get(0),
+        ).optional().map_err(|e| e.to_string())?;
+        if let Some(other) = other {
+            return Err(format!("Stajyerin bu tarihlerde birincil mentoru var: {other}"));
+        }
+    }
+
+    let load = overlapping_load(conn, p.mentor_id, &start, end.as_deref())?;
+    if load >= capacity {
+        return Err(format!("{name} kontenjanı dolu ({load}/{capacity})"));
+    }
+
+    conn.execute(
+        "INSERT INTO intern_mentors (intern_id, mentor_id, role, start_date, end_date) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![p.intern_id, p.mentor_id, p.role.as_str(), start, end],
+    ).map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    events::emit_current(conn, events::MENTOR_ASSIGNED, id, events::mentor_assignment_data)?;
+    Ok(id)
+}
+
+fn assignments_where(conn: &Connection, where_sql: &str, id: i64) -> Result<Vec<MentorAssignment>, String> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT x.id, x.intern_id, i.first_name || ' ' || i.last_name,
+               x.mentor_id, m.name, x.role, x.start_date, x.end_date
+        FROM intern_mentors x
+        JOIN interns i ON i.id = x.intern_id
+        JOIN mentors m ON m.id = x.mentor_id
+        WHERE {where_sql}
+        ORDER BY x.start_date DESC, x.role, x.id
+        "#
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![id], |r| {
+        Ok(MentorAssignment {
+            id: r.get(0)?,
+            intern_id: r.get(1)?,
+            intern_name: r.get(2)?,
+            mentor_id: r.get(3)?,
+            mentor_name: r.get(4)?,
+            role: r.get(5)?,
+            start_date: r.get(6)?,
+            end_date: r.get(7)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+fn validate(m: &MentorPayload) -> Result<(), String> {
+    if m.name.trim().is_empty() || m.department.trim().is_empty() {
+        return Err("Mentor adı ve bölümü boş olamaz".to_string());
+    }
+    if m.capacity < 0 {
+        return Err("Kontenjan negatif olamaz".to_string());
+    }
+    Ok(())
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+#[tauri::command]
+pub fn create_mentor(handle: AppHandle, mentor: MentorPayload) -> Result<i64, String> {
+    validate(&mentor)?;
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        "INSERT INTO mentors (name, department, email, capacity, active) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![mentor.name.trim(), mentor.department.trim(), mentor.email.trim(), mentor.capacity, mentor.active],
+    ).map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn list_mentors(handle: AppHandle) -> Result<Vec<Mentor>, String> {
+    let conn = open_conn(&handle)?;
+    list_mentors_conn(&conn, &today())
+}
+
+#[tauri::command]
+pub fn update_mentor(handle: AppHandle, id: i64, mentor: MentorPayload) -> Result<(), String> {
+    validate(&mentor)?;
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        "UPDATE mentors SET name = ?1, department = ?2, email = ?3, capacity = ?4, active = ?5 WHERE id = ?6",
+        params![mentor.name.trim(), mentor.department.trim(), mentor.email.trim(), mentor.capacity, mentor.active, id],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Mentor bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+// Atamalar da silinir; değerlendirmelerdeki değerlendiren alanı boşalır
+#[tauri::command]
+pub fn delete_mentor(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    conn.execute("DELETE FROM mentors WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn assign_mentor(handle: AppHandle, assignment: AssignMentorPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    // kontenjan denetimi ve kayıt birlikte; eşzamanlı atamalar sınırı aşamaz
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = assign_conn(&tx, &assignment)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+// end_date verilirse atama o tarihte sonlandırılır (geçmiş korunur), yoksa silinir
+#[tauri::command]
+pub fn unassign_mentor(handle: AppHandle, id: i64, end_date: Option<String>) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let not_found = || "Atama bulunamadı veya bitiş tarihi başlangıçtan önce".to_string();
+    match end_date.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(d) => {
+            let d = format_date(parse_date(d)?);
+            let n = tx.execute(
+                "UPDATE intern_mentors SET end_date = ?1 WHERE id = ?2 AND start_date <= ?1",
+                params![d, id],
+            ).map_err(|e| e.to_string())?;
+            if n == 0 {
+                return Err(not_found());
+            }
+            events::emit_current(&tx, events::MENTOR_UNASSIGNED, id, events::mentor_assignment_data)?;
+        }
+        None => {
+            let data = events::mentor_assignment_data(&tx, id)?.ok_or_else(not_found)?;
+            tx.execute("DELETE FROM intern_mentors WHERE id = ?1", params![id])
+                .map_err(|e| e.to_string())?;
+            events::emit(&tx, events::MENTOR_UNASSIGNED, id, data)?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_intern_mentors(handle: AppHandle, intern_id: i64) -> Result<Vec<MentorAssignment>, String> {
+    let conn = open_conn(&handle)?;
+    assignments_where(&conn, "x.intern_id = ?1", intern_id)
+}
+
+#[tauri::command]
+pub fn get_mentor_interns(handle: AppHandle, mentor_id: i64) -> Result<Vec<MentorAssignment>, String> {
+    let conn = open_conn(&handle)?;
+    assignments_where(&conn, "x.mentor_id = ?1", mentor_id)
+}
+
+// from varsayılanı bugün; to = None: açık uçlu
+#[tauri::command]
+pub fn mentors_with_capacity(
+    handle: AppHandle,
+    department: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<MentorAvailability>, String> {
+    let conn = open_conn(&handle)?;
+    let from = match from.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(d) => format_date(parse_date(d)?),
+        None => today(),
+    };
+    let to = match to.as_deref().filter(|d| !d.trim().is_empty()) {
+        Some(d) => Some(format_date(parse_date(d)?)),
+        None => None,
+    };
+    let department = department.as_deref().map(str::trim).filter(|d| !d.is_empty());
+    available_mentors_conn(&conn, department, &from, to.as_deref())
+}
@@ -0,0 +1,674 @@
+// Küçük PDF yazıcı: standart Helvetica yazı tipleri (gömülü font yok),
+// çizgi / dikdörtgen ve JPEG / PNG görseller. Türkçe karakterler için
+// WinAnsi kodlaması Windows-1254 (Latin-5) konumlarıyla genişletilir.
+
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+pub(crate) const A4_PORTRAIT: (f32, f32) = (595.28, 841.89);
+pub(crate) const A4_LANDSCAPE: (f32, f32) = (841.89, 595.28);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Font {
+    Regular,
+    Bold,
+    Italic,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rgb(pub f32, pub f32, pub f32);
+
+pub(crate) const BLACK: Rgb = Rgb(0.0, 0.0, 0.0);
+pub(crate) const GRAY: Rgb = Rgb(0.45, 0.45, 0.45);
+pub(crate) const LIGHT_GRAY: Rgb = Rgb(0.9, 0.9, 0.9);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Style {
+    pub size: f32,
+    pub font: Font,
+    pub color: Rgb,
+    // satır aralığı (paragraflar için)
+    pub leading: f32,
+}
+
+impl Style {
+    pub fn new(size: f32, font: Font) -> Self {
+        Style { size, font, color: BLACK, leading: size * 1.4 }
+    }
+
+    pub fn color(mut self, color: Rgb) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ImageRef {
+    index: usize,
+    pub width: u32,
+    pub height: u32,
+}
+
+struct ImageObject {
+    width: u32,
+    height: u32,
+    color_space: &'static str,
+    bits: u8,
+    filter: &'static str,
+    data: Vec<u8>,
+    // PNG alfa kanalı (FlateDecode, DeviceGray)
+    alpha: Option<Vec<u8>>,
+}
+
+// Helvetica ve Helvetica-Bold genişlikleri (1/1000 em), ASCII 32..=126
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+// Windows-1254: WinAnsi ile aynı, yalnızca altı konum Türkçe harflere ayrılmış
+const TR_DIFFERENCES: &str =
+    "[208 /Gbreve 221 /Idotaccent 222 /Scedilla 240 /gbreve 253 /dotlessi 254 /scedilla]";
+
+fn encode_char(ch: char) -> u8 {
+    match ch {
+        'Ğ' => 0xD0,
+        'İ' => 0xDD,
+        'Ş' => 0xDE,
+        'ğ' => 0xF0,
+        'ı' => 0xFD,
+        'ş' => 0xFE,
+        '€' => 0x80,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' | '·' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        // bu konumlar Türkçe harflerle değiştirildi
+        'Ð' | 'Ý' | 'Þ' | 'ð' | 'ý' | 'þ' => b'?',
+        c if (c as u32) < 0x80 && (c as u32) >= 0x20 => c as u8,
+        c if (0xA0..=0xFF).contains(&(c as u32)) => c as u32 as u8,
+        '\t' => b' ',
+        _ => b'?',
+    }
+}
+
+// genişlik hesabı için aksanlı harfin ASCII karşılığı
+fn width_base(ch: char) -> char {
+    match ch {
+        'Ç' => 'C',
+        'ç' => 'c',
+        'Ğ' => 'G',
+        'ğ' => 'g',
+        'İ' | 'Î' | 'Í' | 'Ì' | 'Ï' => 'I',
+        'ı' | 'î' | 'í' | 'ì' | 'ï' => 'i',
+        'Ö' | 'Ó' | 'Ò' | 'Ô' => 'O',
+        'ö' | 'ó' | 'ò' | 'ô' => 'o',
+        'Ş' => 'S',
+        'ş' => 's',
+        'Ü' | 'Ú' | 'Ù' | 'Û' => 'U',
+        'ü' | 'ú' | 'ù' | 'û' => 'u',
+        'Â' | 'Á' | 'À' | 'Ä' => 'A',
+        'â' | 'á' | 'à' | 'ä' => 'a',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        '–' | '·' | '•' => '-',
+        '—' => 'M',
+        '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        c => c,
+    }
+}
+
+pub(crate) fn text_width(text: &str, size: f32, font: Font) -> f32 {
+    let table = match font {
+        Font::Bold => &HELVETICA_BOLD_WIDTHS,
+        _ => &HELVETICA_WIDTHS,
+    };
+    let units: u32 = text
+        .chars()
+        .map(|c| {
+            if c == '…' {
+                return 1000;
+            }
+            let b = width_base(c) as u32;
+            if (32..=126).contains(&b) { table[(b - 32) as usize] as u32 } else { 556 }
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+// Kelime kaydırma: her satır max_width'i aşmayacak şekilde
+pub(crate) fn wrap_text(text: &str, size: f32, font: Font, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{line} {word}") };
+            if text_width(&candidate, size, font) <= max_width || line.is_empty() {
+                line = candidate;
+            } else {
+                lines.push(std::mem::take(&mut line));
+                line = word.to_string();
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for ch in text.chars() {
+        match encode_char(ch) {
+            b'(' => out.extend_from_slice(b"\\("),
+            b')' => out.extend_from_slice(b"\\)"),
+            b'\\' => out.extend_from_slice(b"\\\\"),
+            b => out.push(b),
+        }
+    }
+    out.push(b')');
+    out
+}
+
+fn fmt(n: f32) -> String {
+    let s = format!("{:.2}", n);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+#[derive(Default)]
+pub(crate) struct Page {
+    ops: Vec<u8>,
+    images: Vec<usize>,
+}
+
+impl Page {
+    pub fn new() -> Self {
+        Page::default()
+    }
+
+    fn op(&mut self, s: &str) {
+        self.ops.extend_from_slice(s.as_bytes());
+        self.ops.push(b'\n');
+    }
+
+    fn text(&mut self, x: f32, y: f32, style: Style, text: &str) {
+        let c = style.color;
+        self.op(&format!("BT /{} {} Tf {} {} {} rg {} {} Td",
+            style.font.resource(), fmt(style.size), fmt(c.0), fmt(c.1), fmt(c.2), fmt(x), fmt(y)));
+        self.ops.extend_from_slice(&pdf_string(text));
+        self.op(" Tj ET");
+    }
+
+    // x: hizalamaya göre sol kenar, orta nokta veya sağ kenar
+    pub fn text_at(&mut self, x: f32, y: f32, style: Style, align: Align, text: &str) {
+        let w = text_width(text, style.size, style.font);
+        let x = match align {
+            Align::Left => x,
+            Align::Center => x - w / 2.0,
+            Align::Right => x - w,
+        };
+        self.text(x, y, style, text);
+    }
+
+    // Kaydırılmış paragraf; bir sonraki satırın y değerini döner
+    pub fn paragraph(&mut self, x: f32, y: f32, width: f32, style: Style, align: Align, text: &str) -> f32 {
+        let ax = match align {
+            Align::Left => x,
+            Align::Center => x + width / 2.0,
+            Align::Right => x + width,
+        };
+        let mut y = y;
+        for line in wrap_text(text, style.size, style.font, width) {
+            self.text_at(ax, y, style, align, &line);
+            y -= style.leading;
+        }
+        y
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32, color: Rgb) {
+        self.op(&format!("{} w {} {} {} RG {} {} m {} {} l S",
+            fmt(width), fmt(color.0), fmt(color.1), fmt(color.2), fmt(x1), fmt(y1), fmt(x2), fmt(y2)));
+    }
+
+    pub fn rect(&mut self, x: f32, y: f32, w: f32, h: f32, fill: Option<Rgb>, stroke: Option<(Rgb, f32)>) {
+        let mut s = String::new();
+        if let Some(c) = fill {
+            s.push_str(&format!("{} {} {} rg ", fmt(c.0), fmt(c.1), fmt(c.2)));
+        }
+        if let Some((c, w)) = stroke {
+            s.push_str(&format!("{} w {} {} {} RG ", fmt(w), fmt(c.0), fmt(c.1), fmt(c.2)));
+        }
+        let paint = match (fill.is_some(), stroke.is_some()) {
+            (true, true) => "B",
+            (true, false) => "f",
+            (false, true) => "S",
+            (false, false) => "n",
+        };
+        s.push_str(&format!("{} {} {} {} re {paint}", fmt(x), fmt(y), fmt(w), fmt(h)));
+        self.op(&s);
+    }
+
+    pub fn image(&mut self, img: ImageRef, x: f32, y: f32, w: f32, h: f32) {
+        if !self.images.contains(&img.index) {
+            self.images.push(img.index);
+        }
+        self.op(&format!("q {} 0 0 {} {} {} cm /Im{} Do Q", fmt(w), fmt(h), fmt(x), fmt(y), img.index));
+    }
+
+    // Oranı koruyarak kutuya sığdırır; çizilen (w, h) döner
+    pub fn image_fit(&mut self, img: ImageRef, x: f32, y: f32, max_w: f32, max_h: f32) -> (f32, f32) {
+        let scale = (max_w / img.width.max(1) as f32).min(max_h / img.height.max(1) as f32);
+        let (w, h) = (img.width as f32 * scale, img.height as f32 * scale);
+        self.image(img, x + (max_w - w) / 2.0, y + (max_h - h) / 2.0, w, h);
+        (w, h)
+    }
+}
+
+// Çok sayfalı akış düzeni: y imleci aşağı iner, sığmayan içerik yeni sayfaya
+// geçer. finish() ile sayfalar belgeye eklenir ve altbilgiye sayfa numarası yazılır.
+pub(crate) struct Flow {
+    pub left: f32,
+    pub right: f32,
+    top: f32,
+    bottom: f32,
+    pub y: f32,
+    page: Page,
+    done: Vec<Page>,
+    footer: String,
+}
+
+pub(crate) struct Column<'a> {
+    pub title: &'a str,
+    // içerik genişliğine oran
+    pub width: f32,
+    pub align: Align,
+}
+
+impl Flow {
+    pub fn new(doc: &PdfDocument, margin: f32, footer: &str) -> Self {
+        let top = doc.height() - margin;
+        Flow {
+            left: margin,
+            right: doc.width() - margin,
+            top,
+            bottom: margin + 20.0,
+            y: top,
+            page: Page::new(),
+            done: Vec::new(),
+            footer: footer.to_string(),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn page(&mut self) -> &mut Page {
+        &mut self.page
+    }
+
+    pub fn new_page(&mut self) {
+        let page = std::mem::take(&mut self.page);
+        self.done.push(page);
+        self.y = self.top;
+    }
+
+    // h yüksekliğinde yer yoksa yeni sayfaya geç
+    pub fn ensure(&mut self, h: f32) {
+        if self.y - h < self.bottom && self.y < self.top {
+            self.new_page();
+        }
+    }
+
+    pub fn space(&mut self, h: f32) {
+        self.y -= h;
+    }
+
+    pub fn heading(&mut self, text: &str) {
+        let style = Style::new(13.0, Font::Bold);
+        self.ensure(40.0);
+        self.y -= style.size;
+        let (left, right, y) = (self.left, self.right, self.y);
+        self.page.text_at(left, y, style, Align::Left, text);
+        self.page.line(left, y - 5.0, right, y - 5.0, 0.6, GRAY);
+        self.y -= 18.0;
+    }
+
+    pub fn paragraph(&mut self, style: Style, text: &str) {
+        for line in wrap_text(text, style.size, style.font, self.width()) {
+            self.ensure(style.leading);
+            self.y -= style.size;
+            let (left, y) = (self.left, self.y);
+            self.page.text_at(left, y, style, Align::Left, &line);
+            self.y -= style.leading - style.size;
+        }
+    }
+
+    // Etiket: değer satırları (etiketler tek sütunda hizalı)
+    pub fn fields(&mut self, x: f32, label_width: f32, rows: &[(&str, String)]) {
+        let label = Style::new(10.0, Font::Bold).color(GRAY);
+        let value = Style::new(10.0, Font::Regular);
+        let width = self.right - x - label_width;
+        for (name, text) in rows {
+            let lines = wrap_text(text, value.size, value.font, width);
+            self.ensure(value.leading * lines.len().max(1) as f32);
+            self.y -= value.size;
+            let y = self.y;
+            self.page.text_at(x, y, label, Align::Left, name);
+            for (i, line) in lines.iter().enumerate() {
+                self.page.text_at(x + label_width, y - i as f32 * value.leading, value, Align::Left, line);
+            }
+            self.y -= value.leading * lines.len().max(1) as f32 - value.size;
+        }
+    }
+
+    // Başlık satırı her sayfada tekrarlanır; hücre metinleri kaydırılır
+    pub fn table(&mut self, columns: &[Column], rows: &[Vec<String>]) {
+        let head = Style::new(9.0, Font::Bold);
+        let cell = Style::new(9.0, Font::Regular);
+        let pad = 4.0;
+        let widths: Vec<f32> = columns.iter().map(|c| c.width * self.width()).collect();
+
+        let draw_header = |flow: &mut Flow| {
+            let h = head.leading + pad * 2.0;
+            let (left, y) = (flow.left, flow.y);
+            let w = flow.width();
+            flow.page.rect(left, y - h, w, h, Some(LIGHT_GRAY), None);
+            let mut x = left;
+            for (c, cw) in columns.iter().zip(&widths) {
+                flow.page.text_at(cell_x(x, *cw, pad, c.align), y - pad - head.size, head, c.align, c.title);
+                x += cw;
+            }
+            flow.y -= h;
+        };
+
+        self.ensure(head.leading * 2.0 + pad * 4.0);
+        draw_header(self);
+        for row in rows {
+            let wrapped: Vec<Vec<String>> = row
+                .iter()
+                .zip(&widths)
+                .map(|(text, cw)| wrap_text(text, cell.size, cell.font, cw - pad * 2.0))
+                .collect();
+            let lines = wrapped.iter().map(|l| l.len().max(1)).max().unwrap_or(1);
+            let h = cell.leading * lines as f32 + pad * 2.0 - (cell.leading - cell.size);
+            if self.y - h < self.bottom {
+                self.new_page();
+                draw_header(self);
+            }
+            let (left, right, y) = (self.left, self.right, self.y);
+            let mut x = left;
+            for ((c, cw), lines) in columns.iter().zip(&widths).zip(&wrapped) {
+                for (i, line) in lines.iter().enumerate() {
+                    let ly = y - pad - cell.size - i as f32 * cell.leading;
+                    self.page.text_at(cell_x(x, *cw, pad, c.align), ly, cell, c.align, line);
+                }
+                x += cw;
+            }
+            self.page.line(left, y - h, right, y - h, 0.3, LIGHT_GRAY);
+            self.y -= h;
+        }
+    }
+
+    pub fn finish(mut self, doc: &mut PdfDocument) {
+        self.new_page();
+        let total = self.done.len();
+        let style = Style::new(8.0, Font::Regular).color(GRAY);
+        let y = self.bottom - 20.0;
+        for (i, mut page) in self.done.into_iter().enumerate() {
+            if !self.footer.is_empty() {
+                page.text_at(self.left, y, style, Align::Left, &self.footer);
+            }
+            page.text_at(self.right, y, style, Align::Right, &format!("Sayfa {} / {total}", i + 1));
+            doc.add_page(page);
+        }
+    }
+}
+
+fn cell_x(x: f32, width: f32, pad: f32, align: Align) -> f32 {
+    match align {
+        Align::Left => x + pad,
+        Align::Center => x + width / 2.0,
+        Align::Right => x + width - pad,
+    }
+}
+
+pub(crate) struct PdfDocument {
+    size: (f32, f32),
+    title: String,
+    pages: Vec<Page>,
+    images: Vec<ImageObject>,
+}
+
+fn jpeg_info(data: &[u8]) -> Option<(u32, u32, u8)> {
+    if data.len() < 4 || data[0] != 0xFF || data[1] != 0xD8 {
+        return None;
+    }
+    let mut i = 2;
+    while i + 9 < data.len() {
+        if data[i] != 0xFF {
+            i += 1;
+            continue;
+        }
+        let marker = data[i + 1];
+        let len = u16::from_be_bytes([data[i + 2], data[i + 3]]) as usize;
+        // SOF0..SOF15 (DHT, JPG, DAC hariç)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+            let h = u16::from_be_bytes([data[i + 5], data[i + 6]]) as u32;
+            let w = u16::from_be_bytes([data[i + 7], data[i + 8]]) as u32;
+            return Some((w, h, data[i + 9]));
+        }
+        i += 2 + len;
+    }
+    None
+}
+
+fn zlib(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
+    enc.write_all(data).map_err(|e| e.to_string())?;
+    enc.finish().map_err(|e| e.to_string())
+}
+
+fn decode_png(data: &[u8]) -> Result<ImageObject, String> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|e| format!("PNG okunamadı: {e}"))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|e| format!("PNG okunamadı: {e}"))?;
+    buf.truncate(info.buffer_size());
+
+    let (channels, color_space) = match info.color_type {
+        png::ColorType::Grayscale => (1, "DeviceGray"),
+        png::ColorType::GrayscaleAlpha => (2, "DeviceGray"),
+        png::ColorType::Rgb => (3, "DeviceRGB"),
+        png::ColorType::Rgba => (4, "DeviceRGB"),
+        png::ColorType::Indexed => return Err("Desteklenmeyen PNG renk tipi".to_string()),
+    };
+    let (pixels, alpha) = if channels == 2 || channels == 4 {
+        let color_n = channels - 1;
+        let mut color = Vec::with_capacity(buf.len() / channels * color_n);
+        let mut alpha = Vec::with_capacity(buf.len() / channels);
+        for px in buf.chunks_exact(channels) {
+            color.extend_from_slice(&px[..color_n]);
+            alpha.push(px[color_n]);
+        }
+        (color, Some(zlib(&alpha)?))
+    } else {
+        (buf, None)
+    };
+
+    Ok(ImageObject {
+        width: info.width,
+        height: info.height,
+        color_space,
+        bits: 8,
+        filter: "FlateDecode",
+        data: zlib(&pixels)?,
+        alpha,
+    })
+}
+
+impl PdfDocument {
+    pub fn new(size: (f32, f32), title: &str) -> Self {
+        PdfDocument { size, title: title.to_string(), pages: Vec::new(), images: Vec::new() }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.size.0
+    }
+
+    pub fn height(&self) -> f32 {
+        self.size.1
+    }
+
+    // JPEG veya PNG
+    pub fn add_image(&mut self, data: &[u8]) -> Result<ImageRef, String> {
+        let img = if let Some((width, height, comps)) = jpeg_info(data) {
+            let color_space = match comps {
+                1 => "DeviceGray",
+                4 => "DeviceCMYK",
+                _ => "DeviceRGB",
+            };
+            ImageObject { width, height, color_space, bits: 8, filter: "DCTDecode", data: data.to_vec(), alpha: None }
+        } else if data.starts_with(b"\x89PNG") {
+            decode_png(data)?
+        } else {
+            return Err("Görsel yalnızca JPEG veya PNG olabilir".to_string());
+        };
+        self.images.push(img);
+        let last = self.images.last().expect("az önce eklendi");
+        Ok(ImageRef { index: self.images.len() - 1, width: last.width, height: last.height })
+    }
+
+    pub fn add_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets: Vec<usize> = Vec::new();
+
+        // 1 katalog, 2 sayfalar, 3 kodlama, 4-6 fontlar, 7 bilgi
+        const FIXED: usize = 7;
+        let mut image_ids = Vec::new();
+        let mut next = FIXED + 1;
+        for img in &self.images {
+            let smask = img.alpha.as_ref().map(|_| next + 1);
+            image_ids.push((next, smask));
+            next += if smask.is_some() { 2 } else { 1 };
+        }
+        let first_page = next;
+
+        fn obj(out: &mut Vec<u8>, offsets: &mut Vec<usize>, body: &[u8]) {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        fn stream(dict: &str, data: &[u8]) -> Vec<u8> {
+            let mut b = format!("<< {dict} /Length {} >>\nstream\n", data.len()).into_bytes();
+            b.extend_from_slice(data);
+            b.extend_from_slice(b"\nendstream");
+            b
+        }
+
+        let kids: Vec<String> = (0..self.pages.len()).map(|i| format!("{} 0 R", first_page + i * 2)).collect();
+        obj(&mut out, &mut offsets, b"<< /Type /Catalog /Pages 2 0 R >>");
+        obj(&mut out, &mut offsets, format!(
+            "<< /Type /Pages /Kids [{}] /Count {} /MediaBox [0 0 {} {}] >>",
+            kids.join(" "), self.pages.len(), fmt(self.size.0), fmt(self.size.1)
+        ).as_bytes());
+        obj(&mut out, &mut offsets, format!(
+            "<< /Type /Encoding /BaseEncoding /WinAnsiEncoding /Differences {TR_DIFFERENCES} >>"
+        ).as_bytes());
+        for name in ["Helvetica", "Helvetica-Bold", "Helvetica-Oblique"] {
+            obj(&mut out, &mut offsets, format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{name} /Encoding 3 0 R >>"
+            ).as_bytes());
+        }
+        // başlık UTF-16BE (BOM ile)
+        let title_hex: String = std::iter::once(0xFEFFu16)
+            .chain(self.title.encode_utf16())
+            .map(|u| format!("{u:04X}"))
+            .collect();
+        obj(&mut out, &mut offsets, format!("<< /Title <{title_hex}> /Producer (InternTracker) >>").as_bytes());
+
+        for (img, (_, smask)) in self.images.iter().zip(&image_ids) {
+            let smask_ref = smask.map(|id| format!(" /SMask {id} 0 R")).unwrap_or_default();
+            obj(&mut out, &mut offsets, &stream(
+                &format!(
+                    "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /{} /BitsPerComponent {} /Filter /{}{smask_ref}",
+                    img.width, img.height, img.color_space, img.bits, img.filter
+                ),
+                &img.data,
+            ));
+            if let Some(alpha) = &img.alpha {
+                obj(&mut out, &mut offsets, &stream(
+                    &format!(
+                        "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /FlateDecode",
+                        img.width, img.height
+                    ),
+                    alpha,
+                ));
+            }
+        }
+
+        for (i, page) in self.pages.iter().enumerate() {
+            let content_id = first_page + i * 2 + 1;
+            let xobjects: String = page
+                .images
+                .iter()
+                .map(|idx| format!("/Im{idx} {} 0 R ", image_ids[*idx].0))
+                .collect();
+            obj(&mut out, &mut offsets, format!(
+                "<< /Type /Page /Parent 2 0 R /Resources << /Font << /F1 4 0 R /F2 5 0 R /F3 6 0 R >> /XObject << {xobjects}>> >> /Contents {content_id} 0 R >>"
+            ).as_bytes());
+            obj(&mut out, &mut offsets, &stream("/Filter /FlateDecode", &zlib(&page.ops)?));
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1).as_bytes());
+        for off in &offsets {
+            out.extend_from_slice(format!("{off:010} 00000 n \n").as_bytes());
+        }
+        out.extend_from_slice(format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 7 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            offsets.len() + 1
+        ).as_bytes());
+        Ok(out)
+    }
+}
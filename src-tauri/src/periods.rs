@@ -0,0 +1,340 @@
+// Staj dönemleri (ör. "2025 Yaz Dönemi"): tarih aralığı, toplam kontenjan ve
+// bölüm bazlı kotalar. Stajyerler interns.period_id ile bir döneme bağlanır.
+
+use std::fs;
+use std::path::PathBuf;
+
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::parse_date;
+use crate::query::{filter_clause, InternFilter};
+use crate::{events, intern_lite_from_row, open_conn, INTERN_LITE_COLUMNS};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartmentQuota {
+    pub department: String,
+    pub capacity: i64,
+    // yalnızca okumada dolu
+    #[serde(default)]
+    pub enrolled: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Period {
+    pub id: i64,
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    // None: sınırsız
+    pub capacity: Option<i64>,
+    pub enrolled: i64,
+    pub quotas: Vec<DepartmentQuota>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PeriodPayload {
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub capacity: Option<i64>,
+    #[serde(default)]
+    pub quotas: Vec<DepartmentQuota>,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS periods (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            start_date TEXT NOT NULL,
+            end_date TEXT NOT NULL,
+            capacity INTEGER CHECK (capacity IS NULL OR capacity >= 0),
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS period_quotas (
+            period_id INTEGER NOT NULL,
+            department TEXT NOT NULL,
+            capacity INTEGER NOT NULL CHECK (capacity >= 0),
+            PRIMARY KEY (period_id, department),
+            FOREIGN KEY (period_id) REFERENCES periods(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn enrolled(conn: &Connection, period_id: i64, department: Option<&str>, exclude: Option<i64>) -> Result<i64, String> {
+    conn.query_row(
+        r#"
+        SELECT COUNT(*) FROM interns
+        WHERE period_id = ?1
+          AND (?2 IS NULL OR department = ?2)
+          AND (?3 IS NULL OR id <> ?3)
+        "#,
+        params![period_id, department, exclude],
+        |r| r.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Stajyer döneme eklenebilir mi? `exclude`: güncellenen stajyerin kendisi.
+pub(crate) fn check_capacity(conn: &Connection, period_id: i64, department: &str, exclude: Option<i64>) -> Result<(), String> {
+    let (name, capacity): (String, Option<i64>) = conn
+        .query_row(
+            "SELECT name, capacity FROM periods WHERE id = ?1",
+            params![period_id],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Staj dönemi bulunamadı".to_string())?;
+
+    if let Some(cap) = capacity {
+        let n = enrolled(conn, period_id, None, exclude)?;
+        if n >= cap {
+            return Err(format!("{name} kontenjanı dolu ({n}/{cap})"));
+        }
+    }
+
+    let quota: Option<i64> = conn
+        .query_row(
+            "SELECT capacity FROM period_quotas WHERE period_id = ?1 AND department = ?2",
+            params![period_id, department],
+            |r| r.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if let Some(cap) = quota {
+        let n = enrolled(conn, period_id, Some(department), exclude)?;
+        if n >= cap {
+            return Err(format!("{name} için {department} kotası dolu ({n}/{cap})"));
+        }
+    }
+    Ok(())
+}
+
+fn load_quotas(conn: &Connection, period_id: i64) -> Result<Vec<DepartmentQuota>, String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT q.department, q.capacity,
+               (SELECT COUNT(*) FROM interns i WHERE i.period_id = q.period_id AND i.department = q.department)
+        FROM period_quotas q
+        WHERE q.period_id = ?1
+        ORDER BY q.department
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![period_id], |r| {
+        Ok(DepartmentQuota { department: r.get(0)?, capacity: r.get(1)?, enrolled: r.get(2)? })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+pub(crate) fn list_periods_conn(conn: &Connection) -> Result<Vec<Period>, String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT p.id, p.name, p.start_date, p.end_date, p.capacity, p.created_at,
+               (SELECT COUNT(*) FROM interns i WHERE i.period_id = p.id)
+        FROM periods p
+        ORDER BY p.start_date DESC, p.id DESC
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |r| {
+        Ok(Period {
+            id: r.get(0)?,
+            name: r.get(1)?,
+            start_date: r.get(2)?,
+            end_date: r.get(3)?,
+            capacity: r.get(4)?,
+            created_at: r.get(5)?,
+            enrolled: r.get(6)?,
+            quotas: Vec::new(),
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows {
+        let mut p = r.map_err(|e| e.to_string())?;
+        p.quotas = load_quotas(conn, p.id)?;
+        out.push(p);
+    }
+    Ok(out)
+}
+
+fn validate(conn: &Connection, p: &PeriodPayload, existing: Option<i64>) -> Result<(), String> {
+    if p.name.trim().is_empty() {
+        return Err("Dönem adı boş olamaz".to_string());
+    }
+    if parse_date(&p.end_date)? < parse_date(&p.start_date)? {
+        return Err("Bitiş tarihi başlangıçtan önce olamaz".to_string());
+    }
+    if p.capacity.is_some_and(|c| c < 0) || p.quotas.iter().any(|q| q.capacity < 0) {
+        return Err("Kontenjan negatif olamaz".to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    for q in &p.quotas {
+        if q.department.trim().is_empty() || !seen.insert(q.department.trim()) {
+            return Err("Bölüm kotaları boş veya tekrarlı olamaz".to_string());
+        }
+    }
+
+    // mevcut dönem: kontenjan kayıtlı stajyer sayısının altına inemez
+    if let Some(id) = existing {
+        let n = enrolled(conn, id, None, None)?;
+        if p.capacity.is_some_and(|c| c < n) {
+            return Err(format!("Kontenjan kayıtlı stajyer sayısından ({n}) az olamaz"));
+        }
+        for q in &p.quotas {
+            let n = enrolled(conn, id, Some(q.department.trim()), None)?;
+            if q.capacity < n {
+                return Err(format!("{} kotası kayıtlı stajyer sayısından ({n}) az olamaz", q.department.trim()));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn store_quotas(conn: &Connection, period_id: i64, quotas: &[DepartmentQuota]) -> Result<(), String> {
+    conn.execute("DELETE FROM period_quotas WHERE period_id = ?1", params![period_id])
+        .map_err(|e| e.to_string())?;
+    for q in quotas {
+        conn.execute(
+            "INSERT INTO period_quotas (period_id, department, capacity) VALUES (?1, ?2, ?3)",
+            params![period_id, q.department.trim(), q.capacity],
+        ).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn map_unique(e: rusqlite::Error) -> String {
+    match e {
+        rusqlite::Error::SqliteFailure(err, _) if err.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE => {
+            "Bu adla bir dönem zaten var".to_string()
+        }
+        e => e.to_string(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([';', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[tauri::command]
+pub fn create_period(handle: AppHandle, period: PeriodPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    validate(&conn, &period, None)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO periods (name, start_date, end_date, capacity) VALUES (?1, ?2, ?3, ?4)",
+        params![period.name.trim(), period.start_date, period.end_date, period.capacity],
+    ).map_err(map_unique)?;
+    let id = tx.last_insert_rowid();
+    store_quotas(&tx, id, &period.quotas)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn list_periods(handle: AppHandle) -> Result<Vec<Period>, String> {
+    let conn = open_conn(&handle)?;
+    list_periods_conn(&conn)
+}
+
+#[tauri::command]
+pub fn update_period(handle: AppHandle, id: i64, period: PeriodPayload) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    validate(&conn, &period, Some(id))?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let n = tx.execute(
+        "UPDATE periods SET name = ?1, start_date = ?2, end_date = ?3, capacity = ?4 WHERE id = ?5",
+        params![period.name.trim(), period.start_date, period.end_date, period.capacity, id],
+    ).map_err(map_unique)?;
+    if n == 0 {
+        return Err("Staj dönemi bulunamadı".to_string());
+    }
+    store_quotas(&tx, id, &period.quotas)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// Stajyerler silinmez; dönem bağlantıları boşaltılır
+#[tauri::command]
+pub fn delete_period(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    conn.execute("DELETE FROM periods WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// period_id = None: stajyeri dönemden çıkar
+#[tauri::command]
+pub fn assign_intern_period(handle: AppHandle, intern_id: i64, period_id: Option<i64>) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let department: String = conn
+        .query_row("SELECT department FROM interns WHERE id = ?1", params![intern_id], |r| r.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    if let Some(pid) = period_id {
+        check_capacity(&conn, pid, &department, Some(intern_id))?;
+    }
+    let changed = conn
+        .execute(
+            "UPDATE interns SET period_id = ?1 WHERE id = ?2 AND period_id IS NOT ?1",
+            params![period_id, intern_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if changed > 0 {
+        events::emit_current(&conn, events::INTERN_UPDATED, intern_id, events::intern_data)?;
+    }
+    Ok(())
+}
+
+// Dönemdeki stajyerler, Excel uyumlu CSV (UTF-8 BOM, ';' ayraç)
+#[tauri::command]
+pub fn export_period_interns(handle: AppHandle, period_id: i64, export_path: String) -> Result<usize, String> {
+    let conn = open_conn(&handle)?;
+    let filter = InternFilter { period_id: Some(period_id), ..Default::default() };
+    let (where_sql, vals) = filter_clause(&filter);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {INTERN_LITE_COLUMNS} FROM interns i WHERE {where_sql} ORDER BY i.last_name, i.first_name"
+    )).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params_from_iter(vals.iter()), intern_lite_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut out = String::from("\u{FEFF}Ad;Soyad;Okul;Bölüm;Başlangıç;Bitiş;Durum;Telefon;E-posta\r\n");
+    let mut count = 0;
+    for r in rows {
+        let i = r.map_err(|e| e.to_string())?;
+        let fields = [
+            i.first_name.as_str(),
+            i.last_name.as_str(),
+            i.school.as_str(),
+            i.department.as_str(),
+            i.start_date.as_str(),
+            i.end_date.as_deref().unwrap_or(""),
+            i.status.as_str(),
+            i.contact.as_str(),
+            i.email.as_str(),
+        ];
+        out.push_str(&fields.map(csv_field).join(";"));
+        out.push_str("\r\n");
+        count += 1;
+    }
+
+    let path = PathBuf::from(export_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Klasör oluşturulamadı: {e}"))?;
+    }
+    fs::write(&path, out).map_err(|e| format!("Dosya yazılamadı: {e}"))?;
+    Ok(count)
+}
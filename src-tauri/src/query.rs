@@ -0,0 +1,256 @@
+// Sunucu tarafı filtreleme / sıralama / sayfalama (stajyer listesi)
+
+use rusqlite::{params_from_iter, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{intern_lite_from_row, open_conn, InternLite, INTERN_LITE_COLUMNS};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InternFilter {
+    pub school: Option<String>,
+    pub department: Option<String>,
+    pub status: Option<String>,
+    // bu tarihte stajı devam edenler (YYYY-MM-DD)
+    pub active_on: Option<String>,
+    // staj dönemi bu aralıkla kesişenler
+    pub period_from: Option<String>,
+    pub period_to: Option<String>,
+    // staj dönemi (periods.id)
+    pub period_id: Option<i64>,
+    // ad, soyad, e-posta, okul, bölüm içinde arama
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    Name,
+    FirstName,
+    School,
+    Department,
+    StartDate,
+    EndDate,
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InternSort {
+    pub key: SortKey,
+    pub direction: SortDirection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct InternQuery {
+    pub filter: InternFilter,
+    pub sort: InternSort,
+    // önceki sayfanın next_cursor değeri
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InternPage {
+    pub items: Vec<InternLite>,
+    pub total: i64,
+    pub next_cursor: Option<String>,
+}
+
+impl SortKey {
+    // keyset sayfalama için sıralama ifadeleri (id her zaman en sonda)
+    fn exprs(self) -> &'static [&'static str] {
+        match self {
+            SortKey::Name => &["last_name", "first_name", "id"],
+            SortKey::FirstName => &["first_name", "last_name", "id"],
+            SortKey::School => &["school", "id"],
+            SortKey::Department => &["department", "id"],
+            SortKey::StartDate => &["start_date", "id"],
+            SortKey::EndDate => &["COALESCE(end_date, '')", "id"],
+            SortKey::Status => &["status", "id"],
+        }
+    }
+
+    fn cursor_values(self, i: &InternLite) -> Vec<serde_json::Value> {
+        let id = serde_json::Value::from(i.id.unwrap_or(0));
+        let s = |v: &str| serde_json::Value::from(v);
+        match self {
+            SortKey::Name => vec![s(&i.last_name), s(&i.first_name), id],
+            SortKey::FirstName => vec![s(&i.first_name), s(&i.last_name), id],
+            SortKey::School => vec![s(&i.school), id],
+            SortKey::Department => vec![s(&i.department), id],
+            SortKey::StartDate => vec![s(&i.start_date), id],
+            SortKey::EndDate => vec![s(i.end_date.as_deref().unwrap_or("")), id],
+            SortKey::Status => vec![s(&i.status), id],
+        }
+    }
+}
+
+fn non_empty(v: &Option<String>) -> Option<&str> {
+    v.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Filtreden WHERE koşulu ve parametreleri üretir. Koşul `i` takma adlı
+/// `interns` tablosuna göre yazılır; boş filtre için "1 = 1" döner.
+pub(crate) fn filter_clause(f: &InternFilter) -> (String, Vec<rusqlite::types::Value>) {
+    use rusqlite::types::Value;
+
+    let mut conds: Vec<String> = Vec::new();
+    let mut vals: Vec<Value> = Vec::new();
+
+    if let Some(school) = non_empty(&f.school) {
+        vals.push(Value::Text(school.to_string()));
+        conds.push(format!("i.school = ?{}", vals.len()));
+    }
+    if let Some(dep) = non_empty(&f.department) {
+        vals.push(Value::Text(dep.to_string()));
+        conds.push(format!("i.department = ?{}", vals.len()));
+    }
+    if let Some(status) = non_empty(&f.status) {
+        vals.push(Value::Text(status.to_string()));
+        conds.push(format!("i.status = ?{}", vals.len()));
+    }
+    if let Some(day) = non_empty(&f.active_on) {
+        vals.push(Value::Text(day.to_string()));
+        let n = vals.len();
+        conds.push(format!(
+            "i.start_date <= ?{n} AND (i.end_date IS NULL OR i.end_date = '' OR i.end_date >= ?{n})"
+        ));
+    }
+    if let Some(from) = non_empty(&f.period_from) {
+        vals.push(Value::Text(from.to_string()));
+        conds.push(format!(
+            "(i.end_date IS NULL OR i.end_date = '' OR i.end_date >= ?{})",
+            vals.len()
+        ));
+    }
+    if let Some(to) = non_empty(&f.period_to) {
+        vals.push(Value::Text(to.to_string()));
+        conds.push(format!("i.start_date <= ?{}", vals.len()));
+    }
+    if let Some(period_id) = f.period_id {
+        vals.push(Value::Integer(period_id));
+        conds.push(format!("i.period_id = ?{}", vals.len()));
+    }
+    if let Some(text) = non_empty(&f.text) {
+        let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        vals.push(Value::Text(format!("%{escaped}%")));
+        let n = vals.len();
+        conds.push(format!(
+            "(i.first_name || ' ' || i.last_name LIKE ?{n} ESCAPE '\\' \
+              OR i.email LIKE ?{n} ESCAPE '\\' \
+              OR i.school LIKE ?{n} ESCAPE '\\' \
+              OR i.department LIKE ?{n} ESCAPE '\\')"
+        ));
+    }
+
+    if conds.is_empty() {
+        ("1 = 1".to_string(), vals)
+    } else {
+        (conds.join(" AND "), vals)
+    }
+}
+
+fn order_clause(sort: &InternSort) -> String {
+    let dir = match sort.direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    sort.key
+        .exprs()
+        .iter()
+        .map(|e| format!("{e} {dir}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn query_interns_conn(conn: &Connection, q: &InternQuery) -> Result<InternPage, String> {
+    use rusqlite::types::Value;
+
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let (where_sql, mut vals) = filter_clause(&q.filter);
+
+    let total: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM interns i WHERE {where_sql}"),
+            params_from_iter(vals.iter()),
+            |r| r.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let exprs = q.sort.key.exprs();
+    let mut page_where = where_sql;
+    if let Some(cursor) = q.cursor.as_deref().filter(|c| !c.is_empty()) {
+        let parsed: Vec<serde_json::Value> =
+            serde_json::from_str(cursor).map_err(|_| "Geçersiz sayfa imleci".to_string())?;
+        if parsed.len() != exprs.len() {
+            return Err("Sayfa imleci sıralama ile uyuşmuyor".to_string());
+        }
+        let mut holders = Vec::new();
+        for v in parsed {
+            vals.push(match v {
+                serde_json::Value::Number(n) => Value::Integer(n.as_i64().unwrap_or(0)),
+                serde_json::Value::String(s) => Value::Text(s),
+                _ => return Err("Geçersiz sayfa imleci".to_string()),
+            });
+            holders.push(format!("?{}", vals.len()));
+        }
+        let op = match q.sort.direction {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        };
+        page_where = format!(
+            "({page_where}) AND ({}) {op} ({})",
+            exprs.join(", "),
+            holders.join(", ")
+        );
+    }
+
+    // bir fazlasını çekip sonraki sayfa var mı anlıyoruz
+    vals.push(Value::Integer(limit + 1));
+    let sql = format!(
+        "SELECT {INTERN_LITE_COLUMNS} FROM interns i WHERE {page_where} ORDER BY {} LIMIT ?{}",
+        order_clause(&q.sort),
+        vals.len()
+    );
+
+    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params_from_iter(vals.iter()), intern_lite_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut items = Vec::new();
+    for r in rows { items.push(r.map_err(|e| e.to_string())?); }
+
+    let mut next_cursor = None;
+    if items.len() as i64 > limit {
+        items.truncate(limit as usize);
+        if let Some(last) = items.last() {
+            next_cursor = Some(
+                serde_json::to_string(&q.sort.key.cursor_values(last)).map_err(|e| e.to_string())?,
+            );
+        }
+    }
+
+    Ok(InternPage { items, total, next_cursor })
+}
+
+#[tauri::command]
+pub fn query_interns(handle: AppHandle, query: InternQuery) -> Result<InternPage, String> {
+    let conn = open_conn(&handle)?;
+    query_interns_conn(&conn, &query)
+}
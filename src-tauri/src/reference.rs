@@ -0,0 +1,555 @@
+// Okul / bölüm referans verisi. Stajyer kaydında metin olarak tutulmaya devam
+// eder; ancak referans tablosu doluysa değer kanonik ada çevrilir, eşleşmeyen
+// değer reddedilir. Eşleştirme anahtarı slug_tr ile Türkçe harfleri katlar.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{events, open_conn, slug_tr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefKind {
+    School,
+    Department,
+}
+
+impl RefKind {
+    fn table(self) -> &'static str {
+        match self {
+            RefKind::School => "schools",
+            RefKind::Department => "departments",
+        }
+    }
+
+    fn alias_table(self) -> &'static str {
+        match self {
+            RefKind::School => "school_aliases",
+            RefKind::Department => "department_aliases",
+        }
+    }
+
+    // interns tablosundaki kolon
+    fn column(self) -> &'static str {
+        match self {
+            RefKind::School => "school",
+            RefKind::Department => "department",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            RefKind::School => "okul",
+            RefKind::Department => "bölüm",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RefEntry {
+    pub id: i64,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub intern_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RefPayload {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeVariant {
+    pub value: String,
+    pub intern_count: i64,
+    // referans tablosunda kayıtlı ad mı
+    pub is_reference: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeGroup {
+    pub canonical: String,
+    pub variants: Vec<MergeVariant>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeResult {
+    pub reference_id: i64,
+    pub interns_updated: usize,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    for kind in [RefKind::School, RefKind::Department] {
+        let (t, a) = (kind.table(), kind.alias_table());
+        conn.execute_batch(&format!(
+            r#"
+            CREATE TABLE IF NOT EXISTS {t} (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                name_key TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE TABLE IF NOT EXISTS {a} (
+                alias_key TEXT PRIMARY KEY,
+                alias TEXT NOT NULL,
+                ref_id INTEGER NOT NULL,
+                FOREIGN KEY (ref_id) REFERENCES {t}(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_{a}_ref ON {a}(ref_id);
+            "#
+        ))
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Eşleştirme anahtarı: "İTÜ" ve "ITU" → "itu"; noktalama ve fazla boşluk yok sayılır.
+pub(crate) fn match_key(s: &str) -> String {
+    slug_tr(s).split('_').filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+// "istanbul teknik universitesi" → "itu"
+fn acronym(key: &str) -> Option<String> {
+    let words: Vec<&str> = key.split(' ').collect();
+    if words.len() < 2 {
+        return None;
+    }
+    Some(words.iter().filter_map(|w| w.chars().next()).collect())
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+// Aynı kurumu gösterdiği tahmin edilen iki anahtar
+fn similar(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    if acronym(a).as_deref() == Some(b) || acronym(b).as_deref() == Some(a) {
+        return true;
+    }
+    // kısaltılmış kelimeler: "bogazici univ" ~ "bogazici universitesi"
+    let (wa, wb): (Vec<&str>, Vec<&str>) = (a.split(' ').collect(), b.split(' ').collect());
+    if wa.len() == wb.len()
+        && wa.iter().zip(&wb).all(|(x, y)| {
+            let (short, long) = if x.len() <= y.len() { (x, y) } else { (y, x) };
+            short.len() >= 3 && long.starts_with(short)
+        })
+    {
+        return true;
+    }
+    let len = a.chars().count().min(b.chars().count());
+    let allowed = match len {
+        0..=3 => 0,
+        4..=8 => 1,
+        9..=20 => 2,
+        _ => 3,
+    };
+    allowed > 0 && levenshtein(a, b) <= allowed
+}
+
+fn has_reference_data(conn: &Connection, kind: RefKind) -> Result<bool, String> {
+    conn.query_row(&format!("SELECT EXISTS(SELECT 1 FROM {})", kind.table()), [], |r| r.get(0))
+        .map_err(|e| e.to_string())
+}
+
+fn lookup(conn: &Connection, kind: RefKind, key: &str) -> Result<Option<(i64, String)>, String> {
+    let (t, a) = (kind.table(), kind.alias_table());
+    conn.query_row(
+        &format!(
+            "SELECT id, name FROM {t} WHERE name_key = ?1
+             UNION ALL
+             SELECT r.id, r.name FROM {a} x JOIN {t} r ON r.id = x.ref_id WHERE x.alias_key = ?1
+             LIMIT 1"
+        ),
+        params![key],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// add_intern / update_intern doğrulaması: referans verisi yoksa değer aynen
+/// kabul edilir; varsa kanonik ad döner, tanımsız değer hata verir.
+pub(crate) fn resolve(conn: &Connection, kind: RefKind, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if !has_reference_data(conn, kind)? {
+        return Ok(value.to_string());
+    }
+    match lookup(conn, kind, &match_key(value))? {
+        Some((_, name)) => Ok(name),
+        None => Err(format!("Tanımsız {}: {value}", kind.label())),
+    }
+}
+
+fn intern_counts(conn: &Connection, kind: RefKind) -> Result<HashMap<String, i64>, String> {
+    let col = kind.column();
+    let mut stmt = conn
+        .prepare(&format!("SELECT {col}, COUNT(*) FROM interns GROUP BY {col}"))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?)))
+        .map_err(|e| e.to_string())?;
+    let mut out = HashMap::new();
+    for r in rows {
+        let (k, v) = r.map_err(|e| e.to_string())?;
+        out.insert(k, v);
+    }
+    Ok(out)
+}
+
+pub(crate) fn list_conn(conn: &Connection, kind: RefKind) -> Result<Vec<RefEntry>, String> {
+    let (t, a) = (kind.table(), kind.alias_table());
+    let counts = intern_counts(conn, kind)?;
+
+    let mut aliases: HashMap<i64, Vec<String>> = HashMap::new();
+    let mut stmt = conn
+        .prepare(&format!("SELECT ref_id, alias FROM {a} ORDER BY alias"))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?;
+    for r in rows {
+        let (id, alias) = r.map_err(|e| e.to_string())?;
+        aliases.entry(id).or_default().push(alias);
+    }
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT id, name FROM {t} ORDER BY name"))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)))
+        .map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows {
+        let (id, name) = r.map_err(|e| e.to_string())?;
+        out.push(RefEntry {
+            intern_count: counts.get(&name).copied().unwrap_or(0),
+            aliases: aliases.remove(&id).unwrap_or_default(),
+            id,
+            name,
+        });
+    }
+    Ok(out)
+}
+
+fn add_alias(conn: &Connection, kind: RefKind, ref_id: i64, alias: &str) -> Result<(), String> {
+    let key = match_key(alias);
+    if key.is_empty() {
+        return Ok(());
+    }
+    if let Some((other, name)) = lookup(conn, kind, &key)? {
+        if other != ref_id {
+            return Err(format!("\"{}\" zaten {name} için kullanılıyor", alias.trim()));
+        }
+        // kendi adı veya mevcut takma adı
+        return Ok(());
+    }
+    conn.execute(
+        &format!("INSERT INTO {} (alias_key, alias, ref_id) VALUES (?1, ?2, ?3)", kind.alias_table()),
+        params![key, alias.trim(), ref_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Stajyer kayıtlarında `from` değerini `to` yapar; değişen her stajyer için
+// intern.updated yazılır
+fn rename_in_interns(conn: &Connection, kind: RefKind, from: &str, to: &str) -> Result<usize, String> {
+    let col = kind.column();
+    let mut stmt = conn
+        .prepare(&format!("SELECT id FROM interns WHERE {col} = ?1 AND {col} <> ?2"))
+        .map_err(|e| e.to_string())?;
+    let ids: Vec<i64> = stmt
+        .query_map(params![from, to], |r| r.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    for &id in &ids {
+        conn.execute(&format!("UPDATE interns SET {col} = ?1 WHERE id = ?2"), params![to, id])
+            .map_err(|e| e.to_string())?;
+        events::emit_current(conn, events::INTERN_UPDATED, id, events::intern_data)?;
+    }
+    Ok(ids.len())
+}
+
+fn save_entry(conn: &Connection, kind: RefKind, id: Option<i64>, p: &RefPayload) -> Result<i64, String> {
+    let name = p.name.trim();
+    let key = match_key(name);
+    if key.is_empty() {
+        return Err("Ad boş olamaz".to_string());
+    }
+    if let Some((other, existing)) = lookup(conn, kind, &key)? {
+        if Some(other) != id {
+            return Err(format!("\"{name}\" zaten {existing} olarak kayıtlı"));
+        }
+    }
+
+    let t = kind.table();
+    let id = match id {
+        Some(id) => {
+            let old: String = conn
+                .query_row(&format!("SELECT name FROM {t} WHERE id = ?1"), params![id], |r| r.get(0))
+                .optional()
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "Kayıt bulunamadı".to_string())?;
+            conn.execute(
+                &format!("UPDATE {t} SET name = ?1, name_key = ?2 WHERE id = ?3"),
+                params![name, key, id],
+            ).map_err(|e| e.to_string())?;
+            conn.execute(&format!("DELETE FROM {} WHERE ref_id = ?1", kind.alias_table()), params![id])
+                .map_err(|e| e.to_string())?;
+            // yeniden adlandırma stajyer kayıtlarına da yansır
+            if old != name {
+                rename_in_interns(conn, kind, &old, name)?;
+            }
+            id
+        }
+        None => {
+            conn.execute(&format!("INSERT INTO {t} (name, name_key) VALUES (?1, ?2)"), params![name, key])
+                .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+    for alias in &p.aliases {
+        add_alias(conn, kind, id, alias)?;
+    }
+    Ok(id)
+}
+
+/// Stajyer kayıtlarındaki değerler ve referans adları/takma adları arasından
+/// aynı kurumu gösterdiği tahmin edilen grupları önerir.
+pub(crate) fn suggest_merges_conn(conn: &Connection, kind: RefKind) -> Result<Vec<MergeGroup>, String> {
+    let counts = intern_counts(conn, kind)?;
+    let refs = list_conn(conn, kind)?;
+
+    // değer → (anahtar, stajyer sayısı, referans adı mı)
+    let mut values: BTreeMap<String, (String, i64, bool)> = BTreeMap::new();
+    for (value, n) in &counts {
+        values.insert(value.clone(), (match_key(value), *n, false));
+    }
+    for r in &refs {
+        values.entry(r.name.clone()).or_insert_with(|| (match_key(&r.name), 0, false)).2 = true;
+    }
+    let items: Vec<(String, String, i64, bool)> =
+        values.into_iter().map(|(v, (k, n, is_ref))| (v, k, n, is_ref)).collect();
+
+    // takma adlar ilgili referans adının anahtarına katılır
+    let mut alias_of: HashMap<String, String> = HashMap::new();
+    for r in &refs {
+        for a in &r.aliases {
+            alias_of.insert(match_key(a), match_key(&r.name));
+        }
+    }
+    let key_of = |k: &str| alias_of.get(k).cloned().unwrap_or_else(|| k.to_string());
+
+    // union-find
+    let mut parent: Vec<usize> = (0..items.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut i = i;
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for i in 0..items.len() {
+        for j in (i + 1)..items.len() {
+            if similar(&key_of(&items[i].1), &key_of(&items[j].1)) {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..items.len() {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+
+    let mut out = Vec::new();
+    for members in groups.into_values() {
+        if members.len() < 2 {
+            continue;
+        }
+        let mut variants: Vec<MergeVariant> = members
+            .iter()
+            .map(|&i| MergeVariant { value: items[i].0.clone(), intern_count: items[i].2, is_reference: items[i].3 })
+            .collect();
+        // referans adı > en çok kullanılan > Türkçe yazılmış > en uzun
+        let turkish = |v: &str| v.chars().filter(|c| !c.is_ascii()).count();
+        variants.sort_by(|a, b| {
+            b.is_reference
+                .cmp(&a.is_reference)
+                .then(b.intern_count.cmp(&a.intern_count))
+                .then(turkish(&b.value).cmp(&turkish(&a.value)))
+                .then(b.value.chars().count().cmp(&a.value.chars().count()))
+                .then(a.value.cmp(&b.value))
+        });
+        out.push(MergeGroup { canonical: variants[0].value.clone(), variants });
+    }
+    out.sort_by(|a, b| a.canonical.cmp(&b.canonical));
+    Ok(out)
+}
+
+/// `variants` değerlerini `canonical` altında birleştirir: referans kaydı yoksa
+/// oluşturur, varyantları takma ad yapar ve stajyer kayıtlarını günceller.
+pub(crate) fn merge_conn(conn: &Connection, kind: RefKind, canonical: &str, variants: &[String]) -> Result<MergeResult, String> {
+    let canonical = canonical.trim();
+    let key = match_key(canonical);
+    if key.is_empty() {
+        return Err("Kanonik ad boş olamaz".to_string());
+    }
+    let (t, a) = (kind.table(), kind.alias_table());
+
+    let ref_id = match conn
+        .query_row(&format!("SELECT id FROM {t} WHERE name_key = ?1"), params![key], |r| r.get::<_, i64>(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+    {
+        Some(id) => {
+            conn.execute(&format!("UPDATE {t} SET name = ?1 WHERE id = ?2"), params![canonical, id])
+                .map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            // kanonik ad başka bir kaydın takma adıysa takma adı bırak
+            conn.execute(&format!("DELETE FROM {a} WHERE alias_key = ?1"), params![key])
+                .map_err(|e| e.to_string())?;
+            conn.execute(&format!("INSERT INTO {t} (name, name_key) VALUES (?1, ?2)"), params![canonical, key])
+                .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    let mut updated = 0;
+    for v in variants {
+        let v = v.trim();
+        let vkey = match_key(v);
+        if vkey.is_empty() {
+            continue;
+        }
+        if vkey != key {
+            // varyant ayrı bir referans kaydıysa takma adlarıyla birlikte taşı
+            let other: Option<i64> = conn
+                .query_row(&format!("SELECT id FROM {t} WHERE name_key = ?1"), params![vkey], |r| r.get(0))
+                .optional()
+                .map_err(|e| e.to_string())?;
+            if let Some(other) = other.filter(|o| *o != ref_id) {
+                conn.execute(&format!("UPDATE {a} SET ref_id = ?1 WHERE ref_id = ?2"), params![ref_id, other])
+                    .map_err(|e| e.to_string())?;
+                conn.execute(&format!("DELETE FROM {t} WHERE id = ?1"), params![other])
+                    .map_err(|e| e.to_string())?;
+            }
+            conn.execute(
+                &format!("INSERT OR REPLACE INTO {a} (alias_key, alias, ref_id) VALUES (?1, ?2, ?3)"),
+                params![vkey, v, ref_id],
+            ).map_err(|e| e.to_string())?;
+        }
+        updated += rename_in_interns(conn, kind, v, canonical)?;
+    }
+    Ok(MergeResult { reference_id: ref_id, interns_updated: updated })
+}
+
+/// Stajyer kayıtlarında geçen ve henüz eşleşmeyen her değer için referans
+/// kaydı açar (doğrulama açılmadan önce mevcut veriyi içeri almak için).
+pub(crate) fn seed_conn(conn: &Connection, kind: RefKind) -> Result<usize, String> {
+    let mut values: Vec<String> = intern_counts(conn, kind)?.into_keys().collect();
+    values.sort();
+    let mut created = 0;
+    for v in values {
+        let key = match_key(&v);
+        if key.is_empty() || lookup(conn, kind, &key)?.is_some() {
+            continue;
+        }
+        conn.execute(&format!("INSERT INTO {} (name, name_key) VALUES (?1, ?2)", kind.table()), params![v.trim(), key])
+            .map_err(|e| e.to_string())?;
+        created += 1;
+    }
+    Ok(created)
+}
+
+#[tauri::command]
+pub fn list_reference(handle: AppHandle, kind: RefKind) -> Result<Vec<RefEntry>, String> {
+    let conn = open_conn(&handle)?;
+    list_conn(&conn, kind)
+}
+
+#[tauri::command]
+pub fn create_reference(handle: AppHandle, kind: RefKind, entry: RefPayload) -> Result<i64, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let id = save_entry(&tx, kind, None, &entry)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn update_reference(handle: AppHandle, kind: RefKind, id: i64, entry: RefPayload) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    save_entry(&tx, kind, Some(id), &entry)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// Kullanımdaki kayıt silinemez (önce birleştirilmeli)
+#[tauri::command]
+pub fn delete_reference(handle: AppHandle, kind: RefKind, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let in_use: i64 = conn.query_row(
+        &format!(
+            "SELECT COUNT(*) FROM interns WHERE {col} = (SELECT name FROM {t} WHERE id = ?1)",
+            col = kind.column(),
+            t = kind.table()
+        ),
+        params![id],
+        |r| r.get(0),
+    ).map_err(|e| e.to_string())?;
+    if in_use > 0 {
+        return Err(format!("Bu {} {in_use} stajyer kaydında kullanılıyor", kind.label()));
+    }
+    conn.execute(&format!("DELETE FROM {} WHERE id = ?1", kind.table()), params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn seed_reference(handle: AppHandle, kind: RefKind) -> Result<usize, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let created = seed_conn(&tx, kind)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(created)
+}
+
+#[tauri::command]
+pub fn suggest_reference_merges(handle: AppHandle, kind: RefKind) -> Result<Vec<MergeGroup>, String> {
+    let conn = open_conn(&handle)?;
+    suggest_merges_conn(&conn, kind)
+}
+
+#[tauri::command]
+pub fn merge_reference(handle: AppHandle, kind: RefKind, canonical: String, variants: Vec<String>) -> Result<MergeResult, String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let result = merge_conn(&tx, kind, &canonical, &variants)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
@@ -0,0 +1,357 @@
+// Arka planda çalışan hatırlatıcı: yaklaşan / geciken görevler ve o gün not
+// girilmemiş stajyerler için masaüstü bildirimi gönderir. Gönderilen
+// hatırlatmalar sent_reminders tablosunda tutulur; ikinci kez gönderilmez.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate, Timelike, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::calendar::{format_date, WorkCalendar};
+use crate::{attendance, interns_missing_evaluation_conn, mail, open_conn, settings};
+
+const SETTINGS_KEY: &str = "reminders";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReminderSettings {
+    pub enabled: bool,
+    // teslime kaç gün kala hatırlatılacak (0 = teslim günü)
+    pub lead_days: Vec<i64>,
+    pub notify_overdue: bool,
+    // gecikme bildirimi en geç teslim tarihinden bu kadar gün sonrasına kadar;
+    // ilk çalıştırmada eski görevlerin hepsi için bildirim gönderilmez
+    pub overdue_window_days: i64,
+    pub interval_minutes: u64,
+    // iş günlerinde, bu saatten sonra not girilmemiş stajyerleri hatırlat
+    pub missing_evaluation: bool,
+    pub missing_evaluation_hour: u32,
+    // zorunlu staj gününü tamamlayamayacak stajyerler (bu saatten sonra, günde bir kez)
+    pub attendance_risk: bool,
+    pub attendance_risk_hour: u32,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        ReminderSettings {
+            enabled: true,
+            lead_days: vec![3, 1, 0],
+            notify_overdue: true,
+            overdue_window_days: 3,
+            interval_minutes: 60,
+            missing_evaluation: true,
+            missing_evaluation_hour: 16,
+            attendance_risk: true,
+            attendance_risk_hour: 16,
+        }
+    }
+}
+
+// zamanlayıcının son turu (arayüzde gösterilir)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SchedulerStatus {
+    pub last_run_at: Option<String>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+    pub last_notification_error: Option<String>,
+    pub last_notification_error_at: Option<String>,
+    pub last_mail_error: Option<String>,
+    pub last_mail_error_at: Option<String>,
+}
+
+static STATUS: Mutex<SchedulerStatus> = Mutex::new(SchedulerStatus {
+    last_run_at: None,
+    last_error: None,
+    last_error_at: None,
+    last_notification_error: None,
+    last_notification_error_at: None,
+    last_mail_error: None,
+    last_mail_error_at: None,
+});
+
+fn now_utc() -> String {
+    Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PendingReminder {
+    pub kind: &'static str,
+    pub ref_id: i64,
+    pub tag: String,
+    pub title: String,
+    pub body: String,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS sent_reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            ref_id INTEGER NOT NULL,
+            tag TEXT NOT NULL,
+            sent_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (kind, ref_id, tag)
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<ReminderSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+// Günde bir kez: bugün not girilmemiş stajyer sayısı
+pub(crate) fn missing_evaluation_reminder(conn: &Connection, today: NaiveDate) -> Result<Option<PendingReminder>, String> {
+    let cal = WorkCalendar::load(conn)?;
+    let missing = interns_missing_evaluation_conn(conn, &cal, today, today)?;
+    if missing.is_empty() {
+        return Ok(None);
+    }
+    let r = PendingReminder {
+        kind: "missing_evaluation",
+        ref_id: 0,
+        tag: format_date(today),
+        title: "Not Eksik Hatırlatma".to_string(),
+        body: format!("Bugün {} stajyer için not girilmemiş.", missing.len()),
+    };
+    Ok(if already_sent(conn, &r)? { None } else { Some(r) })
+}
+
+// Günde bir kez: devamsızlık nedeniyle zorunlu günü riskte olan stajyerler
+pub(crate) fn attendance_risk_reminder(conn: &Connection, today: NaiveDate) -> Result<Option<PendingReminder>, String> {
+    let at_risk = attendance::warnings_conn(conn, today)?;
+    if at_risk.is_empty() {
+        return Ok(None);
+    }
+    let short = at_risk.iter().filter(|s| s.risk == attendance::RiskLevel::Short).count();
+    let warning = at_risk.len() - short;
+    let body = match (short, warning) {
+        (0, w) => format!("{w} stajyer zorunlu staj gününü tamamlayamama riski taşıyor."),
+        (s, 0) => format!("{s} stajyer zorunlu staj gününü tamamlayamayacak."),
+        (s, w) => format!("{s} stajyer zorunlu staj gününü tamamlayamayacak, {w} stajyer risk altında."),
+    };
+    let r = PendingReminder {
+        kind: "attendance_risk",
+        ref_id: 0,
+        tag: format_date(today),
+        title: "Devam Uyarısı".to_string(),
+        body,
+    };
+    Ok(if already_sent(conn, &r)? { None } else { Some(r) })
+}
+
+pub(crate) fn deadline_reminders(conn: &Connection, s: &ReminderSettings, today: &str) -> Result<Vec<PendingReminder>, String> {
+    let mut leads: Vec<i64> = s.lead_days.iter().copied().filter(|d| *d >= 0).collect();
+    leads.sort_unstable();
+    leads.dedup();
+    let max_lead = leads.last().copied().unwrap_or(-1);
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT a.id, a.project_type, date(a.due_date),
+               i.first_name || ' ' || i.last_name,
+               CAST(julianday(date(a.due_date)) - julianday(?1) AS INTEGER) AS days_left
+        FROM assignments a
+        JOIN interns i ON i.id = a.intern_id
+        WHERE a.status <> 'Completed'
+          AND date(a.due_date) IS NOT NULL
+          AND julianday(date(a.due_date)) - julianday(?1) BETWEEN -?3 - 1 AND ?2
+        ORDER BY a.due_date, a.id
+        "#
+    ).map_err(|e| e.to_string())?;
+
+    let window = if s.notify_overdue { s.overdue_window_days.max(0) } else { -1 };
+    let rows = stmt.query_map(params![today, max_lead, window], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, i64>(4)?,
+        ))
+    }).map_err(|e| e.to_string())?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        let (id, project, due, intern, days_left) = r.map_err(|e| e.to_string())?;
+        let reminder = if days_left < 0 {
+            if !s.notify_overdue { continue; }
+            PendingReminder {
+                kind: "deadline",
+                ref_id: id,
+                tag: format!("{due}:overdue"),
+                title: "Geciken Görev".to_string(),
+                body: format!("{intern}: {project} teslim tarihi {} gün geçti.", -days_left),
+            }
+        } else {
+            // kalan güne en yakın (>=) hatırlatma eşiği; her eşik bir kez
+            let Some(lead) = leads.iter().copied().find(|l| *l >= days_left) else { continue };
+            let body = if days_left == 0 {
+                format!("{intern}: {project} bugün teslim edilmeli.")
+            } else {
+                format!("{intern}: {project} teslimine {days_left} gün kaldı.")
+            };
+            PendingReminder {
+                kind: "deadline",
+                ref_id: id,
+                tag: format!("{due}:{lead}"),
+                title: "Görev Hatırlatma".to_string(),
+                body,
+            }
+        };
+        if !already_sent(conn, &reminder)? {
+            out.push(reminder);
+        }
+    }
+    Ok(out)
+}
+
+fn already_sent(conn: &Connection, r: &PendingReminder) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sent_reminders WHERE kind = ?1 AND ref_id = ?2 AND tag = ?3)",
+        params![r.kind, r.ref_id, r.tag],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// Hatırlatmayı gönderilmiş olarak işaretler; başkası önce davrandıysa false
+fn claim(conn: &Connection, r: &PendingReminder) -> Result<bool, String> {
+    let n = conn.execute(
+        "INSERT OR IGNORE INTO sent_reminders (kind, ref_id, tag) VALUES (?1, ?2, ?3)",
+        params![r.kind, r.ref_id, r.tag],
+    ).map_err(|e| e.to_string())?;
+    Ok(n == 1)
+}
+
+fn release(conn: &Connection, r: &PendingReminder) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM sent_reminders WHERE kind = ?1 AND ref_id = ?2 AND tag = ?3",
+        params![r.kind, r.ref_id, r.tag],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub(crate) fn deliver(handle: &AppHandle, conn: &Connection, items: Vec<PendingReminder>) -> Result<usize, String> {
+    let mut sent = 0;
+    let mut last_error = None;
+    for r in items {
+        if !claim(conn, &r)? { continue; }
+        match handle.notification().builder().title(&r.title).body(&r.body).show() {
+            Ok(()) => sent += 1,
+            Err(e) => {
+                // bir sonraki turda tekrar denensin
+                release(conn, &r)?;
+                last_error = Some(format!("Bildirim gönderilemedi: {e}"));
+            }
+        }
+    }
+    if let Ok(mut status) = STATUS.lock() {
+        if let Some(e) = last_error {
+            status.last_notification_error = Some(e);
+            status.last_notification_error_at = Some(now_utc());
+        } else if sent > 0 {
+            status.last_notification_error = None;
+        }
+    }
+    Ok(sent)
+}
+
+pub(crate) fn run_checks(handle: &AppHandle) -> Result<usize, String> {
+    let conn = open_conn(handle)?;
+    let s = load_settings(&conn)?;
+    if !s.enabled {
+        return Ok(0);
+    }
+
+    // silinmiş görevlerin kayıtlarını temizle
+    conn.execute(
+        "DELETE FROM sent_reminders WHERE kind = 'deadline' AND ref_id NOT IN (SELECT id FROM assignments)",
+        [],
+    ).map_err(|e| e.to_string())?;
+
+    let now = Local::now();
+    let today = now.date_naive();
+    let mut pending = deadline_reminders(&conn, &s, &format_date(today))?;
+    if s.missing_evaluation && now.hour() >= s.missing_evaluation_hour {
+        pending.extend(missing_evaluation_reminder(&conn, today)?);
+    }
+    if s.attendance_risk && now.hour() >= s.attendance_risk_hour {
+        pending.extend(attendance_risk_reminder(&conn, today)?);
+    }
+    deliver(handle, &conn, pending)
+}
+
+// setup içinde bir kez çağrılır; tur hataları zamanlayıcı durumunda görünür
+// (get_reminder_scheduler_status)
+pub(crate) fn start_scheduler(handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        let checks = run_checks(&handle);
+        let mail = mail::run(&handle);
+        if let Ok(mut status) = STATUS.lock() {
+            let now = now_utc();
+            match checks {
+                Ok(_) => status.last_error = None,
+                Err(e) => {
+                    status.last_error = Some(e);
+                    status.last_error_at = Some(now.clone());
+                }
+            }
+            match mail {
+                Ok(_) => status.last_mail_error = None,
+                Err(e) => {
+                    status.last_mail_error = Some(e);
+                    status.last_mail_error_at = Some(now.clone());
+                }
+            }
+            status.last_run_at = Some(now);
+        }
+        let minutes = open_conn(&handle)
+            .and_then(|conn| load_settings(&conn))
+            .map(|s| s.interval_minutes)
+            .unwrap_or(60)
+            .max(1);
+        std::thread::sleep(Duration::from_secs(minutes * 60));
+    });
+}
+
+#[tauri::command]
+pub fn get_reminder_settings(handle: AppHandle) -> Result<ReminderSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_reminder_settings(handle: AppHandle, settings: ReminderSettings) -> Result<(), String> {
+    if settings.lead_days.iter().any(|d| !(0..=90).contains(d)) {
+        return Err("Hatırlatma günleri 0 ile 90 arasında olmalı".to_string());
+    }
+    if settings.missing_evaluation_hour > 23 || settings.attendance_risk_hour > 23 {
+        return Err("Hatırlatma saati 0 ile 23 arasında olmalı".to_string());
+    }
+    if !(0..=30).contains(&settings.overdue_window_days) {
+        return Err("Gecikme bildirimi süresi 0 ile 30 gün arasında olmalı".to_string());
+    }
+    if !(1..=1440).contains(&settings.interval_minutes) {
+        return Err("Kontrol aralığı 1 ile 1440 dakika arasında olmalı".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
+
+// Zamanlayıcıyı beklemeden kontrol et; gönderilen bildirim sayısını döner
+#[tauri::command]
+pub fn run_reminders_now(handle: AppHandle) -> Result<usize, String> {
+    run_checks(&handle)
+}
+
+#[tauri::command]
+pub fn get_reminder_scheduler_status() -> Result<SchedulerStatus, String> {
+    STATUS.lock().map(|s| s.clone()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,313 @@
+// Staj sonu değerlendirme raporu (PDF): profil + fotoğraf, özet istatistikler,
+// puan grafiği, görevler ve değerlendirmeler tabloları.
+
+use std::fs;
+
+use chrono::Local;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::analytics::{score_series_conn, stats, ScorePoint};
+use crate::calendar::{parse_date, WorkCalendar};
+use crate::certificate::{display_date, load_template};
+use crate::pdf::{self, Align, Column, Flow, Font, PdfDocument, Style, BLACK, GRAY, LIGHT_GRAY};
+use crate::{open_conn, person_dir};
+
+// hareketli ortalama penceresi
+const MOVING_WINDOW: usize = 3;
+
+#[derive(Debug, Serialize)]
+pub struct ReportResult {
+    pub intern_id: i64,
+    pub file_path: String,
+    pub bytes: usize,
+}
+
+struct ReportIntern {
+    first_name: String,
+    last_name: String,
+    school: String,
+    department: String,
+    start_date: String,
+    end_date: Option<String>,
+    status: String,
+    contact: String,
+    email: String,
+    photo: Option<Vec<u8>>,
+}
+
+struct ReportAssignment {
+    project_type: String,
+    task_description: String,
+    due_date: String,
+    status: String,
+}
+
+fn load_intern(conn: &Connection, id: i64) -> Result<ReportIntern, String> {
+    let intern = conn.query_row(
+        r#"
+        SELECT first_name, last_name, school, department, start_date, end_date,
+               status, contact, email, photo_blob, photo_path
+        FROM interns WHERE id = ?1
+        "#,
+        params![id],
+        |r| {
+            let blob: Option<Vec<u8>> = r.get(9)?;
+            let path: Option<String> = r.get(10)?;
+            Ok((
+                ReportIntern {
+                    first_name: r.get(0)?,
+                    last_name: r.get(1)?,
+                    school: r.get(2)?,
+                    department: r.get(3)?,
+                    start_date: r.get(4)?,
+                    end_date: r.get(5)?,
+                    status: r.get(6)?,
+                    contact: r.get(7)?,
+                    email: r.get(8)?,
+                    photo: blob,
+                },
+                path,
+            ))
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())?;
+
+    let (mut intern, path) = intern.ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    // BLOB yoksa diskteki kopya
+    if intern.photo.is_none() {
+        intern.photo = path.and_then(|p| fs::read(p).ok());
+    }
+    Ok(intern)
+}
+
+fn load_assignments(conn: &Connection, intern_id: i64) -> Result<Vec<ReportAssignment>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT project_type, task_description, due_date, status FROM assignments WHERE intern_id = ?1 ORDER BY due_date, id"
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![intern_id], |r| {
+        Ok(ReportAssignment {
+            project_type: r.get(0)?,
+            task_description: r.get(1)?,
+            due_date: r.get(2)?,
+            status: r.get(3)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    for r in rows { out.push(r.map_err(|e| e.to_string())?); }
+    Ok(out)
+}
+
+fn fmt_score(x: f64) -> String {
+    format!("{x:.1}").replace('.', ",")
+}
+
+// Puanlar (siyah) ve hareketli ortalama (gri), 0-100 ekseni
+fn score_chart(flow: &mut Flow, points: &[ScorePoint]) {
+    let h = 150.0;
+    flow.ensure(h + 30.0);
+    let (left, width) = (flow.left + 24.0, flow.width() - 24.0);
+    let bottom = flow.y - h;
+    let small = Style::new(7.0, Font::Regular).color(GRAY);
+    let page = flow.page();
+
+    for v in [0, 25, 50, 75, 100] {
+        let y = bottom + h * v as f32 / 100.0;
+        page.line(left, y, left + width, y, 0.3, LIGHT_GRAY);
+        page.text_at(left - 4.0, y - 2.5, small, Align::Right, &v.to_string());
+    }
+    let step = if points.len() > 1 { width / (points.len() - 1) as f32 } else { 0.0 };
+    let at = |i: usize, v: f64| (left + step * i as f32, bottom + h * (v.clamp(0.0, 100.0) as f32) / 100.0);
+    for pair in points.windows(2).enumerate() {
+        let (i, w) = pair;
+        let (x1, y1) = at(i, w[0].moving_average);
+        let (x2, y2) = at(i + 1, w[1].moving_average);
+        page.line(x1, y1, x2, y2, 1.0, GRAY);
+        let (x1, y1) = at(i, w[0].score as f64);
+        let (x2, y2) = at(i + 1, w[1].score as f64);
+        page.line(x1, y1, x2, y2, 1.4, BLACK);
+    }
+    for (i, p) in points.iter().enumerate() {
+        let (x, y) = at(i, p.score as f64);
+        page.rect(x - 1.8, y - 1.8, 3.6, 3.6, Some(BLACK), None);
+    }
+    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+        page.text_at(left, bottom - 10.0, small, Align::Left, &display_date(&first.date));
+        page.text_at(left + width, bottom - 10.0, small, Align::Right, &display_date(&last.date));
+    }
+    page.text_at(left + width / 2.0, bottom - 10.0, small, Align::Center,
+        &format!("Puan (siyah) / {MOVING_WINDOW} değerlendirmelik ortalama (gri)"));
+    flow.space(h + 24.0);
+}
+
+pub(crate) fn render_report(conn: &Connection, intern_id: i64, today: &str) -> Result<Vec<u8>, String> {
+    let intern = load_intern(conn, intern_id)?;
+    let assignments = load_assignments(conn, intern_id)?;
+    let series = score_series_conn(conn, intern_id, MOVING_WINDOW)?;
+    let organization = load_template(conn)?.organization_name;
+    let full_name = format!("{} {}", intern.first_name, intern.last_name);
+
+    let end_date = intern.end_date.clone().filter(|d| !d.trim().is_empty());
+    let working_days = match (parse_date(&intern.start_date), end_date.as_deref().map(parse_date)) {
+        (Ok(start), Some(Ok(end))) if end >= start => Some(WorkCalendar::load(conn)?.count_working_days(start, end)),
+        _ => None,
+    };
+
+    let mut doc = PdfDocument::new(pdf::A4_PORTRAIT, &format!("Staj Değerlendirme Raporu - {full_name}"));
+    let photo = intern.photo.as_deref().and_then(|bytes| doc.add_image(bytes).ok());
+    let mut flow = Flow::new(&doc, 50.0, &format!("{full_name} · {}", display_date(today)));
+
+    // başlık
+    let (left, right) = (flow.left, flow.right);
+    flow.space(18.0);
+    let y = flow.y;
+    flow.page().text_at(left, y, Style::new(18.0, Font::Bold), Align::Left, "Staj Değerlendirme Raporu");
+    if !organization.is_empty() {
+        flow.page().text_at(right, y, Style::new(10.0, Font::Regular).color(GRAY), Align::Right, &organization);
+    }
+    flow.space(28.0);
+
+    // profil: solda alanlar, sağda fotoğraf
+    flow.heading("Stajyer Bilgileri");
+    let top = flow.y;
+    let (photo_w, photo_h) = (90.0, 115.0);
+    if let Some(img) = photo {
+        flow.page().image_fit(img, right - photo_w, top - photo_h, photo_w, photo_h);
+    }
+    let period = format!(
+        "{} – {}",
+        display_date(&intern.start_date),
+        end_date.as_deref().map(display_date).unwrap_or_else(|| "devam ediyor".to_string())
+    );
+    let mut fields = vec![
+        ("Ad Soyad", full_name.clone()),
+        ("Okul", intern.school.clone()),
+        ("Bölüm", intern.department.clone()),
+        ("Staj dönemi", period),
+        ("Durum", intern.status.clone()),
+        ("Telefon", intern.contact.clone()),
+        ("E-posta", intern.email.clone()),
+    ];
+    if let Some(days) = working_days {
+        fields.push(("İş günü", days.to_string()));
+    }
+    // fotoğraf sütununa taşmasın diye sağ kenarı geçici olarak daralt
+    flow.right = right - if photo.is_some() { photo_w + 16.0 } else { 0.0 };
+    flow.fields(left, 90.0, &fields);
+    flow.right = right;
+    if photo.is_some() {
+        flow.y = flow.y.min(top - photo_h);
+    }
+    flow.space(16.0);
+
+    // özet
+    let scores: Vec<i64> = series.iter().map(|p| p.score).collect();
+    let s = stats(&scores);
+    let completed = assignments.iter().filter(|a| a.status == "Completed").count();
+    let overdue = assignments
+        .iter()
+        .filter(|a| a.status != "Completed" && a.due_date.get(..10).is_some_and(|d| d < today))
+        .count();
+    flow.heading("Özet");
+    let mut summary = vec![
+        ("Görevler", format!("{} görev, {completed} tamamlandı, {overdue} gecikmiş", assignments.len())),
+        ("Değerlendirme", format!("{} kayıt", s.count)),
+    ];
+    if s.count > 0 {
+        summary.push(("Ortalama", fmt_score(s.mean)));
+        summary.push(("Medyan", fmt_score(s.median)));
+        summary.push(("En düşük / yüksek", format!("{} / {}", s.min, s.max)));
+        summary.push(("Çeyrekler", format!("{} – {}", fmt_score(s.p25), fmt_score(s.p75))));
+        if let (Some(first), Some(last)) = (series.first(), series.last()) {
+            summary.push(("Gelişim", format!("{:+} puan (ilk ve son değerlendirme arası)", last.score - first.score)));
+        }
+    }
+    flow.fields(left, 110.0, &summary);
+    flow.space(16.0);
+
+    if series.len() > 1 {
+        flow.heading("Puan Gelişimi");
+        score_chart(&mut flow, &series);
+    }
+
+    flow.heading("Görevler");
+    if assignments.is_empty() {
+        flow.paragraph(Style::new(10.0, Font::Italic).color(GRAY), "Kayıtlı görev yok.");
+    } else {
+        let rows: Vec<Vec<String>> = assignments
+            .iter()
+            .map(|a| vec![
+                a.project_type.clone(),
+                a.task_description.clone(),
+                display_date(&a.due_date),
+                a.status.clone(),
+            ])
+            .collect();
+        flow.table(&[
+            Column { title: "Proje", width: 0.22, align: Align::Left },
+            Column { title: "Açıklama", width: 0.48, align: Align::Left },
+            Column { title: "Teslim", width: 0.14, align: Align::Center },
+            Column { title: "Durum", width: 0.16, align: Align::Left },
+        ], &rows);
+    }
+    flow.space(16.0);
+
+    flow.heading("Değerlendirmeler");
+    if series.is_empty() {
+        flow.paragraph(Style::new(10.0, Font::Italic).color(GRAY), "Kayıtlı değerlendirme yok.");
+    } else {
+        let rows: Vec<Vec<String>> = series
+            .iter()
+            .map(|p| vec![
+                display_date(&p.date),
+                p.label.clone(),
+                p.score.to_string(),
+                fmt_score(p.moving_average),
+            ])
+            .collect();
+        flow.table(&[
+            Column { title: "Tarih", width: 0.16, align: Align::Left },
+            Column { title: "Başlık", width: 0.54, align: Align::Left },
+            Column { title: "Puan", width: 0.12, align: Align::Right },
+            Column { title: "Ortalama", width: 0.18, align: Align::Right },
+        ], &rows);
+    }
+
+    // imza alanı
+    flow.ensure(90.0);
+    flow.space(60.0);
+    let y = flow.y;
+    let label = Style::new(9.0, Font::Regular).color(GRAY);
+    let page = flow.page();
+    page.line(left, y, left + 180.0, y, 0.6, BLACK);
+    page.text_at(left + 90.0, y - 12.0, label, Align::Center, "Staj Sorumlusu (Ad Soyad / İmza)");
+    page.line(right - 180.0, y, right, y, 0.6, BLACK);
+    page.text_at(right - 90.0, y - 12.0, label, Align::Center, "Tarih / Kaşe");
+
+    flow.finish(&mut doc);
+    doc.to_bytes()
+}
+
+// output_path verilmezse stajyer klasörüne yazılır
+#[tauri::command]
+pub fn generate_intern_report(handle: AppHandle, intern_id: i64, output_path: Option<String>) -> Result<ReportResult, String> {
+    let conn = open_conn(&handle)?;
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let bytes = render_report(&conn, intern_id, &today)?;
+
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p.into(),
+        None => {
+            let (first, last): (String, String) = conn.query_row(
+                "SELECT first_name, last_name FROM interns WHERE id = ?1",
+                params![intern_id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            ).map_err(|e| e.to_string())?;
+            person_dir(&handle, intern_id, &first, &last)?.join(format!("staj_raporu_{today}.pdf"))
+        }
+    };
+    fs::write(&path, &bytes).map_err(|e| format!("Rapor yazılamadı: {e}"))?;
+    Ok(ReportResult { intern_id, file_path: path.to_string_lossy().to_string(), bytes: bytes.len() })
+}
@@ -0,0 +1,325 @@
+// FTS5 tabanlı arama: stajyerler, görevler ve değerlendirme etiketleri.
+// İndekste metinler Türkçe harfleri katlanmış olarak tutulur; ham metin
+// UNINDEXED kolonlarda durur ve vurgulama Rust tarafında yapılır.
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{fold_tr, fold_tr_char, open_conn};
+
+const DEFAULT_GROUP_LIMIT: i64 = 20;
+const SNIPPET_CHARS: usize = 160;
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    pub id: i64,
+    pub intern_id: i64,
+    pub intern_name: String,
+    pub title: String,
+    // HTML-escape edilmiş, eşleşmeler <mark> ile işaretli
+    pub title_html: String,
+    pub snippet_html: String,
+    pub rank: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SearchResults {
+    pub interns: Vec<SearchHit>,
+    pub assignments: Vec<SearchHit>,
+    pub evaluations: Vec<SearchHit>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    // grup başına en fazla sonuç
+    pub limit: Option<i64>,
+}
+
+// Türkçe harfler SQLite'ın yerleşik replace() zinciriyle katlanır; büyük/küçük
+// harf ve diğer aksanları FTS5 tokenizer'ı (remove_diacritics) halleder. Böylece
+// tetikleyiciler uygulamaya kayıtlı bir SQL fonksiyonu gerektirmez ve
+// veritabanına başka bir bağlantıdan (sqlite3, tauri-plugin-sql) yazmak da çalışır.
+const TR_CHARS: &str = "çÇğĞıIİöÖşŞüÜ";
+
+fn sql_fold(expr: &str) -> String {
+    TR_CHARS.chars().fold(expr.to_string(), |acc, ch| {
+        format!("replace({acc}, '{ch}', '{}')", fold_tr_char(ch).unwrap_or(ch))
+    })
+}
+
+// İndekslenen tablolar. İfadelerdeki {p}, tetikleyicide "new." olur.
+struct Source {
+    kind: &'static str,
+    table: &'static str,
+    // bu kolonlar değişince satır yeniden indekslenir
+    watched: &'static str,
+    intern_id: &'static str,
+    title: &'static str,
+    body: &'static str,
+    title_raw: &'static str,
+    body_raw: &'static str,
+}
+
+const SOURCES: &[Source] = &[
+    Source {
+        kind: "intern",
+        table: "interns",
+        watched: "first_name, last_name, school, department, email",
+        intern_id: "{p}id",
+        title: "{p}first_name || ' ' || {p}last_name",
+        body: "{p}school || ' ' || {p}department || ' ' || {p}email",
+        title_raw: "{p}first_name || ' ' || {p}last_name",
+        body_raw: "{p}school || ' · ' || {p}department || ' · ' || {p}email",
+    },
+    Source {
+        kind: "assignment",
+        table: "assignments",
+        watched: "intern_id, project_type, task_description",
+        intern_id: "{p}intern_id",
+        title: "{p}project_type",
+        body: "{p}task_description",
+        title_raw: "{p}project_type",
+        body_raw: "{p}task_description",
+    },
+    Source {
+        kind: "evaluation",
+        table: "evaluations",
+        watched: "intern_id, label",
+        intern_id: "{p}intern_id",
+        title: "{p}label",
+        body: "''",
+        title_raw: "{p}label",
+        body_raw: "''",
+    },
+];
+
+impl Source {
+    // search_index'e eklenecek değerler; prefix "new." ya da ""
+    fn values(&self, prefix: &str) -> String {
+        let e = |x: &str| x.replace("{p}", prefix);
+        format!(
+            "'{}', {prefix}id, {}, {}, {}, {}, {}",
+            self.kind,
+            e(self.intern_id),
+            sql_fold(&e(self.title)),
+            sql_fold(&e(self.body)),
+            e(self.title_raw),
+            e(self.body_raw)
+        )
+    }
+}
+
+pub(crate) fn ensure_search_index(conn: &Connection) -> Result<(), String> {
+    let exists: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'search_index'",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    conn.execute_batch(
+        r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+            kind UNINDEXED,
+            ref_id UNINDEXED,
+            intern_id UNINDEXED,
+            title,
+            body,
+            title_raw UNINDEXED,
+            body_raw UNINDEXED,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut sql = String::new();
+    for source in SOURCES {
+        let &Source { kind, table, watched, .. } = source;
+        // stajyer silinince ona bağlı tüm satırlar da gider
+        let on_delete = match kind {
+            "intern" => "intern_id = old.id".to_string(),
+            _ => format!("kind = '{kind}' AND ref_id = old.id"),
+        };
+        let values = source.values("new.");
+        sql.push_str(&format!(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS trg_search_{table}_ai AFTER INSERT ON {table} BEGIN
+                INSERT INTO search_index (kind, ref_id, intern_id, title, body, title_raw, body_raw)
+                VALUES ({values});
+            END;
+            CREATE TRIGGER IF NOT EXISTS trg_search_{table}_au AFTER UPDATE OF {watched} ON {table} BEGIN
+                DELETE FROM search_index WHERE kind = '{kind}' AND ref_id = old.id;
+                INSERT INTO search_index (kind, ref_id, intern_id, title, body, title_raw, body_raw)
+                VALUES ({values});
+            END;
+            CREATE TRIGGER IF NOT EXISTS trg_search_{table}_ad AFTER DELETE ON {table} BEGIN
+                DELETE FROM search_index WHERE {on_delete};
+            END;
+            "#
+        ));
+    }
+    conn.execute_batch(&sql).map_err(|e| e.to_string())?;
+
+    // indeks yeni oluşturulduysa mevcut kayıtları doldur
+    if exists == 0 {
+        rebuild_index(conn)?;
+    }
+    Ok(())
+}
+
+// Açık bir işlemin içinden de çağrılabilir (savepoint)
+pub(crate) fn rebuild_index(conn: &Connection) -> Result<(), String> {
+    let mut sql = String::from("DELETE FROM search_index;");
+    for source in SOURCES {
+        sql.push_str(&format!(
+            "INSERT INTO search_index (kind, ref_id, intern_id, title, body, title_raw, body_raw)
+             SELECT {} FROM {};",
+            source.values(""),
+            source.table
+        ));
+    }
+    conn.execute_batch("SAVEPOINT search_rebuild").map_err(|e| e.to_string())?;
+    let result = conn.execute_batch(&sql);
+    if result.is_err() {
+        let _ = conn.execute_batch("ROLLBACK TO search_rebuild");
+    }
+    conn.execute_batch("RELEASE search_rebuild").map_err(|e| e.to_string())?;
+    result.map_err(|e| e.to_string())
+}
+
+// Kullanıcı metnini katlanmış önek terimlerine böler
+fn query_terms(text: &str) -> Vec<String> {
+    fold_tr(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+// Terimlerden biriyle başlayan kelimeleri <mark> ile işaretler. fold_tr
+// karakter sayısını koruduğu için katlanmış metindeki konumlar ham metne
+// birebir uyar. Uzun metinlerde ilk eşleşmenin çevresi kesilir.
+fn highlight(raw: &str, terms: &[String], max_chars: Option<usize>) -> String {
+    let chars: Vec<char> = raw.chars().collect();
+    let folded: Vec<char> = fold_tr(raw).chars().collect();
+
+    let mut marks: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < folded.len() {
+        if !folded[i].is_alphanumeric() { i += 1; continue; }
+        let start = i;
+        while i < folded.len() && folded[i].is_alphanumeric() { i += 1; }
+        let word: String = folded[start..i].iter().collect();
+        if terms.iter().any(|t| word.starts_with(t.as_str())) {
+            marks.push((start, i));
+        }
+    }
+
+    let (from, to) = match max_chars {
+        Some(max) if chars.len() > max => {
+            let first = marks.first().map(|m| m.0).unwrap_or(0);
+            let from = first.saturating_sub(max / 4);
+            (from, (from + max).min(chars.len()))
+        }
+        _ => (0, chars.len()),
+    };
+
+    let mut out = String::new();
+    if from > 0 { out.push('…'); }
+    let mut pos = from;
+    for (s, e) in marks.into_iter().filter(|(s, e)| *e > from && *s < to) {
+        let (s, e) = (s.max(from), e.min(to));
+        out.push_str(&escape_html(&chars[pos..s].iter().collect::<String>()));
+        out.push_str("<mark>");
+        out.push_str(&escape_html(&chars[s..e].iter().collect::<String>()));
+        out.push_str("</mark>");
+        pos = e;
+    }
+    out.push_str(&escape_html(&chars[pos..to].iter().collect::<String>()));
+    if to < chars.len() { out.push('…'); }
+    out
+}
+
+pub(crate) fn search_conn(conn: &Connection, text: &str, opts: &SearchOptions) -> Result<SearchResults, String> {
+    let terms = query_terms(text);
+    if terms.is_empty() {
+        return Ok(SearchResults::default());
+    }
+    let match_expr = terms
+        .iter()
+        .map(|t| format!("\"{t}\"*"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let limit = opts.limit.unwrap_or(DEFAULT_GROUP_LIMIT).clamp(1, 200);
+
+    // başlık eşleşmeleri gövdeye göre daha ağır
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT s.kind, s.ref_id, s.intern_id,
+               COALESCE(i.first_name || ' ' || i.last_name, ''),
+               s.title_raw, s.body_raw,
+               bm25(search_index, 0.0, 0.0, 0.0, 10.0, 1.0, 0.0, 0.0) AS rank
+        FROM search_index s
+        LEFT JOIN interns i ON i.id = s.intern_id
+        WHERE search_index MATCH ?1 AND s.kind = ?2
+        ORDER BY rank
+        LIMIT ?3
+        "#
+    ).map_err(|e| e.to_string())?;
+
+    let mut results = SearchResults::default();
+    for kind in ["intern", "assignment", "evaluation"] {
+        let rows = stmt.query_map(params![match_expr, kind, limit], |row| {
+            let title: String = row.get(4)?;
+            let body: String = row.get(5)?;
+            Ok(SearchHit {
+                id: row.get(1)?,
+                intern_id: row.get(2)?,
+                intern_name: row.get(3)?,
+                title_html: highlight(&title, &terms, None),
+                snippet_html: highlight(&body, &terms, Some(SNIPPET_CHARS)),
+                title,
+                rank: row.get(6)?,
+            })
+        }).map_err(|e| e.to_string())?;
+
+        let group = match kind {
+            "intern" => &mut results.interns,
+            "assignment" => &mut results.assignments,
+            _ => &mut results.evaluations,
+        };
+        for r in rows { group.push(r.map_err(|e| e.to_string())?); }
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+pub fn search(handle: AppHandle, text: String, options: Option<SearchOptions>) -> Result<SearchResults, String> {
+    let conn = open_conn(&handle)?;
+    search_conn(&conn, &text, &options.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn rebuild_search_index(handle: AppHandle) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    rebuild_index(&conn)
+}
@@ -0,0 +1,43 @@
+// Uygulama ayarları: anahtar başına JSON değer (hatırlatıcı süreleri vb.)
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+// Kayıt yoksa varsayılan döner
+pub(crate) fn load<T: DeserializeOwned + Default>(conn: &Connection, key: &str) -> Result<T, String> {
+    let raw: Option<String> = conn
+        .query_row("SELECT value FROM app_settings WHERE key = ?1", params![key], |r| r.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match raw {
+        Some(raw) => serde_json::from_str(&raw).map_err(|e| format!("Ayar okunamadı ({key}): {e}")),
+        None => Ok(T::default()),
+    }
+}
+
+pub(crate) fn store<T: Serialize>(conn: &Connection, key: &str, value: &T) -> Result<(), String> {
+    let raw = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    conn.execute(
+        r#"
+        INSERT INTO app_settings (key, value) VALUES (?1, ?2)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')
+        "#,
+        params![key, raw],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,1037 @@
+// Kurulumlar arası iki yönlü eşitleme (ör. iki ofis). Stajyer, görev ve
+// değerlendirmeler tamsayı kimliğin yanında kalıcı bir uuid taşır. Her yerel
+// değişiklik tetikleyicilerle sync_rows'ta satırın sürümünü artırır ve yerel
+// bir sıra numarası (seq) alır. Değişiklik setleri bir JSON dosyasıyla ya da
+// ortak klasördeki <site_id>.itsync.json dosyalarıyla taşınır; her set karşı
+// tarafın aldığını bildirdiği sıradan sonraki değişiklikleri içerir.
+//
+// Çakışma: iki kurulum aynı satırı son eşitlemeden sonra değiştirdiyse. Kazanan
+// her iki tarafta aynı seçilir: yüksek sürüm, sonra geç updated_at, sonra büyük
+// site_id. Kaybeden veri sync_conflicts'e yazılır; resolve_sync_conflict ile
+// o taraf yeni bir değişiklik olarak geri alınabilir.
+//
+// Ekler (CV, fotoğraf), mentorlar ve dönemler eşitlenmez: dönem adıyla,
+// değerlendiren mentor e-posta ya da adıyla yerel kayda eşlenir.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::{current_os_user, delete_assignment_conn, delete_evaluation_conn, delete_intern_conn, events, open_conn, settings};
+
+const SETTINGS_KEY: &str = "sync";
+// bu veritabanında uygulanan eşitleme şeması (uuid doldurma, tetikleyiciler)
+pub(crate) const SCHEMA_KEY: &str = "sync_schema";
+// 2: değer değişmeyen güncellemeler sürümü artırmaz
+const SCHEMA_VERSION: i64 = 2;
+const FORMAT: &str = "interntracker-sync";
+const FORMAT_VERSION: u32 = 1;
+const FILE_SUFFIX: &str = ".itsync.json";
+
+// rastgele (v4) uuid
+const UUID_SQL: &str = "lower(hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' || \
+     substr(hex(randomblob(2)), 2) || '-' || substr('89ab', 1 + (abs(random()) % 4), 1) || \
+     substr(hex(randomblob(2)), 2) || '-' || hex(randomblob(6)))";
+// UTC, milisaniyeli; metin olarak sıralanabilir
+const NOW_SQL: &str = "strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Entity {
+    Intern,
+    Assignment,
+    Evaluation,
+}
+
+impl Entity {
+    // üst kayıtlar önce uygulanır
+    const ALL: [Entity; 3] = [Entity::Intern, Entity::Assignment, Entity::Evaluation];
+
+    fn key(self) -> &'static str {
+        match self {
+            Entity::Intern => "intern",
+            Entity::Assignment => "assignment",
+            Entity::Evaluation => "evaluation",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            Entity::Intern => "interns",
+            Entity::Assignment => "assignments",
+            Entity::Evaluation => "evaluations",
+        }
+    }
+
+    // sürümü artıran kolonlar (dosya yolları gibi yerel alanlar hariç)
+    fn tracked_columns(self) -> &'static str {
+        match self {
+            Entity::Intern => {
+                "first_name, last_name, school, department, start_date, end_date, status, contact, email, \
+                 period_id, required_days"
+            }
+            Entity::Assignment => "intern_id, project_type, task_description, due_date, status",
+            Entity::Evaluation => "intern_id, label, score, evaluator_id",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Entity> {
+        Entity::ALL.into_iter().find(|e| e.key() == s)
+    }
+
+    fn rank(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    // bu kurulumun kimliği (ilk kullanımda üretilir, değiştirilemez)
+    pub site_id: String,
+    // karşı tarafta görünen ad (ör. "Ankara ofisi")
+    pub site_name: String,
+    // sync_now için ortak klasör
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Change {
+    entity: Entity,
+    uuid: String,
+    version: i64,
+    // değişikliği yapan kurulum
+    origin: String,
+    updated_at: String,
+    // gönderenin bu satırı en son başka kurulumdan aldığı sürüm
+    base_version: i64,
+    deleted: bool,
+    seq: i64,
+    data: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChangeSet {
+    format: String,
+    format_version: u32,
+    site_id: String,
+    site_name: String,
+    created_at: String,
+    // seq > from_seq olan değişiklikler
+    from_seq: i64,
+    to_seq: i64,
+    // gönderenin her kurulumdan uyguladığı son sıra
+    acks: HashMap<String, i64>,
+    changes: Vec<Change>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncExportReport {
+    pub path: String,
+    pub changes: usize,
+    pub from_seq: i64,
+    pub to_seq: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncImportReport {
+    pub site_id: String,
+    pub site_name: String,
+    pub applied: usize,
+    // zaten bilinen ya da daha eski değişiklikler
+    pub unchanged: usize,
+    // üst kaydı (stajyer) bu kurulumda olmadığı için uygulanamayanlar
+    pub skipped: usize,
+    pub conflicts: usize,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncRunReport {
+    pub imported: Vec<SyncImportReport>,
+    pub exported: SyncExportReport,
+    // okunamayan dosyalar
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncPeer {
+    pub site_id: String,
+    pub name: String,
+    pub received_seq: i64,
+    pub acked_seq: i64,
+    pub last_sync_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncConflict {
+    pub id: i64,
+    pub entity: Entity,
+    pub uuid: String,
+    // değişikliği getiren kurulum
+    pub site_id: String,
+    pub local_version: i64,
+    pub local_data: Option<Value>,
+    pub remote_version: i64,
+    pub remote_origin: String,
+    pub remote_data: Option<Value>,
+    // "local" | "remote"
+    pub winner: String,
+    pub detected_at: String,
+    pub resolved_at: Option<String>,
+    pub resolution: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictChoice {
+    // otomatik seçilen kalsın
+    Keep,
+    Local,
+    Remote,
+}
+
+struct RowMeta {
+    version: i64,
+    // NULL: bu kurulum
+    origin: Option<String>,
+    updated_at: String,
+    base_version: i64,
+    deleted: bool,
+}
+
+enum Decision {
+    Skip,
+    Apply,
+    // iki tarafta da silinmiş; yalnızca sürüm bilgisi alınır
+    MetaOnly,
+    Conflict { remote_wins: bool },
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS sync_rows (
+            entity TEXT NOT NULL,
+            uuid TEXT NOT NULL,
+            version INTEGER NOT NULL,
+            -- NULL: bu kurulumda yapılmış değişiklik
+            origin TEXT,
+            updated_at TEXT NOT NULL,
+            base_version INTEGER NOT NULL DEFAULT 0,
+            deleted INTEGER NOT NULL DEFAULT 0,
+            seq INTEGER NOT NULL,
+            PRIMARY KEY (entity, uuid)
+        );
+        CREATE INDEX IF NOT EXISTS idx_sync_rows_seq ON sync_rows(seq);
+
+        CREATE TABLE IF NOT EXISTS sync_peers (
+            site_id TEXT PRIMARY KEY,
+            name TEXT NOT NULL DEFAULT '',
+            -- karşı taraftan uygulanan son sıra
+            received_seq INTEGER NOT NULL DEFAULT 0,
+            -- karşı tarafın aldığını bildirdiği bizim son sıramız
+            acked_seq INTEGER NOT NULL DEFAULT 0,
+            last_sync_at TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS sync_conflicts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entity TEXT NOT NULL,
+            uuid TEXT NOT NULL,
+            site_id TEXT NOT NULL,
+            local_version INTEGER NOT NULL,
+            local_data TEXT,
+            remote_version INTEGER NOT NULL,
+            remote_origin TEXT NOT NULL,
+            remote_data TEXT,
+            winner TEXT NOT NULL CHECK (winner IN ('local', 'remote')),
+            detected_at TEXT NOT NULL DEFAULT (datetime('now')),
+            resolved_at TEXT,
+            resolution TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_sync_conflicts_open ON sync_conflicts(resolved_at, id);
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+
+    // uuid doldurma ve tetikleyiciler her açılışta değil, sürüm değişince bir kez
+    let done: i64 = settings::load(conn, SCHEMA_KEY)?;
+    if done >= SCHEMA_VERSION {
+        return Ok(());
+    }
+    conn.execute_batch("SAVEPOINT sync_schema").map_err(|e| e.to_string())?;
+    let result = migrate(conn);
+    if result.is_err() {
+        let _ = conn.execute_batch("ROLLBACK TO sync_schema");
+    }
+    conn.execute_batch("RELEASE sync_schema").map_err(|e| e.to_string())?;
+    result
+}
+
+fn migrate(conn: &Connection) -> Result<(), String> {
+    for entity in Entity::ALL {
+        let (t, k) = (entity.table(), entity.key());
+        let cols = entity.tracked_columns();
+        // yalnızca izlenen bir değer gerçekten değiştiyse sürüm artar
+        let changed = cols
+            .split(',')
+            .map(|c| format!("old.{0} IS NOT new.{0}", c.trim()))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        conn.execute_batch(&format!(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_{t}_uuid ON {t}(uuid);
+
+            -- mevcut kayıtlar
+            UPDATE {t} SET uuid = {UUID_SQL} WHERE uuid IS NULL;
+            INSERT INTO sync_rows (entity, uuid, version, updated_at, seq)
+            SELECT '{k}', uuid, 1, {NOW_SQL},
+                   (SELECT COALESCE(MAX(seq), 0) FROM sync_rows) + ROW_NUMBER() OVER (ORDER BY id)
+            FROM {t} WHERE uuid NOT IN (SELECT uuid FROM sync_rows WHERE entity = '{k}');
+
+            DROP TRIGGER IF EXISTS trg_sync_{t}_ai;
+            DROP TRIGGER IF EXISTS trg_sync_{t}_au;
+            DROP TRIGGER IF EXISTS trg_sync_{t}_ad;
+            CREATE TRIGGER trg_sync_{t}_ai AFTER INSERT ON {t} BEGIN
+                UPDATE {t} SET uuid = {UUID_SQL} WHERE id = new.id AND uuid IS NULL;
+                INSERT INTO sync_rows (entity, uuid, version, updated_at, seq)
+                VALUES ('{k}', (SELECT uuid FROM {t} WHERE id = new.id), 1, {NOW_SQL},
+                        (SELECT COALESCE(MAX(seq), 0) + 1 FROM sync_rows))
+                ON CONFLICT (entity, uuid) DO UPDATE SET
+                    version = version + 1, origin = NULL, updated_at = excluded.updated_at,
+                    deleted = 0, seq = excluded.seq;
+            END;
+            CREATE TRIGGER trg_sync_{t}_au AFTER UPDATE OF {cols} ON {t}
+            WHEN old.uuid IS NOT NULL AND ({changed}) BEGIN
+                UPDATE sync_rows SET version = version + 1, origin = NULL, updated_at = {NOW_SQL},
+                    deleted = 0, seq = (SELECT MAX(seq) + 1 FROM sync_rows)
+                WHERE entity = '{k}' AND uuid = new.uuid;
+            END;
+            CREATE TRIGGER trg_sync_{t}_ad AFTER DELETE ON {t}
+            WHEN old.uuid IS NOT NULL BEGIN
+                UPDATE sync_rows SET version = version + 1, origin = NULL, updated_at = {NOW_SQL},
+                    deleted = 1, seq = (SELECT MAX(seq) + 1 FROM sync_rows)
+                WHERE entity = '{k}' AND uuid = old.uuid;
+            END;
+            "#
+        ))
+        .map_err(|e| e.to_string())?;
+    }
+    settings::store(conn, SCHEMA_KEY, &SCHEMA_VERSION)
+}
+
+// Kimlik yoksa üretip kaydeder
+pub(crate) fn load_settings(conn: &Connection) -> Result<SyncSettings, String> {
+    let mut s: SyncSettings = settings::load(conn, SETTINGS_KEY)?;
+    if s.site_id.is_empty() {
+        s.site_id = conn.query_row(&format!("SELECT {UUID_SQL}"), [], |r| r.get(0)).map_err(|e| e.to_string())?;
+        if s.site_name.trim().is_empty() {
+            s.site_name = current_os_user();
+        }
+        settings::store(conn, SETTINGS_KEY, &s)?;
+    }
+    Ok(s)
+}
+
+fn local_id(conn: &Connection, entity: Entity, uuid: &str) -> Result<Option<i64>, String> {
+    conn.query_row(&format!("SELECT id FROM {} WHERE uuid = ?1", entity.table()), params![uuid], |r| r.get(0))
+        .optional()
+        .map_err(|e| e.to_string())
+}
+
+fn row_meta(conn: &Connection, entity: Entity, uuid: &str) -> Result<Option<RowMeta>, String> {
+    conn.query_row(
+        "SELECT version, origin, updated_at, base_version, deleted FROM sync_rows WHERE entity = ?1 AND uuid = ?2",
+        params![entity.key(), uuid],
+        |r| {
+            Ok(RowMeta {
+                version: r.get(0)?,
+                origin: r.get(1)?,
+                updated_at: r.get(2)?,
+                base_version: r.get(3)?,
+                deleted: r.get::<_, i64>(4)? == 1,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+// Değişiklik setindeki satır verisi: yerel kimlikler yerine uuid ve adlar
+fn row_data(conn: &Connection, entity: Entity, uuid: &str) -> Result<Option<Value>, String> {
+    let result = match entity {
+        Entity::Intern => conn.query_row(
+            r#"
+            SELECT i.first_name, i.last_name, i.school, i.department, i.start_date, i.end_date,
+                   i.status, i.contact, i.email, i.required_days, p.name
+            FROM interns i LEFT JOIN periods p ON p.id = i.period_id
+            WHERE i.uuid = ?1
+            "#,
+            params![uuid],
+            |r| {
+                Ok(json!({
+                    "first_name": r.get::<_, String>(0)?,
+                    "last_name": r.get::<_, String>(1)?,
+                    "school": r.get::<_, String>(2)?,
+                    "department": r.get::<_, String>(3)?,
+                    "start_date": r.get::<_, String>(4)?,
+                    "end_date": r.get::<_, Option<String>>(5)?,
+                    "status": r.get::<_, String>(6)?,
+                    "contact": r.get::<_, String>(7)?,
+                    "email": r.get::<_, String>(8)?,
+                    "required_days": r.get::<_, Option<i64>>(9)?,
+                    "period": r.get::<_, Option<String>>(10)?,
+                }))
+            },
+        ),
+        Entity::Assignment => conn.query_row(
+            r#"
+            SELECT i.uuid, a.project_type, a.task_description, a.due_date, a.status, a.created_at
+            FROM assignments a JOIN interns i ON i.id = a.intern_id
+            WHERE a.uuid = ?1
+            "#,
+            params![uuid],
+            |r| {
+                Ok(json!({
+                    "intern": r.get::<_, String>(0)?,
+                    "project_type": r.get::<_, String>(1)?,
+                    "task_description": r.get::<_, String>(2)?,
+                    "due_date": r.get::<_, String>(3)?,
+                    "status": r.get::<_, String>(4)?,
+                    "created_at": r.get::<_, String>(5)?,
+                }))
+            },
+        ),
+        Entity::Evaluation => conn.query_row(
+            r#"
+            SELECT i.uuid, e.label, e.score, e.created_at, m.email, m.name
+            FROM evaluations e
+            JOIN interns i ON i.id = e.intern_id
+            LEFT JOIN mentors m ON m.id = e.evaluator_id
+            WHERE e.uuid = ?1
+            "#,
+            params![uuid],
+            |r| {
+                Ok(json!({
+                    "intern": r.get::<_, String>(0)?,
+                    "label": r.get::<_, String>(1)?,
+                    "score": r.get::<_, i64>(2)?,
+                    "created_at": r.get::<_, String>(3)?,
+                    "evaluator_email": r.get::<_, Option<String>>(4)?,
+                    "evaluator_name": r.get::<_, Option<String>>(5)?,
+                }))
+            },
+        ),
+    };
+    result.optional().map_err(|e| e.to_string())
+}
+
+fn text(d: &Value, key: &str) -> Result<String, String> {
+    d.get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| format!("Değişiklik setinde {key} eksik"))
+}
+
+fn opt_text(d: &Value, key: &str) -> Option<String> {
+    d.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+// Satırı verilen hâle getirir (None: sil). Olaylar her zamanki gibi yazılır.
+// Üst stajyer bu kurulumda yoksa false döner.
+fn apply_data(conn: &Connection, entity: Entity, uuid: &str, data: Option<&Value>) -> Result<bool, String> {
+    let id = local_id(conn, entity, uuid)?;
+    let Some(d) = data else {
+        if let Some(id) = id {
+            match entity {
+                Entity::Intern => delete_intern_conn(conn, id)?,
+                Entity::Assignment => delete_assignment_conn(conn, id)?,
+                Entity::Evaluation => delete_evaluation_conn(conn, id)?,
+            };
+        }
+        return Ok(true);
+    };
+
+    let intern_id = match entity {
+        Entity::Intern => None,
+        _ => match local_id(conn, Entity::Intern, &text(d, "intern")?)? {
+            Some(i) => Some(i),
+            None => return Ok(false),
+        },
+    };
+
+    match entity {
+        Entity::Intern => {
+            let period_id: Option<i64> = match opt_text(d, "period") {
+                Some(name) => conn
+                    .query_row("SELECT id FROM periods WHERE name = ?1", params![name], |r| r.get(0))
+                    .optional()
+                    .map_err(|e| e.to_string())?,
+                None => None,
+            };
+            let values = params![
+                uuid,
+                text(d, "first_name")?,
+                text(d, "last_name")?,
+                text(d, "school")?,
+                text(d, "department")?,
+                text(d, "start_date")?,
+                opt_text(d, "end_date"),
+                text(d, "status")?,
+                text(d, "contact")?,
+                text(d, "email")?,
+                d.get("required_days").and_then(Value::as_i64),
+                period_id,
+            ];
+            match id {
+                Some(id) => {
+                    conn.execute(
+                        r#"
+                        UPDATE interns SET first_name = ?2, last_name = ?3, school = ?4, department = ?5,
+                            start_date = ?6, end_date = ?7, status = ?8, contact = ?9, email = ?10,
+                            required_days = ?11, period_id = ?12
+                        WHERE uuid = ?1
+                        "#,
+                        values,
+                    )
+                    .map_err(|e| e.to_string())?;
+                    events::emit_current(conn, events::INTERN_UPDATED, id, events::intern_data)?;
+                }
+                None => {
+                    conn.execute(
+                        r#"
+                        INSERT INTO interns (uuid, first_name, last_name, school, department, start_date,
+                            end_date, status, contact, email, required_days, period_id)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                        "#,
+                        values,
+                    )
+                    .map_err(|e| e.to_string())?;
+                    events::emit_current(conn, events::INTERN_CREATED, conn.last_insert_rowid(), events::intern_data)?;
+                }
+            }
+        }
+        Entity::Assignment => {
+            let values = params![
+                uuid,
+                intern_id,
+                text(d, "project_type")?,
+                text(d, "task_description")?,
+                text(d, "due_date")?,
+                text(d, "status")?,
+                text(d, "created_at")?,
+            ];
+            match id {
+                Some(id) => {
+                    conn.execute(
+                        r#"
+                        UPDATE assignments SET intern_id = ?2, project_type = ?3, task_description = ?4,
+                            due_date = ?5, status = ?6, created_at = ?7
+                        WHERE uuid = ?1
+                        "#,
+                        values,
+                    )
+                    .map_err(|e| e.to_string())?;
+                    events::emit_current(conn, events::ASSIGNMENT_UPDATED, id, events::assignment_data)?;
+                }
+                None => {
+                    conn.execute(
+                        r#"
+                        INSERT INTO assignments (uuid, intern_id, project_type, task_description, due_date, status, created_at)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                        "#,
+                        values,
+                    )
+                    .map_err(|e| e.to_string())?;
+                    events::emit_current(conn, events::ASSIGNMENT_CREATED, conn.last_insert_rowid(), events::assignment_data)?;
+                }
+            }
+        }
+        Entity::Evaluation => {
+            let evaluator_id: Option<i64> = conn
+                .query_row(
+                    r#"
+                    SELECT id FROM mentors
+                    WHERE (?1 IS NOT NULL AND lower(email) = lower(?1)) OR (?2 IS NOT NULL AND name = ?2)
+                    ORDER BY (?1 IS NOT NULL AND lower(email) = lower(?1)) DESC, id
+                    LIMIT 1
+                    "#,
+                    params![opt_text(d, "evaluator_email"), opt_text(d, "evaluator_name")],
+                    |r| r.get(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            let score = d.get("score").and_then(Value::as_i64).ok_or("Değişiklik setinde score eksik")?;
+            let values = params![uuid, intern_id, text(d, "label")?, score, text(d, "created_at")?, evaluator_id];
+            match id {
+                Some(id) => {
+                    conn.execute(
+                        r#"
+                        UPDATE evaluations SET intern_id = ?2, label = ?3, score = ?4, created_at = ?5, evaluator_id = ?6
+                        WHERE uuid = ?1
+                        "#,
+                        values,
+                    )
+                    .map_err(|e| e.to_string())?;
+                    events::emit_current(conn, events::EVALUATION_UPDATED, id, events::evaluation_data)?;
+                }
+                None => {
+                    conn.execute(
+                        r#"
+                        INSERT INTO evaluations (uuid, intern_id, label, score, created_at, evaluator_id)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                        "#,
+                        values,
+                    )
+                    .map_err(|e| e.to_string())?;
+                    events::emit_current(conn, events::EVALUATION_CREATED, conn.last_insert_rowid(), events::evaluation_data)?;
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+// Uygulanan uzak değişikliğin sürüm bilgisini yazar; seq ilerler, böylece
+// değişiklik diğer kurulumlara da aktarılır
+fn store_meta(conn: &Connection, c: &Change) -> Result<(), String> {
+    conn.execute(
+        r#"
+        INSERT INTO sync_rows (entity, uuid, version, origin, updated_at, base_version, deleted, seq)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?3, ?6, (SELECT COALESCE(MAX(seq), 0) + 1 FROM sync_rows))
+        ON CONFLICT (entity, uuid) DO UPDATE SET
+            version = excluded.version, origin = excluded.origin, updated_at = excluded.updated_at,
+            base_version = excluded.base_version, deleted = excluded.deleted, seq = excluded.seq
+        "#,
+        params![c.entity.key(), c.uuid, c.version, c.origin, c.updated_at, c.deleted as i64],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn decide(local: Option<&RowMeta>, r: &Change, site_id: &str) -> Decision {
+    let Some(l) = local else { return Decision::Apply };
+    let l_origin = l.origin.as_deref().unwrap_or(site_id);
+    let lt = (l.version, l.updated_at.as_str(), l_origin);
+    let rt = (r.version, r.updated_at.as_str(), r.origin.as_str());
+    if rt == lt {
+        return Decision::Skip;
+    }
+    if l.deleted && r.deleted {
+        return if rt > lt { Decision::MetaOnly } else { Decision::Skip };
+    }
+    // yerelde, karşı tarafın henüz görmediği değişiklik var mı
+    let dirty = l_origin == site_id && l.version > l.base_version;
+    if !dirty {
+        return if rt > lt { Decision::Apply } else { Decision::Skip };
+    }
+    // kendi değişikliğimiz ya da zaten aldığımız bir sürüm geri geldi
+    if (r.origin == site_id && r.version <= l.version) || r.version <= l.base_version {
+        return Decision::Skip;
+    }
+    // gönderen bizim son hâlimizi görmüş
+    if r.base_version >= l.version {
+        return Decision::Apply;
+    }
+    Decision::Conflict { remote_wins: rt > lt }
+}
+
+fn export_set(conn: &Connection, full: bool) -> Result<ChangeSet, String> {
+    let me = load_settings(conn)?;
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let from_seq: i64 = if full {
+        0
+    } else {
+        tx.query_row("SELECT COALESCE(MIN(acked_seq), 0) FROM sync_peers", [], |r| r.get(0))
+            .map_err(|e| e.to_string())?
+    };
+    let to_seq: i64 = tx
+        .query_row("SELECT COALESCE(MAX(seq), 0) FROM sync_rows", [], |r| r.get(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = tx
+        .prepare(
+            r#"
+            SELECT entity, uuid, version, origin, updated_at, base_version, deleted, seq
+            FROM sync_rows WHERE seq > ?1 ORDER BY seq
+            "#,
+        )
+        .map_err(|e| e.to_string())?;
+    let rows: Vec<Option<Change>> = stmt
+        .query_map(params![from_seq], |r| {
+            let entity: String = r.get(0)?;
+            let origin: Option<String> = r.get(3)?;
+            Entity::parse(&entity).map(|entity| -> rusqlite::Result<Change> {
+                Ok(Change {
+                    entity,
+                    uuid: r.get(1)?,
+                    version: r.get(2)?,
+                    origin: origin.unwrap_or_else(|| me.site_id.clone()),
+                    updated_at: r.get(4)?,
+                    base_version: r.get(5)?,
+                    deleted: r.get(6)?,
+                    seq: r.get(7)?,
+                    data: None,
+                })
+            }).transpose()
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    drop(stmt);
+    let mut changes = Vec::with_capacity(rows.len());
+    for mut c in rows.into_iter().flatten() {
+        if !c.deleted {
+            c.data = row_data(&tx, c.entity, &c.uuid)?;
+        }
+        changes.push(c);
+    }
+
+    let mut stmt = tx.prepare("SELECT site_id, received_seq FROM sync_peers").map_err(|e| e.to_string())?;
+    let acks = stmt
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ChangeSet {
+        format: FORMAT.to_string(),
+        format_version: FORMAT_VERSION,
+        site_id: me.site_id,
+        site_name: me.site_name,
+        created_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        from_seq,
+        to_seq,
+        acks,
+        changes,
+    })
+}
+
+fn import_set(conn: &mut Connection, set: &ChangeSet) -> Result<SyncImportReport, String> {
+    if set.format != FORMAT {
+        return Err("Geçerli bir eşitleme dosyası değil".to_string());
+    }
+    if set.format_version > FORMAT_VERSION {
+        return Err("Eşitleme dosyası daha yeni bir sürümle oluşturulmuş; uygulamayı güncelleyin".to_string());
+    }
+    let me = load_settings(conn)?;
+    if set.site_id == me.site_id {
+        return Err("Bu değişiklik seti bu kurulumun kendisine ait".to_string());
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let received: i64 = tx
+        .query_row("SELECT received_seq FROM sync_peers WHERE site_id = ?1", params![set.site_id], |r| r.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .unwrap_or(0);
+    let mut report = SyncImportReport {
+        site_id: set.site_id.clone(),
+        site_name: set.site_name.clone(),
+        applied: 0,
+        unchanged: 0,
+        skipped: 0,
+        conflicts: 0,
+        warnings: Vec::new(),
+    };
+    // arada kaçırılmış set var: tam set gelene kadar sıra ilerletilmez
+    let gap = set.from_seq > received;
+    if gap {
+        report.warnings.push(format!(
+            "{} kurulumundan önceki değişiklikler eksik olabilir; karşı taraftan tam değişiklik seti isteyin",
+            set.site_name
+        ));
+    }
+
+    let mut changes: Vec<&Change> = set.changes.iter().filter(|c| c.seq > received).collect();
+    changes.sort_by_key(|c| (c.entity.rank(), c.seq));
+    let actor = format!("sync:{}", if set.site_name.is_empty() { &set.site_id } else { &set.site_name });
+    events::as_actor(&actor, || -> Result<(), String> {
+        for c in changes {
+            let local = row_meta(&tx, c.entity, &c.uuid)?;
+            let data = if c.deleted { None } else { c.data.as_ref() };
+            match decide(local.as_ref(), c, &me.site_id) {
+                Decision::Skip => report.unchanged += 1,
+                Decision::MetaOnly => {
+                    store_meta(&tx, c)?;
+                    report.unchanged += 1;
+                }
+                Decision::Apply => {
+                    if apply_data(&tx, c.entity, &c.uuid, data)? {
+                        store_meta(&tx, c)?;
+                        report.applied += 1;
+                    } else {
+                        report.skipped += 1;
+                    }
+                }
+                Decision::Conflict { remote_wins } => {
+                    let local_data = row_data(&tx, c.entity, &c.uuid)?;
+                    if remote_wins {
+                        if !apply_data(&tx, c.entity, &c.uuid, data)? {
+                            report.skipped += 1;
+                            continue;
+                        }
+                        store_meta(&tx, c)?;
+                        report.applied += 1;
+                    }
+                    if local_data.as_ref() != data {
+                        tx.execute(
+                            r#"
+                            INSERT INTO sync_conflicts (entity, uuid, site_id, local_version, local_data,
+                                remote_version, remote_origin, remote_data, winner)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                            "#,
+                            params![
+                                c.entity.key(),
+                                c.uuid,
+                                set.site_id,
+                                local.as_ref().map(|l| l.version).unwrap_or_default(),
+                                local_data.map(|v| v.to_string()),
+                                c.version,
+                                c.origin,
+                                data.map(|v| v.to_string()),
+                                if remote_wins { "remote" } else { "local" },
+                            ],
+                        )
+                        .map_err(|e| e.to_string())?;
+                        report.conflicts += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    tx.execute(
+        r#"
+        INSERT INTO sync_peers (site_id, name, received_seq, acked_seq, last_sync_at)
+        VALUES (?1, ?2, ?3, ?4, datetime('now'))
+        ON CONFLICT (site_id) DO UPDATE SET
+            name = excluded.name,
+            received_seq = MAX(received_seq, excluded.received_seq),
+            acked_seq = MAX(acked_seq, excluded.acked_seq),
+            last_sync_at = excluded.last_sync_at
+        "#,
+        params![
+            set.site_id,
+            set.site_name,
+            if gap { received } else { set.to_seq },
+            set.acks.get(&me.site_id).copied().unwrap_or(0),
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+fn write_set(path: &Path, set: &ChangeSet) -> Result<SyncExportReport, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Klasör oluşturulamadı: {e}"))?;
+    }
+    let part = path.with_extension("part");
+    let bytes = serde_json::to_vec(set).map_err(|e| e.to_string())?;
+    fs::write(&part, bytes).map_err(|e| format!("Dosya yazılamadı: {e}"))?;
+    fs::rename(&part, path).map_err(|e| format!("Dosya yazılamadı: {e}"))?;
+    Ok(SyncExportReport {
+        path: path.to_string_lossy().into_owned(),
+        changes: set.changes.len(),
+        from_seq: set.from_seq,
+        to_seq: set.to_seq,
+    })
+}
+
+fn read_set(path: &Path) -> Result<ChangeSet, String> {
+    let raw = fs::read(path).map_err(|e| format!("{} okunamadı: {e}", path.display()))?;
+    serde_json::from_slice(&raw).map_err(|_| format!("{} geçerli bir eşitleme dosyası değil", path.display()))
+}
+
+pub(crate) fn export_conn(conn: &Connection, path: &Path, full: bool) -> Result<SyncExportReport, String> {
+    write_set(path, &export_set(conn, full)?)
+}
+
+pub(crate) fn import_conn(conn: &mut Connection, path: &Path) -> Result<SyncImportReport, String> {
+    import_set(conn, &read_set(path)?)
+}
+
+// Ortak klasör: önce diğer kurulumların dosyaları alınır, sonra kendi
+// dosyamız (alındı bilgileriyle) yazılır
+pub(crate) fn run_conn(conn: &mut Connection) -> Result<SyncRunReport, String> {
+    let me = load_settings(conn)?;
+    let folder = me
+        .folder
+        .as_deref()
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(PathBuf::from)
+        .ok_or("Eşitleme klasörü ayarlanmamış")?;
+    let own = format!("{}{FILE_SUFFIX}", me.site_id);
+    let mut files: Vec<PathBuf> = fs::read_dir(&folder)
+        .map_err(|e| format!("Eşitleme klasörü okunamadı: {e}"))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(FILE_SUFFIX) && n != own)
+        })
+        .collect();
+    files.sort();
+
+    let mut imported = Vec::new();
+    let mut errors = Vec::new();
+    for path in files {
+        match import_conn(conn, &path) {
+            Ok(r) => imported.push(r),
+            Err(e) => errors.push(format!("{}: {e}", path.display())),
+        }
+    }
+    let exported = export_conn(conn, &folder.join(own), false)?;
+    Ok(SyncRunReport { imported, exported, errors })
+}
+
+pub(crate) fn resolve_conn(conn: &Connection, id: i64, choice: ConflictChoice) -> Result<(), String> {
+    let (entity, uuid, local_data, remote_data, winner, resolved_at): (
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+        String,
+        Option<String>,
+    ) = conn
+        .query_row(
+            "SELECT entity, uuid, local_data, remote_data, winner, resolved_at FROM sync_conflicts WHERE id = ?1",
+            params![id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or("Çakışma bulunamadı")?;
+    if resolved_at.is_some() {
+        return Err("Çakışma zaten çözülmüş".to_string());
+    }
+    let entity = Entity::parse(&entity).ok_or("Bilinmeyen kayıt türü")?;
+    let (resolution, chosen) = match choice {
+        ConflictChoice::Keep => ("keep", None),
+        ConflictChoice::Local => ("local", (winner != "local").then_some(local_data)),
+        ConflictChoice::Remote => ("remote", (winner != "remote").then_some(remote_data)),
+    };
+    // kaybeden taraf seçildiyse yeni bir yerel değişiklik olarak yazılır
+    if let Some(raw) = chosen {
+        let data: Option<Value> = raw
+            .map(|r| serde_json::from_str(&r).map_err(|e| e.to_string()))
+            .transpose()?;
+        if !apply_data(conn, entity, &uuid, data.as_ref())? {
+            return Err("Stajyer kaydı artık yok; görev ya da değerlendirme geri alınamaz".to_string());
+        }
+    }
+    conn.execute(
+        "UPDATE sync_conflicts SET resolved_at = datetime('now'), resolution = ?2 WHERE id = ?1",
+        params![id, resolution],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_sync_settings(handle: AppHandle) -> Result<SyncSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_sync_settings(handle: AppHandle, settings: SyncSettings) -> Result<SyncSettings, String> {
+    let conn = open_conn(&handle)?;
+    let current = load_settings(&conn)?;
+    if settings.site_name.trim().is_empty() {
+        return Err("Kurulum adı boş olamaz".to_string());
+    }
+    let s = SyncSettings { site_id: current.site_id, site_name: settings.site_name.trim().to_string(), folder: settings.folder };
+    settings::store(&conn, SETTINGS_KEY, &s)?;
+    Ok(s)
+}
+
+// full: karşı tarafın onayına bakmadan tüm satırlar
+#[tauri::command]
+pub fn export_sync_changes(handle: AppHandle, path: String, full: Option<bool>) -> Result<SyncExportReport, String> {
+    let conn = open_conn(&handle)?;
+    export_conn(&conn, Path::new(&path), full.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn import_sync_changes(handle: AppHandle, path: String) -> Result<SyncImportReport, String> {
+    let mut conn = open_conn(&handle)?;
+    import_conn(&mut conn, Path::new(&path))
+}
+
+#[tauri::command]
+pub fn sync_now(handle: AppHandle) -> Result<SyncRunReport, String> {
+    let mut conn = open_conn(&handle)?;
+    run_conn(&mut conn)
+}
+
+#[tauri::command]
+pub fn list_sync_peers(handle: AppHandle) -> Result<Vec<SyncPeer>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        "SELECT site_id, name, received_seq, acked_seq, last_sync_at FROM sync_peers ORDER BY name"
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], |r| {
+        Ok(SyncPeer {
+            site_id: r.get(0)?,
+            name: r.get(1)?,
+            received_seq: r.get(2)?,
+            acked_seq: r.get(3)?,
+            last_sync_at: r.get(4)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_sync_conflicts(handle: AppHandle, include_resolved: Option<bool>) -> Result<Vec<SyncConflict>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, entity, uuid, site_id, local_version, local_data, remote_version, remote_origin,
+               remote_data, winner, detected_at, resolved_at, resolution
+        FROM sync_conflicts
+        WHERE ?1 OR resolved_at IS NULL
+        ORDER BY id DESC
+        "#
+    ).map_err(|e| e.to_string())?;
+    let json = |raw: Option<String>| raw.and_then(|r| serde_json::from_str(&r).ok());
+    let rows = stmt.query_map(params![include_resolved.unwrap_or(false)], |r| {
+        let entity: String = r.get(1)?;
+        Ok(SyncConflict {
+            id: r.get(0)?,
+            entity: Entity::parse(&entity).unwrap_or(Entity::Intern),
+            uuid: r.get(2)?,
+            site_id: r.get(3)?,
+            local_version: r.get(4)?,
+            local_data: json(r.get(5)?),
+            remote_version: r.get(6)?,
+            remote_origin: r.get(7)?,
+            remote_data: json(r.get(8)?),
+            winner: r.get(9)?,
+            detected_at: r.get(10)?,
+            resolved_at: r.get(11)?,
+            resolution: r.get(12)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn resolve_sync_conflict(handle: AppHandle, id: i64, choice: ConflictChoice) -> Result<(), String> {
+    let mut conn = open_conn(&handle)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    resolve_conn(&tx, id, choice)?;
+    tx.commit().map_err(|e| e.to_string())
+}
@@ -0,0 +1,900 @@
+// Mesaj şablonları: görev hatırlatma, eksik not, staj bitişi vb. metinler
+// veritabanında anahtar + dil (tr/en, arayüzdeki tr.json/en.json ile aynı)
+// olarak tutulur. Yer tutucular `{intern.full_name}` biçimindedir ve stajyer,
+// görev, değerlendirme alanlarına ya da stajyer listesine bağlanır; `{{` ve
+// `}}` düz parantez yazar.
+// Kaydetmeden önce bilinmeyen ya da şablonun kapsamında olmayan yer tutucular
+// reddedilir.
+
+use std::collections::HashMap;
+
+use chrono::{Local, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date};
+use crate::mentors::OPEN_END;
+use crate::{certificate, open_conn, settings};
+
+const SETTINGS_KEY: &str = "templates";
+const LOCALES: &[&str] = &["tr", "en"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Intern,
+    Assignment,
+    Evaluation,
+    // birden çok stajyeri tek e-postada listeleyen şablonlar
+    InternList,
+}
+
+impl Scope {
+    fn prefix(self) -> &'static str {
+        match self {
+            Scope::Intern => "intern",
+            Scope::Assignment => "assignment",
+            Scope::Evaluation => "evaluation",
+            Scope::InternList => "interns",
+        }
+    }
+}
+
+// (ad, açıklama); `org.` ve `today` her şablonda kullanılabilir
+const FIELDS: &[(&str, &str)] = &[
+    ("intern.first_name", "Stajyerin adı"),
+    ("intern.last_name", "Stajyerin soyadı"),
+    ("intern.full_name", "Ad soyad"),
+    ("intern.school", "Okul"),
+    ("intern.department", "Bölüm"),
+    ("intern.email", "E-posta"),
+    ("intern.contact", "Telefon"),
+    ("intern.status", "Durum"),
+    ("intern.start_date", "Staj başlangıcı"),
+    ("intern.end_date", "Staj bitişi"),
+    ("intern.ends_in", "Bitişe kalan süre (\"3 gün sonra\")"),
+    ("intern.mentor", "Bugün görevli mentor(lar)"),
+    ("assignment.project", "Proje türü"),
+    ("assignment.task", "Görev açıklaması"),
+    ("assignment.due_date", "Teslim tarihi"),
+    ("assignment.due_in", "Teslime kalan süre (\"bugün\", \"2 gün önce\")"),
+    ("assignment.status", "Görev durumu"),
+    ("evaluation.label", "Değerlendirme başlığı"),
+    ("evaluation.score", "Puan"),
+    ("evaluation.date", "Değerlendirme tarihi"),
+    ("evaluation.evaluator", "Değerlendiren mentor"),
+    ("interns.list", "Stajyer listesi (her satırda bir ad)"),
+    ("interns.count", "Listedeki stajyer sayısı"),
+    ("org.name", "Kurum adı (belge ayarlarından)"),
+    ("today", "Bugünün tarihi"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemplateSettings {
+    // otomatik e-postaların dili
+    pub default_locale: String,
+}
+
+impl Default for TemplateSettings {
+    fn default() -> Self {
+        TemplateSettings { default_locale: "tr".to_string() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Placeholder {
+    pub name: String,
+    pub description: String,
+    // None: her şablonda kullanılabilir
+    pub scope: Option<Scope>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageTemplate {
+    pub id: i64,
+    pub key: String,
+    pub locale: String,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub subject: String,
+    pub body: String,
+    pub builtin: bool,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplatePayload {
+    pub key: String,
+    pub locale: String,
+    pub name: String,
+    #[serde(default)]
+    pub scopes: Vec<Scope>,
+    #[serde(default)]
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TemplateCheck {
+    pub valid: bool,
+    // parantez hataları
+    pub errors: Vec<String>,
+    // katalogda olmayan
+    pub unknown: Vec<String>,
+    // katalogda var ama şablonun kapsamında değil
+    pub unavailable: Vec<String>,
+    pub used: Vec<String>,
+}
+
+// Önizleme verisi; id verilmeyen kapsamlar örnek değerlerle doldurulur
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct TemplateContext {
+    pub intern_id: Option<i64>,
+    pub assignment_id: Option<i64>,
+    pub evaluation_id: Option<i64>,
+    // interns.* için, listedeki sırayla
+    pub intern_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenderedMessage {
+    pub subject: String,
+    pub body: String,
+    // değeri olmayan yer tutucular (boş yazıldı)
+    pub missing: Vec<String>,
+}
+
+struct Builtin {
+    key: &'static str,
+    scopes: &'static [Scope],
+    // (dil, ad, konu, metin)
+    texts: [(&'static str, &'static str, &'static str, &'static str); 2],
+}
+
+const BUILTINS: &[Builtin] = &[
+    Builtin {
+        key: "deadline_reminder",
+        scopes: &[Scope::Intern, Scope::Assignment],
+        texts: [
+            (
+                "tr",
+                "Görev hatırlatma",
+                "[InternTracker] Görev hatırlatma: {assignment.project}",
+                "Merhaba {intern.full_name},\n\n{assignment.project} görevinin teslimi {assignment.due_in}.\n\n\
+                 Görev: {assignment.task}\nTeslim tarihi: {assignment.due_date}\n\n\
+                 Bu e-posta InternTracker tarafından otomatik gönderilmiştir.",
+            ),
+            (
+                "en",
+                "Task reminder",
+                "[InternTracker] Task reminder: {assignment.project}",
+                "Hello {intern.full_name},\n\nThe {assignment.project} task is due {assignment.due_in}.\n\n\
+                 Task: {assignment.task}\nDue date: {assignment.due_date}\n\n\
+                 This email was sent automatically by InternTracker.",
+            ),
+        ],
+    },
+    Builtin {
+        key: "overdue_task",
+        scopes: &[Scope::Intern, Scope::Assignment],
+        texts: [
+            (
+                "tr",
+                "Geciken görev",
+                "[InternTracker] Geciken görev: {assignment.project}",
+                "Merhaba {intern.full_name},\n\n{assignment.project} görevinin teslim tarihi geçti ({assignment.due_in}).\n\n\
+                 Görev: {assignment.task}\nTeslim tarihi: {assignment.due_date}\n\n\
+                 Bu e-posta InternTracker tarafından otomatik gönderilmiştir.",
+            ),
+            (
+                "en",
+                "Overdue task",
+                "[InternTracker] Overdue task: {assignment.project}",
+                "Hello {intern.full_name},\n\nThe {assignment.project} task is overdue (due {assignment.due_in}).\n\n\
+                 Task: {assignment.task}\nDue date: {assignment.due_date}\n\n\
+                 This email was sent automatically by InternTracker.",
+            ),
+        ],
+    },
+    Builtin {
+        key: "missing_evaluation",
+        scopes: &[Scope::InternList],
+        texts: [
+            (
+                "tr",
+                "Günlük not eksik",
+                "[InternTracker] Günlük not eksik",
+                "Merhaba,\n\nBugün ({today}) günlük değerlendirme notu girilmemiş stajyerler:\n\n{interns.list}\n\n\
+                 Bu e-posta InternTracker tarafından otomatik gönderilmiştir.",
+            ),
+            (
+                "en",
+                "Missing daily note",
+                "[InternTracker] Daily note missing",
+                "Hello,\n\nNo daily evaluation note has been entered today ({today}) for these interns:\n\n\
+                 {interns.list}\n\nThis email was sent automatically by InternTracker.",
+            ),
+        ],
+    },
+    Builtin {
+        key: "internship_ending",
+        scopes: &[Scope::Intern],
+        texts: [
+            (
+                "tr",
+                "Staj bitiyor",
+                "[InternTracker] Staj bitiyor: {intern.full_name}",
+                "Merhaba,\n\n{intern.full_name} ({intern.department}) stajı {intern.ends_in} ({intern.end_date}) \
+                 sona eriyor. Son değerlendirme, staj defteri onayı ve belge işlemlerini tamamlamayı unutmayın.\n\n\
+                 Bu e-posta InternTracker tarafından otomatik gönderilmiştir.",
+            ),
+            (
+                "en",
+                "Internship ending",
+                "[InternTracker] Internship ending: {intern.full_name}",
+                "Hello,\n\nThe internship of {intern.full_name} ({intern.department}) ends {intern.ends_in} \
+                 ({intern.end_date}). Please complete the final evaluation, journal approval and certificate.\n\n\
+                 This email was sent automatically by InternTracker.",
+            ),
+        ],
+    },
+    Builtin {
+        key: "evaluation_feedback",
+        scopes: &[Scope::Intern, Scope::Evaluation],
+        texts: [
+            (
+                "tr",
+                "Değerlendirme sonucu",
+                "Değerlendirme: {evaluation.label}",
+                "Merhaba {intern.first_name},\n\n{evaluation.date} tarihli \"{evaluation.label}\" \
+                 değerlendirmesinden {evaluation.score} puan aldın. Değerlendiren: {evaluation.evaluator}.",
+            ),
+            (
+                "en",
+                "Evaluation result",
+                "Evaluation: {evaluation.label}",
+                "Hello {intern.first_name},\n\nYou scored {evaluation.score} in the \"{evaluation.label}\" \
+                 evaluation on {evaluation.date}. Evaluator: {evaluation.evaluator}.",
+            ),
+        ],
+    },
+];
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS message_templates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL,
+            locale TEXT NOT NULL CHECK (locale IN ('tr', 'en')),
+            name TEXT NOT NULL,
+            -- virgülle ayrılmış: intern,assignment,evaluation
+            scopes TEXT NOT NULL DEFAULT 'intern',
+            subject TEXT NOT NULL DEFAULT '',
+            body TEXT NOT NULL,
+            builtin INTEGER NOT NULL DEFAULT 0,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (key, locale)
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())?;
+    // artık gönderilmeyen yerleşik şablonlar
+    conn.execute("DELETE FROM message_templates WHERE builtin = 1 AND key IN ('welcome')", [])
+        .map_err(|e| e.to_string())?;
+    for b in BUILTINS {
+        for (locale, name, subject, body) in b.texts {
+            conn.execute(
+                r#"
+                INSERT OR IGNORE INTO message_templates (key, locale, name, scopes, subject, body, builtin)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1)
+                "#,
+                params![b.key, locale, name, scopes_to_str(b.scopes), subject, body],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<TemplateSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+fn scopes_to_str(scopes: &[Scope]) -> String {
+    scopes.iter().map(|s| s.prefix()).collect::<Vec<_>>().join(",")
+}
+
+fn scopes_from_str(s: &str) -> Vec<Scope> {
+    s.split(',')
+        .filter_map(|p| match p.trim() {
+            "intern" => Some(Scope::Intern),
+            "assignment" => Some(Scope::Assignment),
+            "evaluation" => Some(Scope::Evaluation),
+            "interns" => Some(Scope::InternList),
+            _ => None,
+        })
+        .collect()
+}
+
+fn check_locale(locale: &str) -> Result<(), String> {
+    if LOCALES.contains(&locale) {
+        Ok(())
+    } else {
+        Err(format!("Desteklenmeyen dil: {locale}"))
+    }
+}
+
+enum Piece {
+    Text(String),
+    Field(String),
+}
+
+fn parse(text: &str) -> Result<Vec<Piece>, String> {
+    let mut out = Vec::new();
+    let mut buf = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                buf.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                buf.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => return Err(format!("Kapanmamış yer tutucu: {{{name}")),
+                        Some(c) => name.push(c),
+                    }
+                }
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err("Boş yer tutucu: {}".to_string());
+                }
+                if !buf.is_empty() {
+                    out.push(Piece::Text(std::mem::take(&mut buf)));
+                }
+                out.push(Piece::Field(name));
+            }
+            '}' => return Err("Eşi olmayan '}' (düz parantez için '}}' yazın)".to_string()),
+            c => buf.push(c),
+        }
+    }
+    if !buf.is_empty() {
+        out.push(Piece::Text(buf));
+    }
+    Ok(out)
+}
+
+/// Metindeki yer tutucular (sırasıyla, tekrarsız).
+pub(crate) fn placeholders(text: &str) -> Result<Vec<String>, String> {
+    let mut out: Vec<String> = Vec::new();
+    for p in parse(text)? {
+        if let Piece::Field(name) = p {
+            if !out.contains(&name) {
+                out.push(name);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// `known` dışındaki yer tutucular hata olarak döner (belge şablonu gibi
+/// kendi alan listesi olan metinler için).
+pub(crate) fn ensure_known(text: &str, known: &[&str]) -> Result<(), String> {
+    let unknown: Vec<String> = placeholders(text)?
+        .into_iter()
+        .filter(|p| !known.contains(&p.as_str()))
+        .map(|p| format!("{{{p}}}"))
+        .collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Bilinmeyen yer tutucu: {}", unknown.join(", ")))
+    }
+}
+
+fn field_scope(name: &str) -> Option<Scope> {
+    [Scope::Intern, Scope::Assignment, Scope::Evaluation, Scope::InternList]
+        .into_iter()
+        .find(|s| name.strip_prefix(s.prefix()).is_some_and(|r| r.starts_with('.')))
+}
+
+pub(crate) fn check(subject: &str, body: &str, scopes: &[Scope]) -> TemplateCheck {
+    let mut c = TemplateCheck::default();
+    for (part, text) in [("Konu", subject), ("Metin", body)] {
+        match placeholders(text) {
+            Ok(names) => {
+                for n in names {
+                    if !c.used.contains(&n) {
+                        c.used.push(n);
+                    }
+                }
+            }
+            Err(e) => c.errors.push(format!("{part}: {e}")),
+        }
+    }
+    for n in &c.used {
+        if !FIELDS.iter().any(|(f, _)| f == n) {
+            c.unknown.push(n.clone());
+        } else if field_scope(n).is_some_and(|s| !scopes.contains(&s)) {
+            c.unavailable.push(n.clone());
+        }
+    }
+    c.valid = c.errors.is_empty() && c.unknown.is_empty() && c.unavailable.is_empty();
+    c
+}
+
+fn check_error(c: &TemplateCheck) -> String {
+    let mut parts = c.errors.clone();
+    if !c.unknown.is_empty() {
+        let list: Vec<String> = c.unknown.iter().map(|n| format!("{{{n}}}")).collect();
+        parts.push(format!("Bilinmeyen yer tutucu: {}", list.join(", ")));
+    }
+    if !c.unavailable.is_empty() {
+        let list: Vec<String> = c.unavailable.iter().map(|n| format!("{{{n}}}")).collect();
+        parts.push(format!("Şablonun kapsamında olmayan yer tutucu: {}", list.join(", ")));
+    }
+    parts.join("; ")
+}
+
+/// Yer tutucuları doldurur; değeri olmayanlar boş yazılır ve ayrıca döner.
+pub(crate) fn render(text: &str, vars: &HashMap<String, String>) -> Result<(String, Vec<String>), String> {
+    let mut out = String::with_capacity(text.len());
+    let mut missing = Vec::new();
+    for p in parse(text)? {
+        match p {
+            Piece::Text(t) => out.push_str(&t),
+            Piece::Field(name) => match vars.get(&name) {
+                Some(v) => out.push_str(v),
+                None => {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+            },
+        }
+    }
+    Ok((out, missing))
+}
+
+fn render_message(subject: &str, body: &str, vars: &HashMap<String, String>) -> Result<RenderedMessage, String> {
+    let (subject, mut missing) = render(subject, vars)?;
+    let (body, body_missing) = render(body, vars)?;
+    for m in body_missing {
+        if !missing.contains(&m) {
+            missing.push(m);
+        }
+    }
+    Ok(RenderedMessage { subject, body, missing })
+}
+
+fn display(locale: &str, d: &str) -> String {
+    match parse_date(d) {
+        Ok(d) if locale == "en" => d.format("%B %-d, %Y").to_string(),
+        Ok(d) => d.format("%d.%m.%Y").to_string(),
+        Err(_) => d.to_string(),
+    }
+}
+
+// "bugün" / "3 gün sonra" / "2 gün önce"
+fn relative(locale: &str, days: i64) -> String {
+    match (locale, days) {
+        ("en", 0) => "today".to_string(),
+        ("en", 1) => "tomorrow".to_string(),
+        ("en", -1) => "yesterday".to_string(),
+        ("en", d) if d > 0 => format!("in {d} days"),
+        ("en", d) => format!("{} days ago", -d),
+        (_, 0) => "bugün".to_string(),
+        (_, 1) => "yarın".to_string(),
+        (_, -1) => "dün".to_string(),
+        (_, d) if d > 0 => format!("{d} gün sonra"),
+        (_, d) => format!("{} gün önce", -d),
+    }
+}
+
+fn days_between(today: NaiveDate, d: &str) -> Option<i64> {
+    parse_date(d).ok().map(|d| (d - today).num_days())
+}
+
+// arayüzdeki durum etiketleri
+fn status_label(locale: &str, status: &str) -> String {
+    let en = locale == "en";
+    match status.to_lowercase().as_str() {
+        "aktif" | "active" => if en { "Active" } else { "Aktif" }.to_string(),
+        "pasif" | "inactive" => if en { "Inactive" } else { "Pasif" }.to_string(),
+        "tamamlandı" | "completed" => if en { "Completed" } else { "Tamamlandı" }.to_string(),
+        "planned" => if en { "Planned" } else { "Planlandı" }.to_string(),
+        "in progress" | "in_progress" => if en { "In Progress" } else { "Devam Ediyor" }.to_string(),
+        _ => status.to_string(),
+    }
+}
+
+fn intern_vars(conn: &Connection, locale: &str, id: i64, today: NaiveDate, vars: &mut HashMap<String, String>) -> Result<(), String> {
+    let row = conn
+        .query_row(
+            r#"
+            SELECT first_name, last_name, school, department, email, contact, status,
+                   start_date, COALESCE(end_date, '')
+            FROM interns WHERE id = ?1
+            "#,
+            params![id],
+            |r| {
+                Ok([
+                    r.get::<_, String>(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?,
+                    r.get(5)?, r.get(6)?, r.get(7)?, r.get(8)?,
+                ])
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+    let [first, last, school, department, email, contact, status, start, end] = row;
+    let day = format_date(today);
+    let mentors: Vec<String> = {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT DISTINCT m.name FROM intern_mentors im
+            JOIN mentors m ON m.id = im.mentor_id
+            WHERE im.intern_id = ?1 AND im.start_date <= ?2 AND COALESCE(im.end_date, ?3) >= ?2
+            ORDER BY im.role = 'primary' DESC, m.name
+            "#,
+        ).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![id, day, OPEN_END], |r| r.get(0)).map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())?
+    };
+    vars.insert("intern.full_name".into(), format!("{first} {last}"));
+    vars.insert("intern.first_name".into(), first);
+    vars.insert("intern.last_name".into(), last);
+    vars.insert("intern.school".into(), school);
+    vars.insert("intern.department".into(), department);
+    vars.insert("intern.email".into(), email);
+    vars.insert("intern.contact".into(), contact);
+    vars.insert("intern.status".into(), status_label(locale, &status));
+    vars.insert("intern.start_date".into(), display(locale, &start));
+    if !end.is_empty() {
+        vars.insert("intern.end_date".into(), display(locale, &end));
+        if let Some(d) = days_between(today, &end) {
+            vars.insert("intern.ends_in".into(), relative(locale, d));
+        }
+    }
+    if !mentors.is_empty() {
+        vars.insert("intern.mentor".into(), mentors.join(", "));
+    }
+    Ok(())
+}
+
+/// Şablon değişkenleri; görev/değerlendirme verilirse stajyeri de oradan alır.
+pub(crate) fn load_vars(conn: &Connection, locale: &str, ctx: &TemplateContext, today: NaiveDate) -> Result<HashMap<String, String>, String> {
+    let mut vars = HashMap::new();
+    let mut intern_id = ctx.intern_id;
+    if let Some(id) = ctx.assignment_id {
+        let (owner, project, task, due, status): (i64, String, String, String, String) = conn
+            .query_row(
+                "SELECT intern_id, project_type, task_description, date(due_date), status FROM assignments WHERE id = ?1",
+                params![id],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get::<_, Option<String>>(3)?.unwrap_or_default(), r.get(4)?)),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Görev bulunamadı".to_string())?;
+        intern_id = Some(owner);
+        vars.insert("assignment.project".into(), project);
+        vars.insert("assignment.task".into(), task);
+        vars.insert("assignment.status".into(), status_label(locale, &status));
+        if let Some(d) = days_between(today, &due) {
+            vars.insert("assignment.due_in".into(), relative(locale, d));
+        }
+        vars.insert("assignment.due_date".into(), display(locale, &due));
+    }
+    if let Some(id) = ctx.evaluation_id {
+        let (owner, label, score, date, evaluator): (i64, String, i64, String, Option<String>) = conn
+            .query_row(
+                r#"
+                SELECT e.intern_id, e.label, e.score, date(e.created_at), m.name
+                FROM evaluations e LEFT JOIN mentors m ON m.id = e.evaluator_id
+                WHERE e.id = ?1
+                "#,
+                params![id],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Değerlendirme bulunamadı".to_string())?;
+        intern_id = Some(owner);
+        vars.insert("evaluation.label".into(), label);
+        vars.insert("evaluation.score".into(), score.to_string());
+        vars.insert("evaluation.date".into(), display(locale, &date));
+        if let Some(name) = evaluator {
+            vars.insert("evaluation.evaluator".into(), name);
+        }
+    }
+    if let Some(id) = intern_id {
+        intern_vars(conn, locale, id, today, &mut vars)?;
+    }
+    if !ctx.intern_ids.is_empty() {
+        let mut names = Vec::with_capacity(ctx.intern_ids.len());
+        for id in &ctx.intern_ids {
+            let name: String = conn
+                .query_row("SELECT first_name || ' ' || last_name FROM interns WHERE id = ?1", params![id], |r| r.get(0))
+                .optional()
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "Stajyer bulunamadı".to_string())?;
+            names.push(format!("- {name}"));
+        }
+        vars.insert("interns.list".into(), names.join("\n"));
+        vars.insert("interns.count".into(), ctx.intern_ids.len().to_string());
+    }
+    let org = certificate::load_template(conn)?.organization_name;
+    if !org.trim().is_empty() {
+        vars.insert("org.name".into(), org);
+    }
+    vars.insert("today".into(), display(locale, &format_date(today)));
+    Ok(vars)
+}
+
+// Önizlemede id'si verilmeyen kapsamlar için
+fn sample_vars(locale: &str, vars: &mut HashMap<String, String>) {
+    let en = locale == "en";
+    let samples: &[(&str, &str)] = &[
+        ("intern.first_name", "Ayşe"),
+        ("intern.last_name", "Yılmaz"),
+        ("intern.full_name", "Ayşe Yılmaz"),
+        ("intern.school", if en { "Istanbul Technical University" } else { "İstanbul Teknik Üniversitesi" }),
+        ("intern.department", if en { "Computer Engineering" } else { "Bilgisayar Mühendisliği" }),
+        ("intern.email", "ayse.yilmaz@example.com"),
+        ("intern.contact", "0555 555 55 55"),
+        ("intern.status", if en { "Active" } else { "Aktif" }),
+        ("intern.start_date", if en { "July 1, 2025" } else { "01.07.2025" }),
+        ("intern.end_date", if en { "August 22, 2025" } else { "22.08.2025" }),
+        ("intern.ends_in", if en { "in 5 days" } else { "5 gün sonra" }),
+        ("intern.mentor", "Mehmet Demir"),
+        ("assignment.project", "Web"),
+        ("assignment.task", if en { "Login page" } else { "Giriş sayfası" }),
+        ("assignment.due_date", if en { "July 18, 2025" } else { "18.07.2025" }),
+        ("assignment.due_in", if en { "in 2 days" } else { "2 gün sonra" }),
+        ("assignment.status", if en { "In Progress" } else { "Devam Ediyor" }),
+        ("evaluation.label", if en { "Week 3" } else { "3. hafta" }),
+        ("evaluation.score", "85"),
+        ("evaluation.date", if en { "July 18, 2025" } else { "18.07.2025" }),
+        ("evaluation.evaluator", "Mehmet Demir"),
+        ("interns.list", "- Ayşe Yılmaz\n- Can Kaya"),
+        ("interns.count", "2"),
+        ("org.name", if en { "Example Inc." } else { "Örnek A.Ş." }),
+    ];
+    for (k, v) in samples {
+        vars.entry(k.to_string()).or_insert_with(|| v.to_string());
+    }
+}
+
+fn template_from_row(r: &rusqlite::Row) -> rusqlite::Result<MessageTemplate> {
+    Ok(MessageTemplate {
+        id: r.get(0)?,
+        key: r.get(1)?,
+        locale: r.get(2)?,
+        name: r.get(3)?,
+        scopes: scopes_from_str(&r.get::<_, String>(4)?),
+        subject: r.get(5)?,
+        body: r.get(6)?,
+        builtin: r.get::<_, i64>(7)? == 1,
+        updated_at: r.get(8)?,
+    })
+}
+
+const TEMPLATE_COLUMNS: &str = "id, key, locale, name, scopes, subject, body, builtin, updated_at";
+
+/// İstenen dilde yoksa Türkçe sürüme düşer.
+pub(crate) fn get_conn(conn: &Connection, key: &str, locale: &str) -> Result<MessageTemplate, String> {
+    conn.query_row(
+        &format!(
+            "SELECT {TEMPLATE_COLUMNS} FROM message_templates WHERE key = ?1 AND locale IN (?2, 'tr') \
+             ORDER BY locale = ?2 DESC LIMIT 1"
+        ),
+        params![key, locale],
+        template_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Şablon bulunamadı: {key}"))
+}
+
+/// Kayıtlı şablonu gerçek kayıtlarla doldurur (otomatik e-postalar için).
+pub(crate) fn render_stored(
+    conn: &Connection,
+    key: &str,
+    locale: &str,
+    ctx: &TemplateContext,
+    today: NaiveDate,
+) -> Result<RenderedMessage, String> {
+    let t = get_conn(conn, key, locale)?;
+    let vars = load_vars(conn, &t.locale, ctx, today)?;
+    render_message(&t.subject, &t.body, &vars)
+}
+
+pub(crate) fn save_conn(conn: &Connection, p: &TemplatePayload) -> Result<i64, String> {
+    let key = p.key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("Şablon anahtarı yalnızca harf, rakam, '_' ve '-' içerebilir".to_string());
+    }
+    check_locale(&p.locale)?;
+    if p.name.trim().is_empty() || p.body.trim().is_empty() {
+        return Err("Şablon adı ve metni boş olamaz".to_string());
+    }
+    // yerleşik şablonların kapsamı sabittir
+    let scopes = match BUILTINS.iter().find(|b| b.key == key) {
+        Some(b) => b.scopes.to_vec(),
+        None => p.scopes.clone(),
+    };
+    let c = check(&p.subject, &p.body, &scopes);
+    if !c.valid {
+        return Err(check_error(&c));
+    }
+    conn.execute(
+        r#"
+        INSERT INTO message_templates (key, locale, name, scopes, subject, body, builtin)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        ON CONFLICT(key, locale) DO UPDATE SET
+            name = excluded.name, scopes = excluded.scopes, subject = excluded.subject,
+            body = excluded.body, updated_at = datetime('now')
+        "#,
+        params![
+            key,
+            p.locale,
+            p.name.trim(),
+            scopes_to_str(&scopes),
+            p.subject.trim(),
+            p.body,
+            BUILTINS.iter().any(|b| b.key == key) as i64,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT id FROM message_templates WHERE key = ?1 AND locale = ?2",
+        params![key, p.locale],
+        |r| r.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn preview_conn(
+    conn: &Connection,
+    subject: &str,
+    body: &str,
+    locale: &str,
+    ctx: &TemplateContext,
+    today: NaiveDate,
+) -> Result<RenderedMessage, String> {
+    check_locale(locale)?;
+    let mut vars = load_vars(conn, locale, ctx, today)?;
+    // yalnızca yüklenmemiş kapsamlar örnekle doldurulur
+    let mut samples = HashMap::new();
+    sample_vars(locale, &mut samples);
+    let intern_loaded = ctx.intern_id.is_some() || ctx.assignment_id.is_some() || ctx.evaluation_id.is_some();
+    for (k, v) in samples {
+        let loaded = match field_scope(&k) {
+            Some(Scope::Intern) => intern_loaded,
+            Some(Scope::Assignment) => ctx.assignment_id.is_some(),
+            Some(Scope::Evaluation) => ctx.evaluation_id.is_some(),
+            Some(Scope::InternList) => !ctx.intern_ids.is_empty(),
+            None => vars.contains_key(&k),
+        };
+        if !loaded {
+            vars.insert(k, v);
+        }
+    }
+    render_message(subject, body, &vars)
+}
+
+#[tauri::command]
+pub fn list_message_templates(handle: AppHandle, locale: Option<String>) -> Result<Vec<MessageTemplate>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {TEMPLATE_COLUMNS} FROM message_templates WHERE ?1 IS NULL OR locale = ?1 \
+             ORDER BY builtin DESC, key, locale"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![locale], template_from_row).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_message_template(handle: AppHandle, key: String, locale: String) -> Result<MessageTemplate, String> {
+    check_locale(&locale)?;
+    let conn = open_conn(&handle)?;
+    get_conn(&conn, &key, &locale)
+}
+
+#[tauri::command]
+pub fn save_message_template(handle: AppHandle, template: TemplatePayload) -> Result<i64, String> {
+    let conn = open_conn(&handle)?;
+    save_conn(&conn, &template)
+}
+
+// Yerleşik şablonlar silinemez, yalnızca varsayılana döndürülür
+#[tauri::command]
+pub fn delete_message_template(handle: AppHandle, key: String, locale: String) -> Result<(), String> {
+    if BUILTINS.iter().any(|b| b.key == key) {
+        return Err("Yerleşik şablon silinemez".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    conn.execute("DELETE FROM message_templates WHERE key = ?1 AND locale = ?2", params![key, locale])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn reset_message_template(handle: AppHandle, key: String, locale: String) -> Result<MessageTemplate, String> {
+    let b = BUILTINS
+        .iter()
+        .find(|b| b.key == key)
+        .ok_or_else(|| "Yalnızca yerleşik şablonlar sıfırlanabilir".to_string())?;
+    let (_, name, subject, body) = b
+        .texts
+        .iter()
+        .copied()
+        .find(|t| t.0 == locale)
+        .ok_or_else(|| format!("Desteklenmeyen dil: {locale}"))?;
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        r#"
+        UPDATE message_templates SET name = ?3, subject = ?4, body = ?5, updated_at = datetime('now')
+        WHERE key = ?1 AND locale = ?2
+        "#,
+        params![key, locale, name, subject, body],
+    )
+    .map_err(|e| e.to_string())?;
+    get_conn(&conn, &key, &locale)
+}
+
+// Editörde canlı doğrulama için; kaydetmez
+#[tauri::command]
+pub fn validate_message_template(subject: String, body: String, scopes: Vec<Scope>) -> TemplateCheck {
+    check(&subject, &body, &scopes)
+}
+
+#[tauri::command]
+pub fn preview_message_template(
+    handle: AppHandle,
+    subject: String,
+    body: String,
+    locale: String,
+    context: Option<TemplateContext>,
+) -> Result<RenderedMessage, String> {
+    let conn = open_conn(&handle)?;
+    preview_conn(&conn, &subject, &body, &locale, &context.unwrap_or_default(), Local::now().date_naive())
+}
+
+#[tauri::command]
+pub fn list_template_placeholders() -> Vec<Placeholder> {
+    FIELDS
+        .iter()
+        .map(|(name, description)| Placeholder {
+            name: name.to_string(),
+            description: description.to_string(),
+            scope: field_scope(name),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_template_settings(handle: AppHandle) -> Result<TemplateSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_template_settings(handle: AppHandle, settings: TemplateSettings) -> Result<(), String> {
+    check_locale(&settings.default_locale)?;
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
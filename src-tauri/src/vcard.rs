@@ -0,0 +1,740 @@
+// Stajyer iletişim bilgilerinin vCard olarak paylaşılması. Dışa aktarım
+// vCard 4.0 (RFC 6350) yazar: ad, e-posta, telefon, kurum/bölüm ve fotoğraf
+// (data: URI). İçe aktarım telefonların ürettiği 2.1 / 3.0 kartlarını da okur
+// (satır katlama, quoted-printable, BASE64 fotoğraf); kayıtlar e-posta ile
+// eşlenir, eşleşen güncellenir, olmayan yeni stajyer olarak eklenir.
+
+use std::fs;
+
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use chrono::Local;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::{format_date, parse_date};
+use crate::ics::{self, escape, fold};
+use crate::{
+    certificate, insert_intern, normalize_reference_fields, open_conn, save_intern_update, slug_tr, storage_root,
+    InternPayload,
+};
+
+// kendi kartlarımızda okul bilgisini taşır (telefonlar yok sayar)
+const SCHOOL_PROPERTY: &str = "X-INTERNTRACKER-SCHOOL";
+
+// dolgu karakteri olan ve olmayan BASE64 birlikte kabul edilir
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+#[derive(Debug, Serialize)]
+pub struct VcardExportResult {
+    pub file_path: String,
+    pub cards: usize,
+    pub photos: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct VcardImportOptions {
+    // yeni kayıtlar için; kartta yoksa kullanılır
+    pub school: Option<String>,
+    pub department: Option<String>,
+    // YYYY-MM-DD; verilmezse bugün
+    pub start_date: Option<String>,
+    pub period_id: Option<i64>,
+    // yalnızca mevcut stajyerler güncellenir, yenisi eklenmez
+    pub update_only: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VcardSkip {
+    // kişi adı ya da okunamayan dosya
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VcardImportResult {
+    pub created: Vec<i64>,
+    pub updated: Vec<i64>,
+    // eşleşen ama farkı olmayan kartlar
+    pub unchanged: usize,
+    pub skipped: Vec<VcardSkip>,
+}
+
+// --- yazma ---
+
+struct CardIntern {
+    id: i64,
+    uuid: Option<String>,
+    first_name: String,
+    last_name: String,
+    school: String,
+    department: String,
+    contact: String,
+    email: String,
+    photo_mime: Option<String>,
+    photo_blob: Option<Vec<u8>>,
+    photo_path: Option<String>,
+}
+
+fn load_card_intern(conn: &Connection, id: i64) -> Result<Option<CardIntern>, String> {
+    conn.query_row(
+        r#"
+        SELECT id, uuid, first_name, last_name, school, department, contact, email,
+               photo_mime, photo_blob, photo_path
+        FROM interns WHERE id = ?1
+        "#,
+        params![id],
+        |r| {
+            Ok(CardIntern {
+                id: r.get(0)?,
+                uuid: r.get(1)?,
+                first_name: r.get(2)?,
+                last_name: r.get(3)?,
+                school: r.get(4)?,
+                department: r.get(5)?,
+                contact: r.get(6)?,
+                email: r.get(7)?,
+                photo_mime: r.get(8)?,
+                photo_blob: r.get(9)?,
+                photo_path: r.get(10)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+// baştaki imzaya göre görsel türü
+fn sniff_image(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG") {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if bytes.len() > 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+// tel: URI'de boşluk olmaz; gruplar '-' ile ayrılır (RFC 3966 görsel ayraç).
+// Harf içeren iletişim bilgisi telefon sayılmaz.
+fn tel_uri(contact: &str) -> Option<String> {
+    let s = contact.trim();
+    if s.is_empty() || s.chars().any(|c| c.is_alphabetic()) || !s.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let number = s
+        .split_whitespace()
+        .map(|part| part.chars().filter(|c| c.is_ascii_digit() || "+-.()".contains(*c)).collect::<String>())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    Some(format!("tel:{number}"))
+}
+
+// Aynı numara farklı biçimde yazılmış olabilir
+fn same_phone(a: &str, b: &str) -> bool {
+    let digits = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>();
+    digits(a) == digits(b)
+}
+
+fn push(out: &mut String, line: &str) {
+    out.push_str(&fold(line));
+    out.push_str("\r\n");
+}
+
+fn write_card(out: &mut String, i: &CardIntern, organization: &str) -> bool {
+    push(out, "BEGIN:VCARD");
+    push(out, "VERSION:4.0");
+    push(out, &format!("PRODID:{}", ics::PRODID));
+    match &i.uuid {
+        Some(uuid) => push(out, &format!("UID:urn:uuid:{uuid}")),
+        None => push(out, &format!("UID:interntracker-intern-{}", i.id)),
+    }
+    push(out, "KIND:individual");
+    push(out, &format!("FN:{}", escape(format!("{} {}", i.first_name, i.last_name).trim())));
+    push(out, &format!("N:{};{};;;", escape(&i.last_name), escape(&i.first_name)));
+    if !i.email.trim().is_empty() {
+        push(out, &format!("EMAIL:{}", escape(i.email.trim())));
+    }
+    if let Some(tel) = tel_uri(&i.contact) {
+        push(out, &format!("TEL;VALUE=uri;TYPE=cell:{tel}"));
+    }
+    push(out, &format!("ORG:{};{}", escape(organization), escape(&i.department)));
+    push(out, "TITLE:Stajyer");
+    if !i.school.trim().is_empty() {
+        push(out, &format!("{SCHOOL_PROPERTY}:{}", escape(&i.school)));
+    }
+
+    // fotoğraf BLOB'u; eski kayıtlarda yalnızca disk yolu olabilir
+    let photo = i
+        .photo_blob
+        .clone()
+        .filter(|b| !b.is_empty())
+        .or_else(|| i.photo_path.as_ref().and_then(|p| fs::read(p).ok()));
+    let mut has_photo = false;
+    if let Some(bytes) = photo {
+        let mime = i
+            .photo_mime
+            .clone()
+            .filter(|m| m.starts_with("image/"))
+            .or_else(|| sniff_image(&bytes).map(str::to_string));
+        if let Some(mime) = mime {
+            push(out, &format!("PHOTO:data:{mime};base64,{}", BASE64.encode(&bytes)));
+            has_photo = true;
+        }
+    }
+    push(out, &format!("REV:{}", ics::now_utc()));
+    push(out, "END:VCARD");
+    has_photo
+}
+
+pub(crate) fn vcards_conn(conn: &Connection, intern_ids: &[i64]) -> Result<(String, usize), String> {
+    let organization = certificate::load_template(conn)?.organization_name;
+    let mut out = String::new();
+    let mut photos = 0;
+    for &id in intern_ids {
+        let intern = load_card_intern(conn, id)?.ok_or_else(|| format!("Stajyer bulunamadı: #{id}"))?;
+        if write_card(&mut out, &intern, &organization) {
+            photos += 1;
+        }
+    }
+    Ok((out, photos))
+}
+
+// --- okuma ---
+
+struct Property {
+    // büyük harf, grup öneki olmadan
+    name: String,
+    // (büyük harf ad, değer); 2.1'deki çıplak parametreler TYPE sayılır
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn has_type(&self, t: &str) -> bool {
+        self.params
+            .iter()
+            .filter(|(k, _)| k == "TYPE")
+            .flat_map(|(_, v)| v.split(','))
+            .any(|v| v.trim().eq_ignore_ascii_case(t))
+    }
+
+    fn is_preferred(&self) -> bool {
+        self.has_type("pref") || self.param("PREF").is_some()
+    }
+
+    fn is_quoted_printable(&self) -> bool {
+        self.param("ENCODING").is_some_and(|e| e.eq_ignore_ascii_case("QUOTED-PRINTABLE"))
+            || self.has_type("QUOTED-PRINTABLE")
+    }
+
+    // kaçışlar çözülmemiş ham metin (quoted-printable açılmış)
+    fn raw_text(&self) -> String {
+        if self.is_quoted_printable() {
+            String::from_utf8_lossy(&decode_quoted_printable(&self.value)).into_owned()
+        } else {
+            self.value.clone()
+        }
+    }
+
+    fn text(&self) -> String {
+        unescape(&self.raw_text()).trim().to_string()
+    }
+
+    // N, ORG gibi ';' ile ayrılmış yapılı değerler
+    fn components(&self) -> Vec<String> {
+        split_unescaped(&self.raw_text(), ';').iter().map(|c| unescape(c).trim().to_string()).collect()
+    }
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+fn split_unescaped(s: &str, sep: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut escaped = false;
+    for c in s.chars() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == sep {
+            parts.push(String::new());
+            continue;
+        }
+        if let Some(last) = parts.last_mut() {
+            last.push(c);
+        }
+    }
+    parts
+}
+
+fn decode_quoted_printable(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+// Katlanmış satırları birleştirir. 2.1 quoted-printable değerleri satır
+// sonundaki '=' ile devam eder.
+fn unfold(text: &str) -> Vec<String> {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        if let Some(last) = lines.last_mut() {
+            if line.starts_with(' ') || line.starts_with('\t') {
+                last.push_str(&line[1..]);
+                continue;
+            }
+            let head = last.split(':').next().unwrap_or("").to_ascii_uppercase();
+            if last.ends_with('=') && head.contains("QUOTED-PRINTABLE") {
+                last.pop();
+                last.push_str(line);
+                continue;
+            }
+        }
+        if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+fn parse_line(line: &str) -> Option<Property> {
+    // ':' parametre değerinde tırnak içinde geçebilir
+    let mut quoted = false;
+    let colon = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        c == ':' && !quoted
+    })?;
+    let (head, value) = (&line[..colon.0], &line[colon.0 + 1..]);
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in head.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => parts.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    parts.push(current);
+
+    let mut parts = parts.into_iter();
+    let name = parts.next()?;
+    let name = name.rsplit('.').next().unwrap_or(&name).trim().to_ascii_uppercase();
+    let params = parts
+        .filter(|p| !p.is_empty())
+        .map(|p| match p.split_once('=') {
+            Some((k, v)) => (k.trim().to_ascii_uppercase(), v.trim().to_string()),
+            None => ("TYPE".to_string(), p.trim().to_string()),
+        })
+        .collect();
+    Some(Property { name, params, value: value.to_string() })
+}
+
+fn parse_cards(text: &str) -> Vec<Vec<Property>> {
+    let mut cards = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    for line in unfold(text.trim_start_matches('\u{feff}')) {
+        let Some(p) = parse_line(&line) else { continue };
+        match (p.name.as_str(), p.value.trim().to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VCARD") => current = Some(Vec::new()),
+            ("END", "VCARD") => cards.extend(current.take()),
+            _ => {
+                if let Some(card) = current.as_mut() {
+                    card.push(p);
+                }
+            }
+        }
+    }
+    cards
+}
+
+#[derive(Debug, Default)]
+struct Contact {
+    first_name: String,
+    last_name: String,
+    // tercih edilen önce
+    emails: Vec<String>,
+    phone: Option<String>,
+    department: Option<String>,
+    school: Option<String>,
+    photo: Option<(String, Vec<u8>)>,
+}
+
+impl Contact {
+    fn display_name(&self) -> String {
+        let name = format!("{} {}", self.first_name, self.last_name).trim().to_string();
+        match (name.is_empty(), self.emails.first()) {
+            (false, _) => name,
+            (true, Some(email)) => email.clone(),
+            (true, None) => "(adsız kart)".to_string(),
+        }
+    }
+}
+
+fn parse_photo(p: &Property) -> Option<(String, Vec<u8>)> {
+    let value = p.value.trim();
+    let (mime, bytes) = if let Some(rest) = value.strip_prefix("data:") {
+        // data:image/jpeg;base64,...
+        let (header, data) = rest.split_once(',')?;
+        if !header.to_ascii_lowercase().ends_with(";base64") {
+            return None;
+        }
+        let mime = header.split(';').next().unwrap_or("").to_ascii_lowercase();
+        (Some(mime).filter(|m| m.starts_with("image/")), decode_base64(data)?)
+    } else if p.param("ENCODING").is_some_and(|e| e.eq_ignore_ascii_case("b") || e.eq_ignore_ascii_case("BASE64"))
+        || p.has_type("BASE64")
+    {
+        // 3.0: TYPE=JPEG, 2.1: çıplak JPEG parametresi
+        (None, decode_base64(value)?)
+    } else {
+        // bağlantı (http:) olarak verilen fotoğraflar indirilmez
+        return None;
+    };
+    let mime = mime.or_else(|| sniff_image(&bytes).map(str::to_string))?;
+    Some((mime, bytes))
+}
+
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    BASE64.decode(compact).ok().filter(|b| !b.is_empty())
+}
+
+fn contact_from_card(props: &[Property]) -> Contact {
+    let mut c = Contact::default();
+    let get = |name: &str| props.iter().find(|p| p.name == name);
+
+    if let Some(n) = get("N") {
+        let parts = n.components();
+        let part = |i: usize| parts.get(i).cloned().unwrap_or_default();
+        c.last_name = part(0);
+        // ikinci ad ilk ada eklenir (ör. "Ayşe Nur")
+        c.first_name = [part(1), part(2)].iter().filter(|s| !s.is_empty()).cloned().collect::<Vec<_>>().join(" ");
+    }
+    if c.first_name.is_empty() && c.last_name.is_empty() {
+        if let Some(fn_) = get("FN") {
+            let full = fn_.text();
+            match full.rsplit_once(' ') {
+                Some((first, last)) => {
+                    c.first_name = first.trim().to_string();
+                    c.last_name = last.trim().to_string();
+                }
+                None => c.first_name = full,
+            }
+        }
+    }
+
+    let mut emails: Vec<&Property> = props.iter().filter(|p| p.name == "EMAIL").collect();
+    emails.sort_by_key(|p| !p.is_preferred());
+    c.emails = emails
+        .into_iter()
+        .map(|p| p.text().trim_start_matches("mailto:").trim().to_string())
+        .filter(|e| e.contains('@'))
+        .collect();
+
+    let phones: Vec<&Property> = props.iter().filter(|p| p.name == "TEL").collect();
+    let phone = phones
+        .iter()
+        .find(|p| p.has_type("cell"))
+        .or_else(|| phones.iter().find(|p| p.is_preferred()))
+        .or_else(|| phones.first());
+    c.phone = phone.map(|p| p.text().trim_start_matches("tel:").to_string()).filter(|s| !s.is_empty());
+
+    c.department = get("ORG").and_then(|p| p.components().get(1).cloned()).filter(|s| !s.is_empty());
+    c.school = get(SCHOOL_PROPERTY).map(Property::text).filter(|s| !s.is_empty());
+    c.photo = props.iter().filter(|p| p.name == "PHOTO").find_map(parse_photo);
+    c
+}
+
+// --- içe aktarma ---
+
+fn find_by_email(conn: &Connection, emails: &[String]) -> Result<Option<i64>, String> {
+    for email in emails {
+        let found = conn
+            .query_row(
+                "SELECT id FROM interns WHERE lower(trim(email)) = lower(?1) ORDER BY id LIMIT 1",
+                params![email.trim()],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    Ok(None)
+}
+
+fn photo_name(mime: &str) -> String {
+    let ext = match mime {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        _ => "bin",
+    };
+    format!("photo.{ext}")
+}
+
+// Kartta olan alanlar mevcut kayda yazılır; fark yoksa false
+fn update_from_contact(handle: &AppHandle, conn: &Connection, id: i64, c: &Contact) -> Result<bool, String> {
+    let (mut p, photo_blob): (InternPayload, Option<Vec<u8>>) = conn
+        .query_row(
+            r#"
+            SELECT first_name, last_name, school, department, start_date, end_date, status,
+                   contact, email, cv_path, photo_path, photo_blob
+            FROM interns WHERE id = ?1
+            "#,
+            params![id],
+            |r| {
+                Ok((
+                    InternPayload {
+                        id: Some(id),
+                        first_name: r.get(0)?,
+                        last_name: r.get(1)?,
+                        school: r.get(2)?,
+                        department: r.get(3)?,
+                        start_date: r.get(4)?,
+                        end_date: r.get(5)?,
+                        status: r.get(6)?,
+                        contact: r.get(7)?,
+                        email: r.get(8)?,
+                        cv_path: r.get(9)?,
+                        photo_path: r.get(10)?,
+                        cv_name: None,
+                        cv_mime: None,
+                        cv_blob: None,
+                        photo_name: None,
+                        photo_mime: None,
+                        photo_blob: None,
+                        period_id: None,
+                    },
+                    r.get(11)?,
+                ))
+            },
+        )
+        .map_err(|e| e.to_string())?;
+    let before = (p.first_name.clone(), p.last_name.clone(), p.school.clone(), p.department.clone(), p.contact.clone());
+
+    if !c.first_name.is_empty() {
+        p.first_name = c.first_name.clone();
+    }
+    if !c.last_name.is_empty() {
+        p.last_name = c.last_name.clone();
+    }
+    // yalnızca rakamlar farklıysa; kullanıcının yazdığı biçim korunur
+    if let Some(phone) = c.phone.as_ref().filter(|phone| !same_phone(phone, &p.contact)) {
+        p.contact = phone.clone();
+    }
+    if let Some(school) = &c.school {
+        p.school = school.clone();
+    }
+    if let Some(department) = &c.department {
+        p.department = department.clone();
+    }
+    normalize_reference_fields(conn, &mut p)?;
+
+    let mut changed = before != (p.first_name.clone(), p.last_name.clone(), p.school.clone(), p.department.clone(), p.contact.clone());
+    if let Some((mime, bytes)) = &c.photo {
+        if photo_blob.as_deref() != Some(bytes.as_slice()) {
+            p.photo_name = Some(photo_name(mime));
+            p.photo_mime = Some(mime.clone());
+            p.photo_blob = Some(bytes.clone());
+            changed = true;
+        }
+    }
+    if changed {
+        save_intern_update(handle, conn, id, &mut p)?;
+    }
+    Ok(changed)
+}
+
+fn create_from_contact(
+    handle: &AppHandle,
+    conn: &Connection,
+    c: &Contact,
+    options: &VcardImportOptions,
+    start_date: &str,
+) -> Result<i64, String> {
+    if c.first_name.is_empty() || c.last_name.is_empty() {
+        return Err("Ad ve soyad gerekli".to_string());
+    }
+    let email = c.emails.first().ok_or_else(|| "E-posta adresi yok".to_string())?;
+    let school = c
+        .school
+        .clone()
+        .or_else(|| options.school.clone())
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| "Okul bilgisi yok; içe aktarma seçeneklerinde okul belirtin".to_string())?;
+    let department = c
+        .department
+        .clone()
+        .or_else(|| options.department.clone())
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| "Bölüm bilgisi yok; içe aktarma seçeneklerinde bölüm belirtin".to_string())?;
+    let (photo_mime, photo_blob) = match &c.photo {
+        Some((mime, bytes)) => (Some(mime.clone()), Some(bytes.clone())),
+        None => (None, None),
+    };
+    let mut intern = InternPayload {
+        id: None,
+        first_name: c.first_name.clone(),
+        last_name: c.last_name.clone(),
+        school,
+        department,
+        start_date: start_date.to_string(),
+        end_date: None,
+        status: "aktif".to_string(),
+        contact: c.phone.clone().unwrap_or_default(),
+        email: email.clone(),
+        cv_path: None,
+        photo_path: None,
+        cv_name: None,
+        cv_mime: None,
+        cv_blob: None,
+        photo_name: photo_mime.as_deref().map(photo_name),
+        photo_mime,
+        photo_blob,
+        period_id: options.period_id,
+    };
+    insert_intern(handle, conn, &mut intern)
+}
+
+// Her kart kendi işleminde yazılır; hatalı kart diğerlerini engellemez.
+// Dönen değer dosyadaki kart sayısıdır.
+pub(crate) fn import_text(
+    handle: &AppHandle,
+    conn: &mut Connection,
+    text: &str,
+    options: &VcardImportOptions,
+    result: &mut VcardImportResult,
+) -> Result<usize, String> {
+    let start_date = match options.start_date.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(s) => format_date(parse_date(s)?),
+        None => format_date(Local::now().date_naive()),
+    };
+    let cards = parse_cards(text);
+    let count = cards.len();
+    for props in cards {
+        let contact = contact_from_card(&props);
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        let outcome = match find_by_email(&tx, &contact.emails)? {
+            Some(id) => update_from_contact(handle, &tx, id, &contact).map(|changed| (id, false, changed)),
+            None if options.update_only => Err("Bu e-posta ile kayıtlı stajyer yok".to_string()),
+            None => create_from_contact(handle, &tx, &contact, options, &start_date).map(|id| (id, true, true)),
+        };
+        match outcome {
+            Ok((id, created, changed)) => {
+                tx.commit().map_err(|e| e.to_string())?;
+                match (created, changed) {
+                    (true, _) => result.created.push(id),
+                    (false, true) => result.updated.push(id),
+                    (false, false) => result.unchanged += 1,
+                }
+            }
+            Err(reason) => result.skipped.push(VcardSkip { name: contact.display_name(), reason }),
+        }
+    }
+    Ok(count)
+}
+
+// --- KOMUTLAR ---
+
+#[tauri::command]
+pub fn export_vcards(
+    handle: AppHandle,
+    intern_ids: Vec<i64>,
+    output_path: Option<String>,
+) -> Result<VcardExportResult, String> {
+    if intern_ids.is_empty() {
+        return Err("Dışa aktarılacak stajyer seçilmedi".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    let (body, photos) = vcards_conn(&conn, &intern_ids)?;
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => p.into(),
+        None => {
+            let dir = storage_root(&handle)?.join("contacts");
+            fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let name = match intern_ids.as_slice() {
+                [id] => {
+                    let i = load_card_intern(&conn, *id)?.ok_or_else(|| format!("Stajyer bulunamadı: #{id}"))?;
+                    format!("{}_{}_{}.vcf", id, slug_tr(&i.first_name), slug_tr(&i.last_name))
+                }
+                _ => format!("interntracker_{}.vcf", Local::now().format("%Y%m%d")),
+            };
+            dir.join(name)
+        }
+    };
+    fs::write(&path, body).map_err(|e| format!("vCard yazılamadı: {e}"))?;
+    Ok(VcardExportResult { file_path: path.to_string_lossy().to_string(), cards: intern_ids.len(), photos })
+}
+
+#[tauri::command]
+pub fn import_vcards(
+    handle: AppHandle,
+    paths: Vec<String>,
+    options: Option<VcardImportOptions>,
+) -> Result<VcardImportResult, String> {
+    let options = options.unwrap_or_default();
+    let mut conn = open_conn(&handle)?;
+    let mut result = VcardImportResult::default();
+    for path in &paths {
+        let reason = match fs::read(path) {
+            Ok(bytes) => match import_text(&handle, &mut conn, &String::from_utf8_lossy(&bytes), &options, &mut result)? {
+                0 => "Dosyada vCard bulunamadı".to_string(),
+                _ => continue,
+            },
+            Err(e) => format!("Dosya okunamadı: {e}"),
+        };
+        result.skipped.push(VcardSkip { name: path.clone(), reason });
+    }
+    Ok(result)
+}
@@ -0,0 +1,196 @@
+// Kaydedilmiş görünümler (örn. "2025 Yaz Dönemi"): adlandırılmış filtre + sıralama.
+// Görünümün sahibi işletim sistemi kullanıcısıdır; paylaşılan görünümler aynı
+// kurulumdaki herkes tarafından listelenip çalıştırılabilir, ama yalnızca
+// sahibi tarafından değiştirilebilir.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::query::{query_interns_conn, InternFilter, InternPage, InternQuery, InternSort};
+use crate::{current_os_user, open_conn};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewDefinition {
+    pub filter: InternFilter,
+    pub sort: InternSort,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SavedView {
+    pub id: i64,
+    pub name: String,
+    pub definition: ViewDefinition,
+    pub owner: String,
+    pub shared: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SavedViewPayload {
+    pub name: String,
+    #[serde(default)]
+    pub definition: ViewDefinition,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS saved_views (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            definition TEXT NOT NULL,
+            owner TEXT NOT NULL,
+            shared INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (owner, name)
+        );
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn validate(v: &SavedViewPayload) -> Result<String, String> {
+    let name = v.name.trim();
+    if name.is_empty() {
+        return Err("Görünüm adı boş olamaz".to_string());
+    }
+    Ok(name.to_string())
+}
+
+fn map_unique_err(e: rusqlite::Error) -> String {
+    if let rusqlite::Error::SqliteFailure(ref err, _) = e {
+        if err.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE {
+            return "Bu adla kayıtlı bir görünümünüz zaten var".to_string();
+        }
+    }
+    e.to_string()
+}
+
+fn view_from_row(row: &rusqlite::Row) -> rusqlite::Result<(SavedView, String)> {
+    let definition: String = row.get(2)?;
+    Ok((
+        SavedView {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            definition: ViewDefinition::default(),
+            owner: row.get(3)?,
+            shared: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        },
+        definition,
+    ))
+}
+
+fn parse_definition(raw: &str) -> Result<ViewDefinition, String> {
+    serde_json::from_str(raw).map_err(|e| format!("Görünüm tanımı okunamadı: {e}"))
+}
+
+pub(crate) fn get_view_conn(conn: &Connection, id: i64, user: &str) -> Result<SavedView, String> {
+    let found = conn
+        .query_row(
+            r#"
+            SELECT id, name, definition, owner, shared, created_at, updated_at
+            FROM saved_views
+            WHERE id = ?1 AND (owner = ?2 OR shared = 1)
+            "#,
+            params![id, user],
+            view_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let (mut view, raw) = found.ok_or_else(|| "Görünüm bulunamadı".to_string())?;
+    view.definition = parse_definition(&raw)?;
+    Ok(view)
+}
+
+pub(crate) fn list_views_conn(conn: &Connection, user: &str) -> Result<Vec<SavedView>, String> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, name, definition, owner, shared, created_at, updated_at
+        FROM saved_views
+        WHERE owner = ?1 OR shared = 1
+        ORDER BY owner <> ?1, name COLLATE NOCASE
+        "#
+    ).map_err(|e| e.to_string())?;
+
+    let rows = stmt.query_map(params![user], view_from_row).map_err(|e| e.to_string())?;
+
+    let mut out = Vec::new();
+    for r in rows {
+        let (mut view, raw) = r.map_err(|e| e.to_string())?;
+        view.definition = parse_definition(&raw)?;
+        out.push(view);
+    }
+    Ok(out)
+}
+
+#[tauri::command]
+pub fn create_view(handle: AppHandle, view: SavedViewPayload) -> Result<i64, String> {
+    let name = validate(&view)?;
+    let definition = serde_json::to_string(&view.definition).map_err(|e| e.to_string())?;
+    let conn = open_conn(&handle)?;
+    conn.execute(
+        "INSERT INTO saved_views (name, definition, owner, shared) VALUES (?1, ?2, ?3, ?4)",
+        params![name, definition, current_os_user(), view.shared],
+    ).map_err(map_unique_err)?;
+    Ok(conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn list_views(handle: AppHandle) -> Result<Vec<SavedView>, String> {
+    let conn = open_conn(&handle)?;
+    list_views_conn(&conn, &current_os_user())
+}
+
+#[tauri::command]
+pub fn update_view(handle: AppHandle, id: i64, view: SavedViewPayload) -> Result<(), String> {
+    let name = validate(&view)?;
+    let definition = serde_json::to_string(&view.definition).map_err(|e| e.to_string())?;
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        r#"
+        UPDATE saved_views
+        SET name = ?1, definition = ?2, shared = ?3, updated_at = datetime('now')
+        WHERE id = ?4 AND owner = ?5
+        "#,
+        params![name, definition, view.shared, id, current_os_user()],
+    ).map_err(map_unique_err)?;
+    if n == 0 {
+        return Err("Görünüm bulunamadı veya size ait değil".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_view(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        "DELETE FROM saved_views WHERE id = ?1 AND owner = ?2",
+        params![id, current_os_user()],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Görünüm bulunamadı veya size ait değil".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn run_view(handle: AppHandle, id: i64, cursor: Option<String>, limit: Option<i64>) -> Result<InternPage, String> {
+    let conn = open_conn(&handle)?;
+    let view = get_view_conn(&conn, id, &current_os_user())?;
+    let q = InternQuery {
+        filter: view.definition.filter,
+        sort: view.definition.sort,
+        cursor,
+        limit,
+    };
+    query_interns_conn(&conn, &q)
+}
@@ -0,0 +1,987 @@
+// Giden webhook'lar: domain_events tablosundaki olaylar, olay filtresi eşleşen
+// her uç nokta için bir teslimat kaydına dönüştürülür ve HMAC-SHA256 ile
+// imzalanmış JSON olarak POST edilir. Başarısız teslimatlar üstel bekleme ile
+// yeniden denenir; her deneme teslimat günlüğüne yazılır.
+//
+// İmza: X-InternTracker-Signature: t=<unix zaman>,v1=<hex(HMAC(secret, "<t>.<gövde>"))>
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tauri::AppHandle;
+
+use crate::{events, open_conn, settings};
+
+const SETTINGS_KEY: &str = "webhooks";
+// bir turda denenecek en fazla teslimat
+const BATCH_SIZE: i64 = 50;
+// günlükte saklanan yanıt gövdesi
+const RESPONSE_EXCERPT: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookSettings {
+    pub enabled: bool,
+    pub timeout_secs: u64,
+    pub max_attempts: i64,
+    // ilk yeniden deneme gecikmesi; her denemede iki katına çıkar
+    pub retry_seconds: i64,
+    // yeni olay yoksa kuyruğa bakma aralığı
+    pub poll_seconds: u64,
+    // tamamlanan teslimatların günlükte kalma süresi
+    pub log_retention_days: i64,
+    // dağıtılmış olayların (denetim kaydı) saklanma süresi; 0: süresiz
+    pub event_retention_days: i64,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        WebhookSettings {
+            enabled: true,
+            timeout_secs: 10,
+            max_attempts: 8,
+            retry_seconds: 30,
+            poll_seconds: 30,
+            log_retention_days: 30,
+            event_retention_days: 180,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookEndpoint {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    // "intern.created", "intern.*" veya "*"
+    pub events: Vec<String>,
+    pub active: bool,
+    // gizli anahtarın son 4 karakteri
+    pub secret_hint: String,
+    pub created_at: String,
+    pub pending: i64,
+    pub failed: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookPayload {
+    pub name: String,
+    pub url: String,
+    pub events: Vec<String>,
+    #[serde(default = "default_true")]
+    pub active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookCreated {
+    pub id: i64,
+    // yalnızca oluşturulurken gösterilir
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub endpoint_id: i64,
+    pub endpoint_name: String,
+    pub event_id: i64,
+    pub event_type: String,
+    // pending | delivered | failed
+    pub status: String,
+    pub attempts: i64,
+    pub last_status_code: Option<i64>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: String,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeliveryAttempt {
+    pub id: i64,
+    pub attempted_at: String,
+    pub status_code: Option<i64>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub response_excerpt: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DispatchReport {
+    // teslimat kaydı oluşturulan olay sayısı
+    pub fanned_out: usize,
+    pub delivered: usize,
+    pub retrying: usize,
+    pub failed: usize,
+}
+
+// arka plan dağıtıcısının son turu (arayüzde gösterilir)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DispatcherStatus {
+    pub last_run_at: Option<String>,
+    pub last_report: Option<DispatchReport>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<String>,
+}
+
+static STATUS: Mutex<DispatcherStatus> = Mutex::new(DispatcherStatus {
+    last_run_at: None,
+    last_report: None,
+    last_error: None,
+    last_error_at: None,
+});
+
+struct DueDelivery {
+    id: i64,
+    url: String,
+    secret: String,
+    event_id: i64,
+    event_type: String,
+    occurred_at: String,
+    payload: String,
+    attempts: i64,
+    actor: Option<String>,
+}
+
+struct Outcome {
+    status_code: Option<i64>,
+    error: Option<String>,
+    duration_ms: i64,
+    response: Option<String>,
+}
+
+impl Outcome {
+    fn ok(&self) -> bool {
+        self.error.is_none() && self.status_code.is_some_and(|c| (200..300).contains(&c))
+    }
+}
+
+pub(crate) fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS webhook_endpoints (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL,
+            -- JSON dizi: olay türleri / kalıpları
+            events TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            active INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            endpoint_id INTEGER NOT NULL,
+            event_id INTEGER NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending'
+                CHECK (status IN ('pending', 'delivered', 'failed')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_status_code INTEGER,
+            last_error TEXT,
+            next_attempt_at TEXT NOT NULL DEFAULT (datetime('now')),
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            delivered_at TEXT,
+            UNIQUE (endpoint_id, event_id),
+            FOREIGN KEY (endpoint_id) REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+            FOREIGN KEY (event_id) REFERENCES domain_events(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_pending ON webhook_deliveries(status, next_attempt_at);
+
+        CREATE TABLE IF NOT EXISTS webhook_attempts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            delivery_id INTEGER NOT NULL,
+            attempted_at TEXT NOT NULL DEFAULT (datetime('now')),
+            status_code INTEGER,
+            error TEXT,
+            duration_ms INTEGER NOT NULL,
+            response_excerpt TEXT,
+            FOREIGN KEY (delivery_id) REFERENCES webhook_deliveries(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_webhook_attempts_delivery ON webhook_attempts(delivery_id);
+        "#,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn load_settings(conn: &Connection) -> Result<WebhookSettings, String> {
+    settings::load(conn, SETTINGS_KEY)
+}
+
+fn new_secret() -> String {
+    let bytes: [u8; 24] = rand::thread_rng().gen();
+    format!("whsec_{}", to_hex(&bytes))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub(crate) fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC her anahtar uzunluğunu kabul eder");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    to_hex(&mac.finalize().into_bytes())
+}
+
+fn matches(patterns: &[String], event_type: &str) -> bool {
+    patterns.iter().any(|p| {
+        p == "*"
+            || p == event_type
+            || p.strip_suffix(".*").is_some_and(|prefix| event_type.strip_prefix(prefix).is_some_and(|r| r.starts_with('.')))
+    })
+}
+
+fn validate(p: &WebhookPayload) -> Result<(), String> {
+    if p.name.trim().is_empty() {
+        return Err("Webhook adı boş olamaz".to_string());
+    }
+    let url = p.url.trim();
+    if !(url.starts_with("http://") || url.starts_with("https://")) || url.len() < 10 {
+        return Err("Adres http:// veya https:// ile başlamalı".to_string());
+    }
+    if p.events.is_empty() {
+        return Err("En az bir olay seçilmeli".to_string());
+    }
+    for e in &p.events {
+        let known = e == "*"
+            || events::EVENT_TYPES.contains(&e.as_str())
+            || e.strip_suffix(".*").is_some_and(|prefix| {
+                events::EVENT_TYPES.iter().any(|t| t.split('.').next() == Some(prefix))
+            });
+        if !known {
+            return Err(format!("Bilinmeyen olay: {e}"));
+        }
+    }
+    Ok(())
+}
+
+fn parse_events(raw: &str) -> Vec<String> {
+    serde_json::from_str(raw).unwrap_or_default()
+}
+
+/// Dağıtılmamış olaylar için eşleşen uç noktalara teslimat kaydı açar.
+pub(crate) fn fan_out(conn: &Connection) -> Result<usize, String> {
+    let endpoints: Vec<(i64, Vec<String>)> = {
+        let mut stmt = conn
+            .prepare("SELECT id, events FROM webhook_endpoints WHERE active = 1")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |r| Ok((r.get::<_, i64>(0)?, parse_events(&r.get::<_, String>(1)?))))
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())?
+    };
+    let pending: Vec<(i64, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT id, event_type FROM domain_events WHERE dispatched = 0 ORDER BY id")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?))).map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())?
+    };
+    for (event_id, event_type) in &pending {
+        for (endpoint_id, patterns) in &endpoints {
+            if matches(patterns, event_type) {
+                conn.execute(
+                    "INSERT OR IGNORE INTO webhook_deliveries (endpoint_id, event_id) VALUES (?1, ?2)",
+                    params![endpoint_id, event_id],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+        }
+        conn.execute("UPDATE domain_events SET dispatched = 1 WHERE id = ?1", params![event_id])
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(pending.len())
+}
+
+fn agent(s: &WebhookSettings) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(s.timeout_secs.max(1)))
+        // yönlendirme başarısız sayılır; imzalı gövde başka adrese gitmesin
+        .redirects(0)
+        .build()
+}
+
+fn post(agent: &ureq::Agent, url: &str, secret: &str, event_type: &str, delivery_id: i64, body: &str) -> Outcome {
+    let timestamp = Utc::now().timestamp();
+    let started = Instant::now();
+    let result = agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .set("User-Agent", "InternTracker-Webhook/1")
+        .set("X-InternTracker-Event", event_type)
+        .set("X-InternTracker-Delivery", &delivery_id.to_string())
+        .set("X-InternTracker-Signature", &format!("t={timestamp},v1={}", sign(secret, timestamp, body)))
+        .send_string(body);
+    let duration_ms = started.elapsed().as_millis() as i64;
+    let excerpt = |resp: ureq::Response| {
+        resp.into_string().ok().map(|mut b| {
+            if b.len() > RESPONSE_EXCERPT {
+                let mut cut = RESPONSE_EXCERPT;
+                while !b.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                b.truncate(cut);
+            }
+            b
+        })
+    };
+    match result {
+        Ok(resp) => {
+            let code = resp.status() as i64;
+            let error = (!(200..300).contains(&code)).then(|| format!("Beklenmeyen yanıt: {code}"));
+            Outcome { status_code: Some(code), error, duration_ms, response: excerpt(resp) }
+        }
+        Err(ureq::Error::Status(code, resp)) => Outcome {
+            status_code: Some(code as i64),
+            error: Some(format!("HTTP {code}")),
+            duration_ms,
+            response: excerpt(resp),
+        },
+        Err(e) => Outcome { status_code: None, error: Some(e.to_string()), duration_ms, response: None },
+    }
+}
+
+fn event_body(event_id: i64, event_type: &str, occurred_at: &str, actor: Option<&str>, payload: &str) -> String {
+    json!({
+        "id": event_id,
+        "type": event_type,
+        "occurred_at": occurred_at,
+        "actor": actor,
+        "data": serde_json::from_str::<Value>(payload).unwrap_or(Value::Null),
+    })
+    .to_string()
+}
+
+// Teslimatı başka bir tur (dağıtıcı / run_webhooks_now) almışsa false.
+// next_attempt_at ileri alınarak kiralanır; gönderim yarıda kalırsa kira
+// bitince yeniden denenir.
+fn claim(conn: &Connection, s: &WebhookSettings, id: i64) -> Result<bool, String> {
+    let lease = s.timeout_secs.max(1) * 2 + 60;
+    let n = conn.execute(
+        r#"
+        UPDATE webhook_deliveries SET next_attempt_at = datetime('now', '+' || ?1 || ' seconds')
+        WHERE id = ?2 AND status = 'pending' AND next_attempt_at <= datetime('now')
+        "#,
+        params![lease as i64, id],
+    ).map_err(|e| e.to_string())?;
+    Ok(n == 1)
+}
+
+/// Zamanı gelen teslimatları gönderir; hata durumunda üstel bekleme ile yeniden dener.
+pub(crate) fn process_conn(conn: &Connection, s: &WebhookSettings, agent: &ureq::Agent, report: &mut DispatchReport) -> Result<(), String> {
+    let due: Vec<DueDelivery> = {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT d.id, w.url, w.secret, ev.id, ev.event_type, ev.occurred_at, ev.payload, d.attempts, ev.actor
+            FROM webhook_deliveries d
+            JOIN webhook_endpoints w ON w.id = d.endpoint_id
+            JOIN domain_events ev ON ev.id = d.event_id
+            WHERE d.status = 'pending' AND w.active = 1 AND d.next_attempt_at <= datetime('now')
+            ORDER BY d.next_attempt_at, d.id LIMIT ?1
+            "#
+        ).map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![BATCH_SIZE], |r| {
+            Ok(DueDelivery {
+                id: r.get(0)?,
+                url: r.get(1)?,
+                secret: r.get(2)?,
+                event_id: r.get(3)?,
+                event_type: r.get(4)?,
+                occurred_at: r.get(5)?,
+                payload: r.get(6)?,
+                attempts: r.get(7)?,
+                actor: r.get(8)?,
+            })
+        }).map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())?
+    };
+
+    for d in due {
+        let id = d.id;
+        if !claim(conn, s, id)? {
+            continue;
+        }
+        let body = event_body(d.event_id, &d.event_type, &d.occurred_at, d.actor.as_deref(), &d.payload);
+        let out = post(agent, &d.url, &d.secret, &d.event_type, id, &body);
+        conn.execute(
+            r#"
+            INSERT INTO webhook_attempts (delivery_id, status_code, error, duration_ms, response_excerpt)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            params![id, out.status_code, out.error, out.duration_ms, out.response],
+        ).map_err(|e| e.to_string())?;
+        if out.ok() {
+            conn.execute(
+                r#"
+                UPDATE webhook_deliveries
+                SET status = 'delivered', attempts = attempts + 1, last_status_code = ?1,
+                    last_error = NULL, delivered_at = datetime('now')
+                WHERE id = ?2
+                "#,
+                params![out.status_code, id],
+            ).map_err(|e| e.to_string())?;
+            report.delivered += 1;
+            continue;
+        }
+        let attempts = d.attempts + 1;
+        let failed = attempts >= s.max_attempts;
+        // 30 sn, 1 dk, 2 dk ... en fazla 6 saat
+        let delay = (s.retry_seconds.max(1) << (attempts - 1).min(20)).min(6 * 3600);
+        conn.execute(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = ?1, attempts = ?2, last_status_code = ?3, last_error = ?4,
+                next_attempt_at = datetime('now', '+' || ?5 || ' seconds')
+            WHERE id = ?6
+            "#,
+            params![if failed { "failed" } else { "pending" }, attempts, out.status_code, out.error, delay, id],
+        ).map_err(|e| e.to_string())?;
+        if failed { report.failed += 1 } else { report.retrying += 1 }
+    }
+    Ok(())
+}
+
+fn purge_conn(conn: &Connection, s: &WebhookSettings) -> Result<(), String> {
+    if s.event_retention_days > 0 {
+        // teslimatları da (CASCADE) gider; bekleyen teslimatı olan olay kalır
+        conn.execute(
+            r#"
+            DELETE FROM domain_events
+            WHERE dispatched = 1
+              AND occurred_at < strftime('%Y-%m-%dT%H:%M:%SZ', 'now', '-' || ?1 || ' days')
+              AND NOT EXISTS (
+                  SELECT 1 FROM webhook_deliveries d
+                  WHERE d.event_id = domain_events.id AND d.status = 'pending'
+              )
+            "#,
+            params![s.event_retention_days],
+        ).map_err(|e| e.to_string())?;
+    }
+    if s.log_retention_days <= 0 {
+        return Ok(());
+    }
+    conn.execute(
+        r#"
+        DELETE FROM webhook_deliveries
+        WHERE status <> 'pending' AND created_at < datetime('now', '-' || ?1 || ' days')
+        "#,
+        params![s.log_retention_days],
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub(crate) fn run_conn(conn: &Connection, s: &WebhookSettings) -> Result<DispatchReport, String> {
+    let mut report = DispatchReport { fanned_out: fan_out(conn)?, ..Default::default() };
+    process_conn(conn, s, &agent(s), &mut report)?;
+    purge_conn(conn, s)?;
+    Ok(report)
+}
+
+// Sonuç dağıtıcı durumuna yazılır (get_webhook_dispatcher_status)
+pub(crate) fn run(handle: &AppHandle) -> Result<DispatchReport, String> {
+    let result = open_conn(handle).and_then(|conn| {
+        let s = load_settings(&conn)?;
+        if !s.enabled {
+            return Ok(DispatchReport::default());
+        }
+        run_conn(&conn, &s)
+    });
+    if let Ok(mut status) = STATUS.lock() {
+        let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        match &result {
+            Ok(report) => {
+                status.last_report = Some(report.clone());
+                status.last_error = None;
+            }
+            Err(e) => {
+                status.last_error = Some(e.clone());
+                status.last_error_at = Some(now.clone());
+            }
+        }
+        status.last_run_at = Some(now);
+    }
+    result
+}
+
+// setup içinde bir kez çağrılır; yeni olay yazılınca beklemeden uyanır.
+// Tur hataları dağıtıcı durumunda görünür.
+pub(crate) fn start_dispatcher(handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        let _ = run(&handle);
+        let seconds = open_conn(&handle)
+            .and_then(|conn| load_settings(&conn))
+            .map(|s| s.poll_seconds)
+            .unwrap_or(30)
+            .max(1);
+        events::wait(Duration::from_secs(seconds));
+    });
+}
+
+fn endpoint_from_row(r: &rusqlite::Row) -> rusqlite::Result<WebhookEndpoint> {
+    let secret: String = r.get(4)?;
+    Ok(WebhookEndpoint {
+        id: r.get(0)?,
+        name: r.get(1)?,
+        url: r.get(2)?,
+        events: parse_events(&r.get::<_, String>(3)?),
+        secret_hint: secret.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect(),
+        active: r.get::<_, i64>(5)? == 1,
+        created_at: r.get(6)?,
+        pending: r.get(7)?,
+        failed: r.get(8)?,
+    })
+}
+
+#[tauri::command]
+pub fn list_webhooks(handle: AppHandle) -> Result<Vec<WebhookEndpoint>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT w.id, w.name, w.url, w.events, w.secret, w.active, w.created_at,
+               (SELECT COUNT(*) FROM webhook_deliveries d WHERE d.endpoint_id = w.id AND d.status = 'pending'),
+               (SELECT COUNT(*) FROM webhook_deliveries d WHERE d.endpoint_id = w.id AND d.status = 'failed')
+        FROM webhook_endpoints w
+        ORDER BY w.name, w.id
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([], endpoint_from_row).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_webhook(handle: AppHandle, webhook: WebhookPayload) -> Result<WebhookCreated, String> {
+    validate(&webhook)?;
+    let conn = open_conn(&handle)?;
+    let secret = new_secret();
+    conn.execute(
+        "INSERT INTO webhook_endpoints (name, url, events, secret, active) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            webhook.name.trim(),
+            webhook.url.trim(),
+            serde_json::to_string(&webhook.events).map_err(|e| e.to_string())?,
+            secret,
+            webhook.active as i64,
+        ],
+    ).map_err(|e| e.to_string())?;
+    Ok(WebhookCreated { id: conn.last_insert_rowid(), secret })
+}
+
+#[tauri::command]
+pub fn update_webhook(handle: AppHandle, id: i64, webhook: WebhookPayload) -> Result<(), String> {
+    validate(&webhook)?;
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        "UPDATE webhook_endpoints SET name = ?1, url = ?2, events = ?3, active = ?4 WHERE id = ?5",
+        params![
+            webhook.name.trim(),
+            webhook.url.trim(),
+            serde_json::to_string(&webhook.events).map_err(|e| e.to_string())?,
+            webhook.active as i64,
+            id,
+        ],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Webhook bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_webhook(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    conn.execute("DELETE FROM webhook_endpoints WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Eski anahtarla imzalanmış bekleyen teslimatlar da yeni anahtarla gönderilir
+#[tauri::command]
+pub fn rotate_webhook_secret(handle: AppHandle, id: i64) -> Result<String, String> {
+    let conn = open_conn(&handle)?;
+    let secret = new_secret();
+    let n = conn
+        .execute("UPDATE webhook_endpoints SET secret = ?1 WHERE id = ?2", params![secret, id])
+        .map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Webhook bulunamadı".to_string());
+    }
+    Ok(secret)
+}
+
+// Kuyruğu atlayarak "ping" olayı gönderir (adresi ve imzayı denemek için).
+// HTTP çağrısı arayüzü dondurmasın diye ayrı iş parçacığında çalışır.
+#[tauri::command]
+pub async fn send_test_webhook(handle: AppHandle, id: i64) -> Result<DeliveryAttempt, String> {
+    tauri::async_runtime::spawn_blocking(move || send_test(&handle, id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn send_test(handle: &AppHandle, id: i64) -> Result<DeliveryAttempt, String> {
+    let conn = open_conn(handle)?;
+    let s = load_settings(&conn)?;
+    let (url, secret): (String, String) = conn
+        .query_row("SELECT url, secret FROM webhook_endpoints WHERE id = ?1", params![id], |r| Ok((r.get(0)?, r.get(1)?)))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Webhook bulunamadı".to_string())?;
+    let occurred_at = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let body = event_body(0, "ping", &occurred_at, None, "{}");
+    let out = post(&agent(&s), &url, &secret, "ping", 0, &body);
+    Ok(DeliveryAttempt {
+        id: 0,
+        attempted_at: occurred_at,
+        status_code: out.status_code,
+        error: out.error,
+        duration_ms: out.duration_ms,
+        response_excerpt: out.response,
+    })
+}
+
+#[tauri::command]
+pub fn list_webhook_deliveries(
+    handle: AppHandle,
+    endpoint_id: Option<i64>,
+    status: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT d.id, d.endpoint_id, w.name, d.event_id, ev.event_type, d.status, d.attempts,
+               d.last_status_code, d.last_error, d.next_attempt_at, d.created_at, d.delivered_at
+        FROM webhook_deliveries d
+        JOIN webhook_endpoints w ON w.id = d.endpoint_id
+        JOIN domain_events ev ON ev.id = d.event_id
+        WHERE (?1 IS NULL OR d.endpoint_id = ?1) AND (?2 IS NULL OR d.status = ?2)
+        ORDER BY d.id DESC LIMIT ?3
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![endpoint_id, status, limit.unwrap_or(100).clamp(1, 1000)], |r| {
+        Ok(WebhookDelivery {
+            id: r.get(0)?,
+            endpoint_id: r.get(1)?,
+            endpoint_name: r.get(2)?,
+            event_id: r.get(3)?,
+            event_type: r.get(4)?,
+            status: r.get(5)?,
+            attempts: r.get(6)?,
+            last_status_code: r.get(7)?,
+            last_error: r.get(8)?,
+            next_attempt_at: r.get(9)?,
+            created_at: r.get(10)?,
+            delivered_at: r.get(11)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_webhook_delivery_attempts(handle: AppHandle, delivery_id: i64) -> Result<Vec<DeliveryAttempt>, String> {
+    let conn = open_conn(&handle)?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, attempted_at, status_code, error, duration_ms, response_excerpt
+        FROM webhook_attempts WHERE delivery_id = ?1 ORDER BY id
+        "#
+    ).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![delivery_id], |r| {
+        Ok(DeliveryAttempt {
+            id: r.get(0)?,
+            attempted_at: r.get(1)?,
+            status_code: r.get(2)?,
+            error: r.get(3)?,
+            duration_ms: r.get(4)?,
+            response_excerpt: r.get(5)?,
+        })
+    }).map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn retry_webhook_delivery(handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_conn(&handle)?;
+    let n = conn.execute(
+        "UPDATE webhook_deliveries SET status = 'pending', attempts = 0, next_attempt_at = datetime('now') WHERE id = ?1 AND status <> 'delivered'",
+        params![id],
+    ).map_err(|e| e.to_string())?;
+    if n == 0 {
+        return Err("Yeniden denenecek teslimat bulunamadı".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_webhook_event_types() -> Vec<String> {
+    events::EVENT_TYPES.iter().map(|t| t.to_string()).collect()
+}
+
+#[tauri::command]
+pub fn get_webhook_settings(handle: AppHandle) -> Result<WebhookSettings, String> {
+    let conn = open_conn(&handle)?;
+    load_settings(&conn)
+}
+
+#[tauri::command]
+pub fn update_webhook_settings(handle: AppHandle, settings: WebhookSettings) -> Result<(), String> {
+    if !(1..=120).contains(&settings.timeout_secs) {
+        return Err("Zaman aşımı 1 ile 120 saniye arasında olmalı".to_string());
+    }
+    if !(1..=20).contains(&settings.max_attempts) {
+        return Err("Deneme sayısı 1 ile 20 arasında olmalı".to_string());
+    }
+    if settings.retry_seconds < 1 || settings.poll_seconds < 1 {
+        return Err("Bekleme süreleri en az 1 saniye olmalı".to_string());
+    }
+    if settings.event_retention_days < 0 || settings.log_retention_days < 0 {
+        return Err("Saklama süresi negatif olamaz".to_string());
+    }
+    let conn = open_conn(&handle)?;
+    settings::store(&conn, SETTINGS_KEY, &settings)
+}
+
+// Zamanlayıcıyı beklemeden dağıt ve gönder
+#[tauri::command]
+pub async fn run_webhooks_now(handle: AppHandle) -> Result<DispatchReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run(&handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_webhook_dispatcher_status() -> Result<DispatcherStatus, String> {
+    STATUS.lock().map(|s| s.clone()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    struct Received {
+        headers: HashMap<String, String>,
+        body: String,
+    }
+
+    // her isteğe `status` ile yanıt veren ve istekleri toplayan HTTP sunucusu
+    fn http_stand_in(status: u16) -> (String, Arc<Mutex<Vec<Received>>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://127.0.0.1:{}/hook", server.server_addr().to_ip().unwrap().port());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let headers = request
+                    .headers()
+                    .iter()
+                    .map(|h| (h.field.as_str().as_str().to_ascii_lowercase(), h.value.as_str().to_string()))
+                    .collect();
+                sink.lock().unwrap().push(Received { headers, body });
+                let _ = request.respond(tiny_http::Response::from_string("yanıt").with_status_code(status));
+            }
+        });
+        (url, received)
+    }
+
+    fn test_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::ensure_schema(&conn).unwrap();
+        conn
+    }
+
+    fn add_endpoint(conn: &Connection, url: &str, events: &[&str]) -> (i64, String) {
+        let secret = new_secret();
+        conn.execute(
+            "INSERT INTO webhook_endpoints (name, url, events, secret) VALUES ('test', ?1, ?2, ?3)",
+            params![url, serde_json::to_string(events).unwrap(), secret],
+        )
+        .unwrap();
+        (conn.last_insert_rowid(), secret)
+    }
+
+    fn emit(conn: &Connection, event_type: &str, entity_id: i64) -> i64 {
+        events::emit(conn, event_type, entity_id, json!({ "id": entity_id })).unwrap()
+    }
+
+    fn settings() -> WebhookSettings {
+        WebhookSettings { timeout_secs: 5, max_attempts: 2, retry_seconds: 30, ..Default::default() }
+    }
+
+    fn deliveries(conn: &Connection) -> Vec<(i64, String, i64, Option<i64>, i64)> {
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT event_id, status, attempts, last_status_code,
+                       CAST(round((julianday(next_attempt_at) - julianday('now')) * 86400) AS INTEGER)
+                FROM webhook_deliveries ORDER BY id
+                "#,
+            )
+            .unwrap();
+        let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?))).unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    fn make_due(conn: &Connection) {
+        conn.execute("UPDATE webhook_deliveries SET next_attempt_at = datetime('now', '-1 second')", []).unwrap();
+    }
+
+    #[test]
+    fn delivery_is_signed_with_endpoint_secret() {
+        let (url, received) = http_stand_in(200);
+        let conn = test_conn();
+        let (_, secret) = add_endpoint(&conn, &url, &["intern.*"]);
+        let event_id = emit(&conn, events::INTERN_CREATED, 7);
+
+        let report = run_conn(&conn, &settings()).unwrap();
+        assert_eq!((report.fanned_out, report.delivered), (1, 1));
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let r = &received[0];
+        assert_eq!(r.headers["x-interntracker-event"], "intern.created");
+        let signature = &r.headers["x-interntracker-signature"];
+        let (t, v1) = signature.split_once(',').unwrap();
+        let t: i64 = t.strip_prefix("t=").unwrap().parse().unwrap();
+        assert_eq!(v1.strip_prefix("v1=").unwrap(), sign(&secret, t, &r.body));
+        assert_ne!(v1.strip_prefix("v1=").unwrap(), sign("başka", t, &r.body));
+
+        let body: Value = serde_json::from_str(&r.body).unwrap();
+        assert_eq!(body["id"], event_id);
+        assert_eq!(body["type"], "intern.created");
+        assert_eq!(body["data"]["id"], 7);
+    }
+
+    #[test]
+    fn only_matching_events_are_delivered() {
+        let (url, received) = http_stand_in(204);
+        let conn = test_conn();
+        add_endpoint(&conn, &url, &["assignment.created", "evaluation.*"]);
+        emit(&conn, events::INTERN_CREATED, 1);
+        let assignment = emit(&conn, events::ASSIGNMENT_CREATED, 2);
+        emit(&conn, events::ASSIGNMENT_COMPLETED, 2);
+        let evaluation = emit(&conn, events::EVALUATION_DELETED, 3);
+
+        let report = run_conn(&conn, &settings()).unwrap();
+        assert_eq!((report.fanned_out, report.delivered), (4, 2));
+        let ids: Vec<i64> = deliveries(&conn).iter().map(|d| d.0).collect();
+        assert_eq!(ids, vec![assignment, evaluation]);
+        let types: Vec<String> = received.lock().unwrap().iter().map(|r| r.headers["x-interntracker-event"].clone()).collect();
+        assert_eq!(types, vec!["assignment.created", "evaluation.deleted"]);
+
+        // dağıtılan olay ikinci kez teslimat üretmez
+        assert_eq!(run_conn(&conn, &settings()).unwrap().fanned_out, 0);
+        assert!(matches(&["*".to_string()], "intern.updated"));
+        assert!(!matches(&["intern.*".to_string()], "internship.created"));
+    }
+
+    #[test]
+    fn server_error_is_retried_with_backoff_and_logged() {
+        let (url, received) = http_stand_in(503);
+        let conn = test_conn();
+        add_endpoint(&conn, &url, &["*"]);
+        let event_id = emit(&conn, events::INTERN_UPDATED, 1);
+        let s = settings();
+
+        let report = run_conn(&conn, &s).unwrap();
+        assert_eq!((report.delivered, report.retrying), (0, 1));
+        let (id, status, attempts, code, wait) = deliveries(&conn).remove(0);
+        assert_eq!((id, status.as_str(), attempts, code), (event_id, "pending", 1, Some(503)));
+        assert!((s.retry_seconds - 2..=s.retry_seconds).contains(&wait));
+
+        // bekleme dolmadan yeniden denenmez
+        assert_eq!(run_conn(&conn, &s).unwrap().retrying, 0);
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        make_due(&conn);
+        assert_eq!(run_conn(&conn, &s).unwrap().failed, 1);
+        assert_eq!(deliveries(&conn)[0].1, "failed");
+
+        let delivery_id: i64 = conn.query_row("SELECT id FROM webhook_deliveries", [], |r| r.get(0)).unwrap();
+        let mut stmt = conn
+            .prepare("SELECT status_code, error, response_excerpt FROM webhook_attempts WHERE delivery_id = ?1 ORDER BY id")
+            .unwrap();
+        let attempts: Vec<(Option<i64>, Option<String>, Option<String>)> = stmt
+            .query_map(params![delivery_id], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(attempts.len(), 2);
+        for a in attempts {
+            assert_eq!(a, (Some(503), Some("HTTP 503".to_string()), Some("yanıt".to_string())));
+        }
+    }
+
+    #[test]
+    fn successful_delivery_is_logged_once() {
+        let (url, received) = http_stand_in(200);
+        let conn = test_conn();
+        add_endpoint(&conn, &url, &["*"]);
+        emit(&conn, events::INTERN_CREATED, 1);
+        let s = settings();
+
+        fan_out(&conn).unwrap();
+        let delivery_id: i64 = conn.query_row("SELECT id FROM webhook_deliveries", [], |r| r.get(0)).unwrap();
+        // başka tur aldıysa bu tur göndermez
+        assert!(claim(&conn, &s, delivery_id).unwrap());
+        assert_eq!(run_conn(&conn, &s).unwrap().delivered, 0);
+        assert!(received.lock().unwrap().is_empty());
+
+        make_due(&conn);
+        assert_eq!(run_conn(&conn, &s).unwrap().delivered, 1);
+        assert_eq!(run_conn(&conn, &s).unwrap().delivered, 0);
+        let (status, attempts, logged): (String, i64, i64) = conn
+            .query_row(
+                "SELECT status, attempts, (SELECT COUNT(*) FROM webhook_attempts) FROM webhook_deliveries",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!((status.as_str(), attempts, logged), ("delivered", 1, 1));
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn old_dispatched_events_are_purged() {
+        let conn = test_conn();
+        add_endpoint(&conn, "http://127.0.0.1:9/hook", &["*"]);
+        let old = emit(&conn, events::INTERN_CREATED, 1);
+        let old_pending = emit(&conn, events::INTERN_UPDATED, 1);
+        let recent = emit(&conn, events::INTERN_DELETED, 1);
+        fan_out(&conn).unwrap();
+        conn.execute(
+            "UPDATE webhook_deliveries SET status = 'delivered' WHERE event_id = ?1",
+            params![old],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE domain_events SET occurred_at = '2020-01-01T00:00:00Z' WHERE id IN (?1, ?2)",
+            params![old, old_pending],
+        )
+        .unwrap();
+        let undispatched = emit(&conn, events::INTERN_CREATED, 2);
+        conn.execute("UPDATE domain_events SET occurred_at = '2020-01-01T00:00:00Z' WHERE id = ?1", params![undispatched])
+            .unwrap();
+
+        purge_conn(&conn, &settings()).unwrap();
+        let mut stmt = conn.prepare("SELECT id FROM domain_events ORDER BY id").unwrap();
+        let left: Vec<i64> = stmt.query_map([], |r| r.get(0)).unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(left, vec![old_pending, recent, undispatched]);
+    }
+}
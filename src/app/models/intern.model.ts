@@ -11,4 +11,5 @@ export interface Intern {
   email: string;
   cv_path?: string;
   photo_path?: string;
+  period_id?: number;
 }
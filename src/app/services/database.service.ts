@@ -25,6 +25,8 @@ export interface Evaluation {
   etiket: string;
   puan: number;
   created_at?: string;
+  evaluator_id?: number | null;
+  evaluator_name?: string | null;
 }
 
 export interface InternFiles {
@@ -30,9 +30,8 @@ export class ReminderService {
 
   // ————— iç işler —————
 
+  // Görev son tarihi ve eksik not hatırlatmaları Rust tarafındaki zamanlayıcıda (reminders.rs)
   private async runChecks() {
-    await this.checkDeadlines();
-    await this.checkMissingNotes();
     await this.checkInternEndDates();
   }
 
@@ -54,53 +53,6 @@ export class ReminderService {
     localStorage.setItem(`notif_${key}`, new Date().toDateString());
   }
 
-  
-  private async checkDeadlines() {
-    if (!(await this.ensureNotifyPermission())) return;
-
-    const assignments = await this.db.getAssignments();
-    const today = new Date();
-    today.setHours(0, 0, 0, 0);
-    const THRESHOLD_DAYS = 3;
-
-    for (const a of assignments) {
-      if (!a?.due_date || a?.status === 'Completed') continue;
-      const diff = this.daysUntil(a.due_date, today);
-      if (diff > 0 && diff <= THRESHOLD_DAYS) {
-        const key = `deadline_${a.id ?? a.due_date}_${a.intern_id ?? ''}`;
-        if (!this.alreadyNotified(key)) {
-          await sendNotification({
-            title: 'Görev Hatırlatma',
-            body: `${a.project_type} teslimine ${diff} gün kaldı.`,
-          });
-          this.markNotified(key);
-        }
-      }
-    }
-  }
-
-private async checkMissingNotes() {
-  if (!(await this.ensureNotifyPermission())) return;
-
-  const today = new Date();
-  const y = today.getFullYear();
-  const m = String(today.getMonth() + 1).padStart(2, '0');
-  const d = String(today.getDate()).padStart(2, '0');
-  const ymd = `${y}-${m}-${d}`;
-
-
-  const missingCount = await this.db.countInternsMissingNoteFor(ymd);
-
-  if (missingCount > 0 && !this.alreadyNotified('missingNotes')) {
-    await sendNotification({
-      title: 'Not Eksik Hatırlatma',
-      body: `Bugün ${missingCount} stajyer için not girilmemiş.`,
-    });
-    this.markNotified('missingNotes');
-  }
-}
-
-  
   private async checkInternEndDates() {
     if (!(await this.ensureNotifyPermission())) return;
 